@@ -37,7 +37,7 @@ fn main() {
     let mut config = ClientConfig::new();
     config.add(SystemClientConfig::default().into());
 
-    let config = config.build();
+    let config = config.build().unwrap();
 
     let client = Client::new(config);
 
@@ -15,7 +15,7 @@ fn main() {
     let mut config = ClientConfig::new();
 
     config.add(SystemClientConfig::default().into());
-    let client = Client::new(config.build());
+    let client = Client::new(config.build().unwrap());
     let result = client.invoke::<bool>(
         &wrap_uri,
         "info",
@@ -0,0 +1,318 @@
+use polywrap_uri::Uri;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use wrap_manifest_schemas::{
+    abi::{Abi, TypeKind, TypeRef},
+    versions::{MethodDefinition, PropertyDefinition},
+};
+
+use crate::naming::{field_ident, ident, to_pascal_case, type_ident};
+
+/// Module re-exporting the items the generated code relies on.
+fn private() -> TokenStream {
+    quote! { ::polywrap_bindings::__private }
+}
+
+/// Generates the types and modules of the ABI.
+pub fn generate(abi: &Abi) -> Result<TokenStream, String> {
+    let generator = Generator { abi };
+    let mut items = vec![];
+
+    for object in &abi.objects {
+        items.push(generator.object(&object.type_, &object.comment, &object.properties)?);
+    }
+    if let Some(env) = &abi.env {
+        items.push(generator.object(&env.type_, &env.comment, &env.properties)?);
+    }
+    for enum_type in &abi.enums {
+        items.push(generator.enum_type(&enum_type.type_, &enum_type.comment, &enum_type.constants));
+    }
+    for object in &abi.imported_objects {
+        items.push(generator.object(&object.type_, &object.comment, &object.properties)?);
+    }
+    for env in &abi.imported_envs {
+        items.push(generator.object(&env.type_, &env.comment, &env.properties)?);
+    }
+    for enum_type in &abi.imported_enums {
+        items.push(generator.enum_type(&enum_type.type_, &enum_type.comment, &enum_type.constants));
+    }
+
+    if let Some(module) = &abi.module {
+        let env = abi.env.as_ref().map(|env| env.type_.as_str());
+        items.push(generator.module(
+            "",
+            None,
+            &module.comment,
+            module.methods.as_deref().unwrap_or_default(),
+            env,
+        )?);
+    }
+    for module in &abi.imported_modules {
+        let env = abi
+            .imported_envs
+            .iter()
+            .find(|env| env.namespace == module.namespace)
+            .map(|env| env.type_.as_str());
+        items.push(generator.module(
+            &module.namespace,
+            Some(&module.uri),
+            &module.comment,
+            module.methods.as_deref().unwrap_or_default(),
+            env,
+        )?);
+    }
+
+    Ok(quote! { #(#items)* })
+}
+
+struct Generator<'a> {
+    abi: &'a Abi,
+}
+
+impl Generator<'_> {
+    fn object(
+        &self,
+        name: &str,
+        comment: &Option<String>,
+        properties: &Option<Vec<Box<PropertyDefinition>>>,
+    ) -> Result<TokenStream, String> {
+        let private = private();
+        let ident = type_ident(name);
+        let doc = doc(comment);
+        let fields = self.fields(properties.as_deref().unwrap_or_default())?;
+
+        Ok(quote! {
+            #doc
+            #[derive(Clone, Debug, PartialEq, #private::serde::Serialize, #private::serde::Deserialize)]
+            #[serde(crate = "::polywrap_bindings::__private::serde")]
+            pub struct #ident {
+                #(#fields)*
+            }
+        })
+    }
+
+    fn fields(&self, properties: &[Box<PropertyDefinition>]) -> Result<Vec<TokenStream>, String> {
+        properties
+            .iter()
+            .map(|property| {
+                let name = property.name.as_deref().unwrap_or_default();
+                let ident = field_ident(name);
+                let type_ = self.type_tokens(&property.type_ref())?;
+                let doc = doc(&property.comment);
+                let rename = (ident.to_string().trim_start_matches("r#") != name)
+                    .then(|| quote! { #[serde(rename = #name)] });
+
+                Ok(quote! {
+                    #doc
+                    #rename
+                    pub #ident: #type_,
+                })
+            })
+            .collect()
+    }
+
+    fn enum_type(
+        &self,
+        name: &str,
+        comment: &Option<String>,
+        constants: &Option<Vec<String>>,
+    ) -> TokenStream {
+        let private = private();
+        let enum_ident = type_ident(name);
+        let doc = doc(comment);
+        let constants = constants.as_deref().unwrap_or_default();
+        let variants = constants
+            .iter()
+            .map(|constant| ident(constant))
+            .collect::<Vec<_>>();
+
+        quote! {
+            #doc
+            #[allow(non_camel_case_types)]
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+            pub enum #enum_ident {
+                #(#variants,)*
+            }
+
+            impl #private::serde::Serialize for #enum_ident {
+                fn serialize<S: #private::serde::Serializer>(
+                    &self,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_i32(*self as i32)
+                }
+            }
+
+            impl<'de> #private::serde::Deserialize<'de> for #enum_ident {
+                fn deserialize<D: #private::serde::Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Self, D::Error> {
+                    const CONSTANTS: &[(&str, #enum_ident)] = &[#((#constants, #enum_ident::#variants)),*];
+                    #private::deserialize_enum(deserializer, #name, CONSTANTS)
+                }
+            }
+        }
+    }
+
+    /// Generates a module and the arguments of its methods. Imported modules are prefixed with
+    /// their namespace and can be created with the URI they're imported from by default.
+    fn module(
+        &self,
+        namespace: &str,
+        uri: Option<&str>,
+        comment: &Option<String>,
+        methods: &[Box<MethodDefinition>],
+        env: Option<&str>,
+    ) -> Result<TokenStream, String> {
+        let private = private();
+        let prefix = to_pascal_case(namespace);
+        let ident = format_ident!("{prefix}Module");
+        let module_doc = doc(comment);
+
+        let mut args_structs = vec![];
+        let mut method_fns = vec![];
+        for method in methods {
+            let name = method.name.as_deref().unwrap_or_default();
+            let method_ident = field_ident(name);
+            let args_ident = format_ident!("{prefix}Args{}", to_pascal_case(name));
+            let args_doc = format!("Arguments of [`{ident}::{method_ident}`].");
+            let fields = self.fields(method.arguments.as_deref().unwrap_or_default())?;
+            let return_type = match &method.return_ {
+                Some(return_) => self.type_tokens(&return_.type_ref())?,
+                None => quote! { () },
+            };
+            let method_doc = doc(&method.comment);
+
+            args_structs.push(quote! {
+                #[doc = #args_doc]
+                #[derive(Clone, Debug, PartialEq, #private::serde::Serialize, #private::serde::Deserialize)]
+                #[serde(crate = "::polywrap_bindings::__private::serde")]
+                pub struct #args_ident {
+                    #(#fields)*
+                }
+            });
+            method_fns.push(quote! {
+                #method_doc
+                pub fn #method_ident(
+                    &self,
+                    args: &#args_ident,
+                    invoker: &dyn #private::Invoker,
+                ) -> Result<#return_type, #private::Error> {
+                    #private::invoke(invoker, &self.uri, #name, args, self.env.as_deref())
+                }
+            });
+        }
+
+        let with_env = env.map(|env| {
+            let env_ident = type_ident(env);
+            quote! {
+                /// Sets the env the methods of the module are invoked with.
+                pub fn with_env(mut self, env: &#env_ident) -> Result<Self, #private::Error> {
+                    self.env = Some(#private::encode_env(env)?);
+                    Ok(self)
+                }
+            }
+        });
+
+        let imported = uri
+            .map(|uri| {
+                uri.parse::<Uri>()
+                    .map_err(|e| format!("Invalid URI of the `{namespace}` module: {e}"))?;
+
+                Ok::<_, String>(quote! {
+                    impl #ident {
+                        /// URI the module is imported from.
+                        pub const URI: &'static str = #uri;
+                    }
+
+                    impl Default for #ident {
+                        fn default() -> Self {
+                            Self::new(#private::Uri::try_from(Self::URI).expect("URI is valid"))
+                        }
+                    }
+                })
+            })
+            .transpose()?;
+
+        Ok(quote! {
+            #(#args_structs)*
+
+            #module_doc
+            #[derive(Clone, Debug)]
+            pub struct #ident {
+                uri: #private::Uri,
+                env: Option<Vec<u8>>,
+            }
+
+            impl #ident {
+                pub fn new(uri: #private::Uri) -> Self {
+                    Self { uri, env: None }
+                }
+
+                pub fn uri(&self) -> &#private::Uri {
+                    &self.uri
+                }
+
+                #with_env
+
+                #(#method_fns)*
+            }
+
+            #imported
+        })
+    }
+
+    fn type_tokens(&self, type_ref: &TypeRef) -> Result<TokenStream, String> {
+        let private = private();
+        let type_ = match &type_ref.kind {
+            TypeKind::Scalar(scalar) => scalar_tokens(scalar)?,
+            TypeKind::Object(name) | TypeKind::Enum(name) | TypeKind::Unresolved(name) => {
+                if self.abi.resolve_type(name).is_none() {
+                    return Err(format!("Type `{name}` isn't declared in the ABI"));
+                }
+                let ident = type_ident(name);
+                quote! { #ident }
+            }
+            TypeKind::Array(item) => {
+                let item = self.type_tokens(item)?;
+                quote! { Vec<#item> }
+            }
+            TypeKind::Map { key, value } => {
+                let key = scalar_tokens(key)?;
+                let value = self.type_tokens(value)?;
+                quote! { #private::BTreeMap<#key, #value> }
+            }
+        };
+
+        Ok(if type_ref.required {
+            type_
+        } else {
+            quote! { Option<#type_> }
+        })
+    }
+}
+
+fn scalar_tokens(scalar: &str) -> Result<TokenStream, String> {
+    Ok(match scalar {
+        "String" => quote! { String },
+        "Boolean" => quote! { bool },
+        "Int" | "Int32" => quote! { i32 },
+        "Int8" => quote! { i8 },
+        "Int16" => quote! { i16 },
+        "UInt" | "UInt32" => quote! { u32 },
+        "UInt8" => quote! { u8 },
+        "UInt16" => quote! { u16 },
+        "Bytes" => quote! { ::polywrap_bindings::ByteBuf },
+        "BigInt" => quote! { ::polywrap_bindings::BigIntWrapper },
+        "BigNumber" => quote! { ::polywrap_bindings::BigNumber },
+        "JSON" => quote! { ::polywrap_bindings::JSONString },
+        scalar => return Err(format!("Unsupported scalar type `{scalar}`")),
+    })
+}
+
+fn doc(comment: &Option<String>) -> Option<TokenStream> {
+    comment.as_ref().map(|comment| {
+        let comment = comment.trim();
+        quote! { #[doc = #comment] }
+    })
+}
@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, LitStr};
+use wrap_manifest_schemas::{abi::Abi, deserialize::deserialize_wrap_manifest};
+
+mod generate;
+mod naming;
+
+/// Generate typed bindings to a wrap from its `wrap.info` manifest.
+/// The path of the manifest is relative to the root of the crate invoking the macro.
+#[proc_macro]
+pub fn wrap_bindings(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as LitStr);
+
+    match expand(&input.value()) {
+        Ok(expanded) => expanded.into(),
+        Err(message) => syn::Error::new(input.span(), message)
+            .to_compile_error()
+            .into(),
+    }
+}
+
+fn expand(path: &str) -> Result<proc_macro2::TokenStream, String> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = PathBuf::from(manifest_dir).join(path);
+
+    let manifest =
+        std::fs::read(&path).map_err(|e| format!("Failed to read `{}`: {e}", path.display()))?;
+    let manifest = deserialize_wrap_manifest(&manifest, None)
+        .map_err(|e| format!("Failed to deserialize `{}`: {e}", path.display()))?;
+    let abi = Abi::try_from(&manifest.abi)
+        .map_err(|e| format!("Failed to parse the ABI of `{}`: {e}", path.display()))?;
+
+    let bindings = generate::generate(&abi)?;
+    // Rebuilds the bindings whenever the manifest changes
+    let path = path.to_string_lossy();
+
+    Ok(quote! {
+        const _: &[u8] = include_bytes!(#path);
+
+        #bindings
+    })
+}
@@ -0,0 +1,60 @@
+use proc_macro2::{Ident, Span};
+
+/// Converts a camelCase ABI name into snake_case, e.g. `tryResolveUri` into `try_resolve_uri`.
+pub fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake_case = String::new();
+
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).map_or(false, |next| next.is_lowercase());
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_is_lowercase)
+            {
+                snake_case.push('_');
+            }
+        }
+        snake_case.extend(c.to_lowercase());
+    }
+
+    snake_case
+}
+
+/// Converts an ABI name into PascalCase, dropping the `_` separating namespaces from types,
+/// e.g. `Http_Request` into `HttpRequest` or `tryResolveUri` into `TryResolveUri`.
+pub fn to_pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Returns an identifier for the name, escaping it if it's a keyword.
+pub fn ident(name: &str) -> Ident {
+    if syn::parse_str::<Ident>(name).is_ok() {
+        return Ident::new(name, Span::call_site());
+    }
+
+    match name {
+        // Can't be raw identifiers
+        "self" | "Self" | "super" | "crate" | "_" => {
+            Ident::new(&format!("{name}_"), Span::call_site())
+        }
+        _ => Ident::new_raw(name, Span::call_site()),
+    }
+}
+
+pub fn type_ident(name: &str) -> Ident {
+    ident(&to_pascal_case(name))
+}
+
+pub fn field_ident(name: &str) -> Ident {
+    ident(&to_snake_case(name))
+}
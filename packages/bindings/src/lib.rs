@@ -0,0 +1,115 @@
+//! Typed Rust bindings to wraps.
+//!
+//! [`wrap_bindings!`] reads the `wrap.info` manifest of a wrap at compile time and generates,
+//! in the module it's invoked in:
+//!
+//! * a struct for every object and env type of the ABI, and an enum for every enum type,
+//! * a `Module` struct with a typed method for every method of the wrap's module,
+//!   along with an `Args<Method>` struct for its arguments,
+//! * the same for every imported module and type, prefixed with their namespace,
+//!   e.g. `HttpModule`, `HttpArgsGet` or `HttpRequest` for the `Http` namespace.
+//!
+//! ```ignore
+//! mod http_resolver {
+//!     polywrap_bindings::wrap_bindings!("wraps/http-resolver/wrap.info");
+//! }
+//!
+//! let module = http_resolver::HttpModule::default();
+//! let response = module.get(
+//!     &http_resolver::HttpArgsGet {
+//!         url: "https://polywrap.io".to_string(),
+//!         request: None,
+//!     },
+//!     &client,
+//! )?;
+//! ```
+//!
+//! The path of the manifest is relative to the root of the crate invoking the macro.
+
+pub use polywrap_bindings_macros::wrap_bindings;
+
+/// Types of the `Bytes`, `BigInt`, `BigNumber` and `JSON` values of generated bindings.
+pub use polywrap_msgpack_serde::{serde_bytes::ByteBuf, BigIntWrapper, BigNumber, JSONString};
+
+/// Items used by the code generated by [`wrap_bindings!`], not meant to be used directly.
+#[doc(hidden)]
+pub mod __private {
+    use std::{fmt, marker::PhantomData};
+
+    pub use polywrap_core::{error::Error, invoker::Invoker, uri::Uri};
+    pub use serde;
+    pub use std::collections::BTreeMap;
+
+    use polywrap_msgpack_serde::{from_slice, to_vec};
+    use serde::{
+        de::{self, DeserializeOwned, Visitor},
+        Deserializer, Serialize,
+    };
+
+    pub fn invoke<A: Serialize, R: DeserializeOwned>(
+        invoker: &dyn Invoker,
+        uri: &Uri,
+        method: &str,
+        args: &A,
+        env: Option<&[u8]>,
+    ) -> Result<R, Error> {
+        let args = to_vec(args)?;
+        let result = invoker.invoke_raw(uri, method, Some(&args), env, None)?;
+
+        from_slice(&result).map_err(Error::MsgpackError)
+    }
+
+    pub fn encode_env<E: Serialize>(env: &E) -> Result<Vec<u8>, Error> {
+        Ok(to_vec(env)?)
+    }
+
+    /// Deserializes a wrap enum, encoded as the index of its constant, or as its name.
+    pub fn deserialize_enum<'de, D: Deserializer<'de>, T: Copy>(
+        deserializer: D,
+        name: &'static str,
+        constants: &'static [(&'static str, T)],
+    ) -> Result<T, D::Error> {
+        deserializer.deserialize_any(EnumVisitor {
+            name,
+            constants,
+            _marker: PhantomData,
+        })
+    }
+
+    struct EnumVisitor<T: 'static> {
+        name: &'static str,
+        constants: &'static [(&'static str, T)],
+        _marker: PhantomData<T>,
+    }
+
+    impl<'de, T: Copy> Visitor<'de> for EnumVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a constant of {}", self.name)
+        }
+
+        fn visit_u64<E: de::Error>(self, index: u64) -> Result<T, E> {
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| self.constants.get(index))
+                .map(|(_, constant)| *constant)
+                .ok_or_else(|| E::invalid_value(de::Unexpected::Unsigned(index), &self))
+        }
+
+        fn visit_i64<E: de::Error>(self, index: i64) -> Result<T, E> {
+            match u64::try_from(index) {
+                Ok(index) => self.visit_u64(index),
+                Err(_) => Err(E::invalid_value(de::Unexpected::Signed(index), &self)),
+            }
+        }
+
+        fn visit_str<E: de::Error>(self, name: &str) -> Result<T, E> {
+            self.constants
+                .iter()
+                .find(|(constant_name, _)| *constant_name == name)
+                .map(|(_, constant)| *constant)
+                .ok_or_else(|| E::invalid_value(de::Unexpected::Str(name), &self))
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use polywrap_bindings::{BigIntWrapper, ByteBuf};
+use polywrap_core::{
+    error::Error, interface_implementation::InterfaceImplementations, invoker::Invoker,
+    macros::uri, resolution::uri_resolution_context::UriResolutionContext, uri::Uri,
+};
+use polywrap_msgpack_serde::{from_slice, to_vec};
+use serde::Serialize;
+use serde_json::json;
+
+mod items {
+    polywrap_bindings::wrap_bindings!("tests/fixtures/items/wrap.info");
+}
+
+mod http_resolver {
+    polywrap_bindings::wrap_bindings!("../default-config/src/embeds/http_resolver/wrap.info");
+}
+
+mod ipfs_http_client {
+    polywrap_bindings::wrap_bindings!("../default-config/src/embeds/ipfs_http_client/wrap.info");
+}
+
+struct Invocation {
+    uri: Uri,
+    method: String,
+    args: Vec<u8>,
+    env: Option<Vec<u8>>,
+}
+
+/// Records the invocations it receives and answers them with the same response.
+struct MockInvoker {
+    response: Vec<u8>,
+    invocations: Mutex<Vec<Invocation>>,
+}
+
+impl MockInvoker {
+    fn new<T: Serialize>(response: &T) -> Self {
+        Self {
+            response: to_vec(response).unwrap(),
+            invocations: Mutex::new(vec![]),
+        }
+    }
+
+    fn last_invocation(&self) -> Invocation {
+        self.invocations.lock().unwrap().pop().unwrap()
+    }
+}
+
+impl Invoker for MockInvoker {
+    fn invoke_raw(
+        &self,
+        uri: &Uri,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        _: Option<Arc<Mutex<UriResolutionContext>>>,
+    ) -> Result<Vec<u8>, Error> {
+        self.invocations.lock().unwrap().push(Invocation {
+            uri: uri.clone(),
+            method: method.to_string(),
+            args: args.unwrap().to_vec(),
+            env: env.map(|env| env.to_vec()),
+        });
+        Ok(self.response.clone())
+    }
+
+    fn get_implementations(&self, _: &Uri) -> Result<Vec<Uri>, Error> {
+        Ok(vec![])
+    }
+
+    fn get_interfaces(&self) -> Option<InterfaceImplementations> {
+        None
+    }
+
+    fn get_env_by_uri(&self, _: &Uri) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+fn get_item() -> items::Item {
+    items::Item {
+        id: BigIntWrapper("123456789012345678901234567890".parse().unwrap()),
+        amount: Some("1.5".parse().unwrap()),
+        data: ByteBuf::from(vec![1, 2, 255]),
+        meta: Some(json!({ "source": "test" }).into()),
+        kind: items::Kind::LARGE,
+        tags: Some(vec!["a".to_string()]),
+        counts: BTreeMap::from([("x".to_string(), 200)]),
+        r#type: None,
+        created_at: Some(1),
+    }
+}
+
+#[test]
+fn methods_encode_args_and_decode_results() {
+    let invoker = MockInvoker::new(&get_item());
+    let args = items::ArgsEcho {
+        item: get_item(),
+        count: Some(-3),
+    };
+
+    let result = items::Module::new(uri!("mock/items"))
+        .echo(&args, &invoker)
+        .unwrap();
+
+    assert_eq!(result, get_item());
+    let invocation = invoker.last_invocation();
+    assert_eq!(invocation.uri, uri!("mock/items"));
+    assert_eq!(invocation.method, "echo");
+    assert_eq!(
+        from_slice::<items::ArgsEcho>(&invocation.args).unwrap(),
+        args
+    );
+    assert_eq!(invocation.env, None);
+}
+
+#[test]
+fn enums_are_encoded_as_their_index() {
+    let encoded = to_vec(&items::Kind::LARGE).unwrap();
+
+    assert_eq!(from_slice::<u32>(&encoded).unwrap(), 1);
+    assert_eq!(
+        from_slice::<items::Kind>(&encoded).unwrap(),
+        items::Kind::LARGE
+    );
+    assert_eq!(
+        from_slice::<items::Kind>(&to_vec(&"SMALL").unwrap()).unwrap(),
+        items::Kind::SMALL
+    );
+    assert!(from_slice::<items::Kind>(&to_vec(&2).unwrap()).is_err());
+}
+
+#[test]
+fn modules_are_invoked_with_their_env() {
+    let invoker = MockInvoker::new(&None::<Vec<items::Item>>);
+    let env = items::Env {
+        prefix: "inventory".to_string(),
+    };
+
+    let result = items::Module::new(uri!("mock/items"))
+        .with_env(&env)
+        .unwrap()
+        .find_by_kind(&items::ArgsFindByKind { kind: None }, &invoker)
+        .unwrap();
+
+    assert_eq!(result, None);
+    let invocation = invoker.last_invocation();
+    assert_eq!(invocation.method, "findByKind");
+    assert_eq!(
+        from_slice::<items::Env>(&invocation.env.unwrap()).unwrap(),
+        env
+    );
+}
+
+#[test]
+fn imported_modules_default_to_their_uri() {
+    let invoker = MockInvoker::new(&true);
+    let module = items::LoggerModule::default();
+
+    let result = module
+        .log(
+            &items::LoggerArgsLog {
+                level: items::LoggerLogLevel::WARN,
+                message: "low stock".to_string(),
+            },
+            &invoker,
+        )
+        .unwrap();
+
+    assert!(result);
+    assert_eq!(
+        items::LoggerModule::URI,
+        "wrap://ens/wraps.eth:logger@1.0.0"
+    );
+    assert_eq!(module.uri(), &uri!("wrap://ens/wraps.eth:logger@1.0.0"));
+    assert_eq!(invoker.last_invocation().uri, *module.uri());
+}
+
+#[test]
+fn bindings_of_published_wraps() {
+    let response = http_resolver::HttpResponse {
+        status: 200,
+        status_text: "OK".to_string(),
+        headers: None,
+        body: Some("wrap".to_string()),
+    };
+    let invoker = MockInvoker::new(&response);
+    let args = http_resolver::HttpArgsGet {
+        url: "https://polywrap.io".to_string(),
+        request: Some(http_resolver::HttpRequest {
+            headers: Some(BTreeMap::from([(
+                "Accept".to_string(),
+                "text/plain".to_string(),
+            )])),
+            url_params: None,
+            response_type: http_resolver::HttpResponseType::TEXT,
+            body: None,
+            form_data: None,
+            timeout: Some(1000),
+        }),
+    };
+
+    let result = http_resolver::HttpModule::default()
+        .get(&args, &invoker)
+        .unwrap();
+
+    assert_eq!(result, Some(response));
+    assert_eq!(
+        from_slice::<http_resolver::HttpArgsGet>(&invoker.last_invocation().args).unwrap(),
+        args
+    );
+
+    let invoker = MockInvoker::new(&ipfs_http_client::ResolveResult {
+        cid: "Qm".to_string(),
+        provider: "https://ipfs.io".to_string(),
+    });
+    let result = ipfs_http_client::Module::new(uri!("mock/ipfs"))
+        .resolve(
+            &ipfs_http_client::ArgsResolve {
+                cid: "Qm".to_string(),
+                ipfs_provider: "https://ipfs.io".to_string(),
+                timeout: None,
+                resolve_options: None,
+            },
+            &invoker,
+        )
+        .unwrap();
+
+    assert_eq!(result.provider, "https://ipfs.io");
+}
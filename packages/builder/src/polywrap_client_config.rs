@@ -2,13 +2,21 @@ use std::{collections::HashMap, sync::Arc};
 
 use polywrap_core::{
     client::{CoreClientConfig, CoreClientConfigBuilder},
+    env_inheritance::EnvInheritance,
+    env_merge::EnvMergeStrategy,
+    error::Error,
+    interceptor::InvokeInterceptor,
     interface_implementation::InterfaceImplementations,
     package::WrapPackage,
     resolution::uri_resolver::UriResolver,
+    resource_limits::ResourceLimits,
     uri::Uri,
+    wasi_policy::WasiPolicy,
+    wrap_log::WrapLogSink,
     wrapper::Wrapper,
 };
 use polywrap_resolvers::static_resolver::{StaticResolver, StaticResolverLike};
+use tokio::runtime::Handle;
 
 use crate::{PolywrapBaseResolver, PolywrapBaseResolverOptions, ClientConfigBuilder};
 
@@ -17,10 +25,19 @@ use crate::{PolywrapBaseResolver, PolywrapBaseResolverOptions, ClientConfigBuild
 pub struct ClientConfig {
     pub interfaces: Option<InterfaceImplementations>,
     pub envs: Option<HashMap<Uri, Vec<u8>>>,
+    pub env_inheritance: Option<EnvInheritance>,
+    pub env_merge_strategy: Option<EnvMergeStrategy>,
     pub wrappers: Option<Vec<(Uri, Arc<dyn Wrapper>)>>,
     pub packages: Option<Vec<(Uri, Arc<dyn WrapPackage>)>>,
     pub redirects: Option<HashMap<Uri, Uri>>,
     pub resolvers: Option<Vec<Arc<dyn UriResolver>>>,
+    pub interceptors: Option<Vec<Arc<dyn InvokeInterceptor>>>,
+    pub resource_limits: Option<ResourceLimits>,
+    pub uri_resource_limits: Option<HashMap<Uri, ResourceLimits>>,
+    pub uri_wasi_policies: Option<HashMap<Uri, WasiPolicy>>,
+    pub log_sink: Option<Arc<dyn WrapLogSink>>,
+    pub validate_args: Option<bool>,
+    pub executor: Option<Handle>,
 }
 
 impl ClientConfig {
@@ -30,10 +47,19 @@ impl ClientConfig {
         Self {
             interfaces: None,
             envs: None,
+            env_inheritance: None,
+            env_merge_strategy: None,
             wrappers: None,
             packages: None,
             redirects: None,
             resolvers: None,
+            interceptors: None,
+            resource_limits: None,
+            uri_resource_limits: None,
+            uri_wasi_policies: None,
+            log_sink: None,
+            validate_args: None,
+            executor: None,
         }
     }
 
@@ -72,6 +98,14 @@ impl ClientConfigBuilder for ClientConfig {
             self.add_envs(e);
         };
 
+        if let Some(inheritance) = config.env_inheritance {
+            self.set_env_inheritance(inheritance);
+        }
+
+        if let Some(strategy) = config.env_merge_strategy {
+            self.set_env_merge_strategy(strategy);
+        }
+
         if let Some(i) = config.interfaces {
             for (interface, implementation_uris) in i.into_iter() {
                 let interface_uri: Uri = interface.try_into().unwrap();
@@ -95,6 +129,38 @@ impl ClientConfigBuilder for ClientConfig {
             self.add_resolvers(resolvers);
         }
 
+        if let Some(interceptors) = config.interceptors {
+            self.add_interceptors(interceptors);
+        }
+
+        if let Some(limits) = config.resource_limits {
+            self.set_resource_limits(limits);
+        }
+
+        if let Some(uri_limits) = config.uri_resource_limits {
+            for (uri, limits) in uri_limits.into_iter() {
+                self.add_resource_limits(uri, limits);
+            }
+        }
+
+        if let Some(uri_policies) = config.uri_wasi_policies {
+            for (uri, policy) in uri_policies.into_iter() {
+                self.add_wasi_policy(uri, policy);
+            }
+        }
+
+        if let Some(log_sink) = config.log_sink {
+            self.set_log_sink(log_sink);
+        }
+
+        if let Some(validate_args) = config.validate_args {
+            self.set_args_validation(validate_args);
+        }
+
+        if let Some(executor) = config.executor {
+            self.set_executor(executor);
+        }
+
         self
     }
 
@@ -128,6 +194,16 @@ impl ClientConfigBuilder for ClientConfig {
         self
     }
 
+    fn set_env_inheritance(&mut self, inheritance: EnvInheritance) -> &mut Self {
+        self.env_inheritance = Some(inheritance);
+        self
+    }
+
+    fn set_env_merge_strategy(&mut self, strategy: EnvMergeStrategy) -> &mut Self {
+        self.env_merge_strategy = Some(strategy);
+        self
+    }
+
     fn add_interface_implementation(
         &mut self,
         interface_uri: Uri,
@@ -252,6 +328,15 @@ impl ClientConfigBuilder for ClientConfig {
         self
     }
 
+    fn try_add_package(
+        &mut self,
+        uri: Uri,
+        package: Arc<dyn WrapPackage>,
+    ) -> Result<&mut Self, Error> {
+        package.validate()?;
+        Ok(self.add_package(uri, package))
+    }
+
     fn add_packages(&mut self, packages: Vec<(Uri, Arc<dyn WrapPackage>)>) -> &mut Self {
         for (uri, package) in packages.into_iter() {
             self.add_package(uri, package);
@@ -322,26 +407,105 @@ impl ClientConfigBuilder for ClientConfig {
         }
         self
     }
+
+    fn add_interceptor(&mut self, interceptor: Arc<dyn InvokeInterceptor>) -> &mut Self {
+        match self.interceptors.as_mut() {
+            Some(interceptors) => {
+                interceptors.push(interceptor);
+            }
+            None => {
+                self.interceptors = Some(vec![interceptor]);
+            }
+        };
+
+        self
+    }
+
+    fn add_interceptors(&mut self, interceptors: Vec<Arc<dyn InvokeInterceptor>>) -> &mut Self {
+        for interceptor in interceptors.into_iter() {
+            self.add_interceptor(interceptor);
+        }
+        self
+    }
+
+    fn set_resource_limits(&mut self, limits: ResourceLimits) -> &mut Self {
+        self.resource_limits = Some(limits);
+        self
+    }
+
+    fn add_resource_limits(&mut self, uri: Uri, limits: ResourceLimits) -> &mut Self {
+        if let Some(uri_limits) = self.uri_resource_limits.as_mut() {
+            uri_limits.insert(uri, limits);
+        } else {
+            self.uri_resource_limits = Some(HashMap::from([(uri, limits)]));
+        }
+
+        self
+    }
+
+    fn add_wasi_policy(&mut self, uri: Uri, policy: WasiPolicy) -> &mut Self {
+        if let Some(uri_policies) = self.uri_wasi_policies.as_mut() {
+            uri_policies.insert(uri, policy);
+        } else {
+            self.uri_wasi_policies = Some(HashMap::from([(uri, policy)]));
+        }
+
+        self
+    }
+
+    fn set_log_sink(&mut self, log_sink: Arc<dyn WrapLogSink>) -> &mut Self {
+        self.log_sink = Some(log_sink);
+        self
+    }
+
+    fn set_args_validation(&mut self, enabled: bool) -> &mut Self {
+        self.validate_args = Some(enabled);
+        self
+    }
+
+    fn set_executor(&mut self, executor: Handle) -> &mut Self {
+        self.executor = Some(executor);
+        self
+    }
 }
 
 impl CoreClientConfigBuilder for ClientConfig {
-    fn build(self) -> CoreClientConfig {
+    fn build(self) -> Result<CoreClientConfig, Error> {
+        for (uri, package) in self.packages.iter().flatten() {
+            package.validate().map_err(|e| {
+                Error::ManifestError(format!(
+                    "Package added at `{uri}` doesn't match its manifest: {e}"
+                ))
+            })?;
+        }
+
         // We first build the resolver because it needs a reference to self
         // this way we don't need to clone `envs`, and `interfaces`.
-        CoreClientConfig {
+        Ok(CoreClientConfig {
             resolver: PolywrapBaseResolver::new(PolywrapBaseResolverOptions {
                 static_resolver: self.build_static_resolver(),
                 dynamic_resolvers: self.resolvers,
                 ..Default::default()
             }),
             envs: self.envs,
+            env_inheritance: self.env_inheritance,
+            env_merge_strategy: self.env_merge_strategy,
             interfaces: self.interfaces,
-        }
+            interceptors: self.interceptors,
+            resource_limits: self.resource_limits,
+            uri_resource_limits: self.uri_resource_limits,
+            uri_wasi_policies: self.uri_wasi_policies,
+            log_sink: self.log_sink,
+            validate_args: self.validate_args,
+            executor: self.executor,
+        })
     }
 }
 
-impl Into<CoreClientConfig> for ClientConfig {
-    fn into(self) -> CoreClientConfig {
-        self.build()
+impl TryFrom<ClientConfig> for CoreClientConfig {
+    type Error = Error;
+
+    fn try_from(config: ClientConfig) -> Result<Self, Self::Error> {
+        config.build()
     }
 }
@@ -1,9 +1,12 @@
 use std::{collections::HashMap, sync::Arc};
 
 use polywrap_core::{
-    client::CoreClientConfigBuilder, package::WrapPackage, resolution::uri_resolver::UriResolver,
-    uri::Uri, wrapper::Wrapper,
+    client::CoreClientConfigBuilder, env_inheritance::EnvInheritance, env_merge::EnvMergeStrategy,
+    error::Error, interceptor::InvokeInterceptor, package::WrapPackage,
+    resolution::uri_resolver::UriResolver, resource_limits::ResourceLimits, uri::Uri,
+    wasi_policy::WasiPolicy, wrap_log::WrapLogSink, wrapper::Wrapper,
 };
+use tokio::runtime::Handle;
 
 use crate::ClientConfig;
 
@@ -38,6 +41,22 @@ pub trait ClientConfigBuilder: CoreClientConfigBuilder {
     /// * `uri` - The `Uri` of the environment entry to be removed.
     fn remove_env(&mut self, uri: &Uri) -> &mut Self;
 
+    /// Sets which env wraps subinvoked by other wraps receive
+    /// when no env is configured for them.
+    ///
+    /// # Arguments
+    ///
+    /// * `inheritance` - The `EnvInheritance` policy of the client.
+    fn set_env_inheritance(&mut self, inheritance: EnvInheritance) -> &mut Self;
+
+    /// Sets how the env passed to an invocation is combined with the envs
+    /// configured for the invoked `Uri` and the `Uri`s it is redirected to.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - The `EnvMergeStrategy` of the client.
+    fn set_env_merge_strategy(&mut self, strategy: EnvMergeStrategy) -> &mut Self;
+
     /// Adds an interface implementation entry.
     ///
     /// # Arguments
@@ -99,6 +118,7 @@ pub trait ClientConfigBuilder: CoreClientConfigBuilder {
 
     /// Embeds a `WrapPackage` to the config.
     /// When invoking this package's `Uri`, the embedded local instance will create a `Wrapper` and invoke it.
+    /// `build` fails if the package doesn't match its manifest, e.g. a plugin missing methods of its ABI.
     ///
     /// # Arguments
     ///
@@ -106,6 +126,19 @@ pub trait ClientConfigBuilder: CoreClientConfigBuilder {
     /// * `package` - A `WrapPackage` instance.
     fn add_package(&mut self, uri: Uri, package: Arc<dyn WrapPackage>) -> &mut Self;
 
+    /// Embeds a `WrapPackage` to the config like `add_package`, if it matches its manifest,
+    /// e.g. a plugin implementing the methods of its ABI, and only them, failing right away otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - The `Uri` of the `WrapPackage`.
+    /// * `package` - A `WrapPackage` instance.
+    fn try_add_package(
+        &mut self,
+        uri: Uri,
+        package: Arc<dyn WrapPackage>,
+    ) -> Result<&mut Self, Error>;
+
     /// Embeds several `WrapPackage`s to the config.
     ///
     /// # Arguments
@@ -154,4 +187,68 @@ pub trait ClientConfigBuilder: CoreClientConfigBuilder {
     ///
     /// * `resolvers` - A list of UriResolver instances.
     fn add_resolvers(&mut self, resolvers: Vec<Arc<dyn UriResolver>>) -> &mut Self;
+
+    /// Adds an interceptor that is called around every invocation, subinvocations included.
+    /// Interceptors are called in the order they were added.
+    ///
+    /// # Arguments
+    ///
+    /// * `interceptor` - An InvokeInterceptor instance.
+    fn add_interceptor(&mut self, interceptor: Arc<dyn InvokeInterceptor>) -> &mut Self;
+
+    /// Adds multiple interceptors to the configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `interceptors` - A list of InvokeInterceptor instances.
+    fn add_interceptors(&mut self, interceptors: Vec<Arc<dyn InvokeInterceptor>>) -> &mut Self;
+
+    /// Sets the resource limits applied to every Wasm wrap.
+    ///
+    /// # Arguments
+    ///
+    /// * `limits` - The `ResourceLimits` of every wrap.
+    fn set_resource_limits(&mut self, limits: ResourceLimits) -> &mut Self;
+
+    /// Sets the resource limits of a specific Wasm wrap.
+    /// Limits left unset fall back to the ones of every wrap.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - The `Uri` of the wrap.
+    /// * `limits` - The `ResourceLimits` of the wrap.
+    fn add_resource_limits(&mut self, uri: Uri, limits: ResourceLimits) -> &mut Self;
+
+    /// Grants WASI capabilities to a specific Wasm wrap.
+    /// Wraps without a policy are denied every capability.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - The `Uri` of the wrap.
+    /// * `policy` - The `WasiPolicy` of the wrap.
+    fn add_wasi_policy(&mut self, uri: Uri, policy: WasiPolicy) -> &mut Self;
+
+    /// Sets the sink receiving the debug logs and aborts of wraps,
+    /// which are emitted as `tracing` events by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `log_sink` - The `WrapLogSink` receiving the events.
+    fn set_log_sink(&mut self, log_sink: Arc<dyn WrapLogSink>) -> &mut Self;
+
+    /// Enables or disables the validation of invocation arguments against the ABI of the
+    /// invoked wrap. Invalid arguments fail with `Error::ArgsValidationError` before the wrap executes.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether arguments are validated.
+    fn set_args_validation(&mut self, enabled: bool) -> &mut Self;
+
+    /// Sets the Tokio runtime driving the async methods of plugins invoked synchronously.
+    /// It must be multi-threaded; by default, a runtime shared by every client is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `executor` - `Handle` of the runtime.
+    fn set_executor(&mut self, executor: Handle) -> &mut Self;
 }
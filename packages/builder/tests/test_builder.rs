@@ -1,7 +1,17 @@
 use std::{collections::HashMap, sync::Arc};
 
 use polywrap_client_builder::{ClientConfig, ClientConfigBuilder};
-use polywrap_core::{macros::uri, package::WrapPackage, uri::Uri, wrapper::Wrapper};
+use polywrap_core::{
+    env_inheritance::EnvInheritance,
+    env_merge::EnvMergeStrategy,
+    macros::uri,
+    package::WrapPackage,
+    resource_limits::ResourceLimits,
+    uri::Uri,
+    wasi_policy::{PreopenedDir, WasiPolicy},
+    wrap_log::TracingLogSink,
+    wrapper::Wrapper,
+};
 use polywrap_msgpack_serde::to_vec;
 use polywrap_tests_utils::mocks::{
     get_different_mock_package, get_different_mock_wrapper, get_mock_invoker, get_mock_package,
@@ -66,6 +76,35 @@ fn test_env_methods() {
     assert!(builder.envs.is_none());
 }
 
+#[test]
+fn test_env_inheritance() {
+    let mut builder = ClientConfig::new();
+    assert!(builder.env_inheritance.is_none());
+
+    builder.set_env_inheritance(EnvInheritance::Fallback);
+    assert_eq!(builder.env_inheritance, Some(EnvInheritance::Fallback));
+
+    let mut other_builder = ClientConfig::new();
+    other_builder.add(builder);
+    assert_eq!(
+        other_builder.env_inheritance,
+        Some(EnvInheritance::Fallback)
+    );
+}
+
+#[test]
+fn test_env_merge_strategy() {
+    let mut builder = ClientConfig::new();
+    assert!(builder.env_merge_strategy.is_none());
+
+    builder.set_env_merge_strategy(EnvMergeStrategy::Deep);
+    assert_eq!(builder.env_merge_strategy, Some(EnvMergeStrategy::Deep));
+
+    let mut other_builder = ClientConfig::new();
+    other_builder.add(builder);
+    assert_eq!(other_builder.env_merge_strategy, Some(EnvMergeStrategy::Deep));
+}
+
 #[test]
 fn test_interface_implementation_methods() {
     let interface_uri = uri!("wrap://mock/interface");
@@ -275,3 +314,78 @@ fn test_resolvers() {
     builder.add_resolver(Arc::new(DifferentMockResolver {}));
     assert_eq!(builder.resolvers.unwrap().len(), 2);
 }
+
+#[test]
+fn test_resource_limits() {
+    let mut builder = ClientConfig::new();
+    let uri = uri!("wrap://mock/wrapper");
+    assert!(builder.resource_limits.is_none());
+    assert!(builder.uri_resource_limits.is_none());
+
+    let limits = ResourceLimits {
+        fuel: Some(1_000),
+        max_memory_pages: None,
+    };
+    builder.set_resource_limits(limits);
+    assert_eq!(builder.resource_limits, Some(limits));
+
+    let uri_limits = ResourceLimits {
+        fuel: None,
+        max_memory_pages: Some(16),
+    };
+    builder.add_resource_limits(uri.clone(), uri_limits);
+    assert_eq!(
+        builder.uri_resource_limits.unwrap().get(&uri),
+        Some(&uri_limits)
+    );
+}
+
+#[test]
+fn test_wasi_policies() {
+    let mut builder = ClientConfig::new();
+    let uri = uri!("wrap://mock/wrapper");
+    assert!(builder.uri_wasi_policies.is_none());
+
+    let policy = WasiPolicy {
+        clock: true,
+        preopened_dirs: vec![PreopenedDir {
+            host_path: "/tmp/wrap".into(),
+            guest_path: "/data".to_string(),
+            read_only: true,
+        }],
+        ..Default::default()
+    };
+    builder.add_wasi_policy(uri.clone(), policy.clone());
+
+    let mut other_builder = ClientConfig::new();
+    other_builder.add(builder);
+    assert_eq!(
+        other_builder.uri_wasi_policies.unwrap().get(&uri),
+        Some(&policy)
+    );
+}
+
+#[test]
+fn test_log_sink() {
+    let mut builder = ClientConfig::new();
+    assert!(builder.log_sink.is_none());
+
+    builder.set_log_sink(Arc::new(TracingLogSink));
+
+    let mut other_builder = ClientConfig::new();
+    other_builder.add(builder);
+    assert!(other_builder.log_sink.is_some());
+}
+
+#[test]
+fn test_args_validation() {
+    let mut builder = ClientConfig::new();
+    assert!(builder.validate_args.is_none());
+
+    builder.set_args_validation(true);
+    assert_eq!(builder.validate_args, Some(true));
+
+    let mut other_builder = ClientConfig::new();
+    other_builder.add(builder);
+    assert_eq!(other_builder.validate_args, Some(true));
+}
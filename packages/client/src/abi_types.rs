@@ -0,0 +1,73 @@
+//! Helpers resolving the types declared in an ABI.
+
+use wrap_manifest_schemas::{
+    abi::{Abi, AbiTypeDefinition},
+    versions::PropertyDefinition,
+};
+
+/// Fields of an object type and constants of an enum type, local or imported.
+pub(crate) enum NamedType<'a> {
+    Object(&'a [Box<PropertyDefinition>]),
+    Enum(&'a [String]),
+}
+
+pub(crate) fn resolve<'a>(abi: &'a Abi, name: &str) -> Option<NamedType<'a>> {
+    match abi.resolve_type(name)? {
+        AbiTypeDefinition::Object(object) => Some(NamedType::Object(
+            object.properties.as_deref().unwrap_or_default(),
+        )),
+        AbiTypeDefinition::ImportedObject(object) => Some(NamedType::Object(
+            object.properties.as_deref().unwrap_or_default(),
+        )),
+        AbiTypeDefinition::Env(env) => Some(NamedType::Object(
+            env.properties.as_deref().unwrap_or_default(),
+        )),
+        AbiTypeDefinition::ImportedEnv(env) => Some(NamedType::Object(
+            env.properties.as_deref().unwrap_or_default(),
+        )),
+        AbiTypeDefinition::Enum(enum_type) => Some(NamedType::Enum(
+            enum_type.constants.as_deref().unwrap_or_default(),
+        )),
+        AbiTypeDefinition::ImportedEnum(enum_type) => Some(NamedType::Enum(
+            enum_type.constants.as_deref().unwrap_or_default(),
+        )),
+    }
+}
+
+pub(crate) fn integer_range(scalar: &str) -> Option<(i64, i64)> {
+    match scalar {
+        "Int8" => Some((i8::MIN.into(), i8::MAX.into())),
+        "Int16" => Some((i16::MIN.into(), i16::MAX.into())),
+        "Int" | "Int32" => Some((i32::MIN.into(), i32::MAX.into())),
+        "UInt8" => Some((0, u8::MAX.into())),
+        "UInt16" => Some((0, u16::MAX.into())),
+        "UInt" | "UInt32" => Some((0, u32::MAX.into())),
+        _ => None,
+    }
+}
+
+pub(crate) fn is_big_int(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    !digits.is_empty() && is_digits(digits)
+}
+
+pub(crate) fn is_big_number(value: &str) -> bool {
+    let (mantissa, exponent) = match value.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (value, None),
+    };
+    let exponent_is_valid = exponent.map_or(true, |exponent| {
+        is_big_int(exponent.strip_prefix('+').unwrap_or(exponent))
+    });
+    let mantissa = mantissa.strip_prefix('-').unwrap_or(mantissa);
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+    exponent_is_valid
+        && is_digits(integer)
+        && is_digits(fraction)
+        && !(integer.is_empty() && fraction.is_empty())
+}
+
+fn is_digits(value: &str) -> bool {
+    value.bytes().all(|b| b.is_ascii_digit())
+}
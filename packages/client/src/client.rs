@@ -1,36 +1,66 @@
+use async_trait::async_trait;
 use polywrap_core::{
+    async_invoker::{spawn_blocking, AsyncInvoker},
+    async_wrap_loader::AsyncWrapLoader,
     client::{CoreClient, CoreClientConfig},
+    env_inheritance::EnvInheritance,
+    env_merge::EnvMergeStrategy,
     error::Error,
+    interceptor::{Invocation, InvokeInterceptor},
     interface_implementation::InterfaceImplementations,
+    invoke_options::InvokeOptions,
     invoker::Invoker,
     resolution::uri_resolution_context::UriResolutionContext,
     resolution::{
-        helpers::get_env_from_resolution_path,
+        helpers::get_redirect_chain,
         uri_resolution_context::{UriPackageOrWrapper, UriResolutionStep},
         uri_resolver::UriResolver,
     },
+    resource_limits::ResourceLimits,
+    telemetry::{record_result, try_resolve_uri_in_span},
     uri::Uri,
     uri_resolver_handler::UriResolverHandler,
+    wasi_policy::WasiPolicy,
     wrap_invoker::WrapInvoker,
     wrap_loader::WrapLoader,
-    wrapper::Wrapper,
+    wrap_log::WrapLogSink,
+    wrapper::{GetFileOptions, Wrapper},
 };
 use polywrap_msgpack_serde::from_slice;
 use serde::de::DeserializeOwned;
+use serde_json::Value;
 use std::{
-    borrow::BorrowMut,
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     sync::{Arc, Mutex},
 };
+use tokio::runtime::Handle;
+use tracing::Instrument;
+use wrap_manifest_schemas::{
+    abi::Abi, deserialize::deserialize_wrap_manifest, versions::WrapManifest,
+};
 
-use crate::subinvoker::Subinvoker;
+use crate::{json, subinvoker::Subinvoker, validation::validate_args};
 
 /// `Client` is a Polywrap client used for interacting, loading, resolving and invoking wraps.
 #[derive(Clone, Debug)]
 pub struct Client {
     pub resolver: Arc<dyn UriResolver>,
     pub envs: Option<HashMap<Uri, Vec<u8>>>,
+    pub env_inheritance: Option<EnvInheritance>,
+    pub env_merge_strategy: Option<EnvMergeStrategy>,
     pub interfaces: Option<InterfaceImplementations>,
+    pub interceptors: Option<Vec<Arc<dyn InvokeInterceptor>>>,
+    pub invoke_options: Option<InvokeOptions>,
+    pub resource_limits: Option<ResourceLimits>,
+    pub uri_resource_limits: Option<HashMap<Uri, ResourceLimits>>,
+    pub uri_wasi_policies: Option<HashMap<Uri, WasiPolicy>>,
+    pub log_sink: Option<Arc<dyn WrapLogSink>>,
+    pub validate_args: Option<bool>,
+    pub executor: Option<Handle>,
+    /// Env of the wrap subinvoking through this client, if it is inherited
+    inherited_env: Option<Vec<u8>>,
+    /// ABIs of the wraps invoked with JSON or validated arguments
+    abis: Arc<Mutex<AbiCache>>,
 }
 
 impl Client {
@@ -43,11 +73,32 @@ impl Client {
     pub fn new(config: CoreClientConfig) -> Self {
         let resolver = config.resolver;
         let envs = config.envs;
+        let env_inheritance = config.env_inheritance;
+        let env_merge_strategy = config.env_merge_strategy;
         let interfaces = config.interfaces;
+        let interceptors = config.interceptors;
+        let resource_limits = config.resource_limits;
+        let uri_resource_limits = config.uri_resource_limits;
+        let uri_wasi_policies = config.uri_wasi_policies;
+        let log_sink = config.log_sink;
+        let validate_args = config.validate_args;
+        let executor = config.executor;
         Self {
             resolver,
             envs,
+            env_inheritance,
+            env_merge_strategy,
             interfaces,
+            interceptors,
+            invoke_options: None,
+            resource_limits,
+            uri_resource_limits,
+            uri_wasi_policies,
+            log_sink,
+            validate_args,
+            executor,
+            inherited_env: None,
+            abis: Arc::default(),
         }
     }
 
@@ -74,6 +125,93 @@ impl Client {
         from_slice(result.as_slice()).map_err(Error::MsgpackError)
     }
 
+    /// Invokes a method on a given URI with a deadline and/or a cancellation token,
+    /// decodes the result into `T` and returns it.
+    /// The options also bound every subinvocation made by the invoked wrap. Wasm wraps are
+    /// interrupted as soon as they expire, and fail with `Error::TimeoutError` or `Error::CancelledError`.
+    /// The fuel consumed by the invocation and its subinvocations is added to `options.resource_usage`.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri`: `Uri` of the wrap to invoke.
+    /// * `method`: A string slice representing the method to be invoked.
+    /// * `args`: Optional msgpack buffer representing the arguments.
+    /// * `env`: Optional msgpack buffer representing the environment.
+    /// * `resolution_context`: Optional resolution context of invocation.
+    /// * `options`: `InvokeOptions` bounding the invocation.
+    pub fn invoke_with_options<T: DeserializeOwned>(
+        &self,
+        uri: &Uri,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        resolution_context: Option<Arc<Mutex<UriResolutionContext>>>,
+        options: &InvokeOptions,
+    ) -> Result<T, Error> {
+        let client = Client {
+            invoke_options: Some(options.within(self.invoke_options.as_ref())),
+            ..self.clone()
+        };
+
+        client.invoke(uri, method, args, env, resolution_context)
+    }
+
+    /// Asynchronously invokes a method on a given URI, decodes the result into `T` and returns it.
+    /// Resolution runs on the blocking thread pool of the current Tokio runtime,
+    /// and so does the execution of wraps that can't be awaited natively, like async plugins can.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri`: `Uri` of the wrap to invoke.
+    /// * `method`: A string slice representing the method to be invoked.
+    /// * `args`: Optional msgpack buffer representing the arguments.
+    /// * `env`: Optional msgpack buffer representing the environment.
+    /// * `resolution_context`: Optional resolution context of invocation.
+    pub async fn invoke_async<T: DeserializeOwned>(
+        &self,
+        uri: &Uri,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        resolution_context: Option<Arc<Mutex<UriResolutionContext>>>,
+    ) -> Result<T, Error> {
+        let result = self
+            .invoke_raw_async(uri, method, args, env, resolution_context)
+            .await?;
+
+        from_slice(result.as_slice()).map_err(Error::MsgpackError)
+    }
+
+    /// Asynchronously invokes a method on a given URI with a deadline and/or a cancellation token,
+    /// decodes the result into `T` and returns it, like `invoke_with_options`.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri`: `Uri` of the wrap to invoke.
+    /// * `method`: A string slice representing the method to be invoked.
+    /// * `args`: Optional msgpack buffer representing the arguments.
+    /// * `env`: Optional msgpack buffer representing the environment.
+    /// * `resolution_context`: Optional resolution context of invocation.
+    /// * `options`: `InvokeOptions` bounding the invocation.
+    pub async fn invoke_with_options_async<T: DeserializeOwned>(
+        &self,
+        uri: &Uri,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        resolution_context: Option<Arc<Mutex<UriResolutionContext>>>,
+        options: &InvokeOptions,
+    ) -> Result<T, Error> {
+        let client = Client {
+            invoke_options: Some(options.within(self.invoke_options.as_ref())),
+            ..self.clone()
+        };
+
+        client
+            .invoke_async(uri, method, args, env, resolution_context)
+            .await
+    }
+
     /// Invokes a method on a given `Wrapper` implementation instance, decodes the result into `TResult` and returns it
     ///
     /// # Arguments
@@ -98,10 +236,252 @@ impl Client {
 
         from_slice(result.as_slice()).map_err(Error::MsgpackError)
     }
-}
 
-impl Invoker for Client {
-    fn invoke_raw(
+    /// Resolves a URI and returns the manifest of the wrap it resolves to.
+    /// The ABI of the manifest can be queried through `wrap_manifest_schemas::abi::Abi`.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri`: `Uri` of the wrap.
+    pub fn get_manifest(&self, uri: &Uri) -> Result<WrapManifest, Error> {
+        read_manifest(self.try_resolve_uri(uri, None)?)
+    }
+
+    /// Invokes a method on a given URI with JSON arguments and returns its result as JSON.
+    /// The arguments are encoded and the result decoded according to the ABI of the wrap,
+    /// and arguments that don't match their declared type fail with `Error::JsonError`.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri`: `Uri` of the wrap to invoke.
+    /// * `method`: A string slice representing the method to be invoked.
+    /// * `args`: JSON object of the arguments, or `null` if the method takes none.
+    pub fn invoke_json(&self, uri: &Uri, method: &str, args: Value) -> Result<Value, Error> {
+        let abi = self.get_abi(uri)?;
+        let method_definition = abi.method(method).ok_or_else(|| {
+            Error::ManifestError(format!("Method `{method}` not found in the ABI of `{uri}`"))
+        })?;
+
+        let args = json::encode_args(&abi, method_definition, &args)?;
+        let result = self.invoke_raw(uri, method, Some(&args), None, None)?;
+
+        json::decode_result(&abi, method_definition, &result)
+    }
+
+    // The ABI is parsed once per resolved URI, and also cached by `uri`
+    // so it isn't resolved again
+    fn get_abi(&self, uri: &Uri) -> Result<Arc<Abi>, Error> {
+        let cached_abi = self.abis.lock().unwrap().get(&self.resolver, uri);
+        if let Some(abi) = cached_abi {
+            return Ok(abi);
+        }
+
+        let uri_package_or_wrapper = self.try_resolve_uri(uri, None)?;
+        let resolved_uri = match &uri_package_or_wrapper {
+            UriPackageOrWrapper::Package(uri, _) | UriPackageOrWrapper::Wrapper(uri, _) => {
+                uri.clone()
+            }
+            UriPackageOrWrapper::Uri(uri) => return Err(Error::UriNotFoundError(uri.to_string())),
+        };
+
+        let cached_abi = self.abis.lock().unwrap().get(&self.resolver, &resolved_uri);
+        let abi = match cached_abi {
+            Some(abi) => abi,
+            None => {
+                let manifest = read_manifest(uri_package_or_wrapper)?;
+                let abi = Abi::try_from(&manifest.abi)
+                    .map_err(|e| Error::ManifestError(e.to_string()))?;
+                Arc::new(abi)
+            }
+        };
+
+        let mut abis = self.abis.lock().unwrap();
+        abis.insert(&self.resolver, uri.clone(), abi.clone());
+        abis.insert(&self.resolver, resolved_uri, abi.clone());
+        Ok(abi)
+    }
+
+    fn check_args(
+        &self,
+        uri: &Uri,
+        resolved_uri: &Uri,
+        method: &str,
+        args: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        let abi = self.get_abi(resolved_uri)?;
+        let mismatches = validate_args(&abi, method, args);
+        if mismatches.is_empty() {
+            return Ok(());
+        }
+
+        Err(Error::ArgsValidationError {
+            uri: uri.to_string(),
+            method: method.to_string(),
+            mismatches,
+        })
+    }
+
+    // Runs the invocation through the configured interceptors.
+    fn intercept(
+        &self,
+        uri: &Uri,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        invoke: impl FnOnce(&Uri, &str, Option<&[u8]>, Option<&[u8]>) -> Result<Vec<u8>, Error>,
+    ) -> Result<Vec<u8>, Error> {
+        let interceptors = match &self.interceptors {
+            Some(interceptors) if !interceptors.is_empty() => interceptors,
+            _ => return invoke(uri, method, args, env),
+        };
+
+        let mut invocation = Invocation {
+            uri: uri.clone(),
+            method: method.to_string(),
+            args: args.map(|a| a.to_vec()),
+            env: env.map(|e| e.to_vec()),
+        };
+
+        let mut short_circuit = None;
+        let mut called = 0;
+        for interceptor in interceptors.iter() {
+            short_circuit = interceptor.before_invoke(&mut invocation);
+            if short_circuit.is_some() {
+                break;
+            }
+            called += 1;
+        }
+
+        let result = match short_circuit {
+            Some(result) => result,
+            None => invoke(
+                &invocation.uri,
+                &invocation.method,
+                invocation.args.as_deref(),
+                invocation.env.as_deref(),
+            ),
+        };
+
+        interceptors[..called]
+            .iter()
+            .rev()
+            .fold(result, |result, interceptor| {
+                interceptor.after_invoke(&invocation, result)
+            })
+    }
+
+    /// Returns the resource limits of the wrap at the given URI,
+    /// falling back to the limits of every wrap for those it doesn't set.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri`: `Uri` of the wrap.
+    pub fn get_resource_limits_by_uri(&self, uri: &Uri) -> Option<ResourceLimits> {
+        let uri_limits = self
+            .uri_resource_limits
+            .as_ref()
+            .and_then(|limits| limits.get(uri));
+
+        match (uri_limits, &self.resource_limits) {
+            (Some(limits), Some(fallback)) => Some(limits.or(fallback)),
+            (limits, fallback) => limits.or(fallback.as_ref()).copied(),
+        }
+    }
+
+    fn get_resource_limits_from_resolution_path(
+        &self,
+        resolution_path: &[Uri],
+    ) -> Option<ResourceLimits> {
+        resolution_path
+            .iter()
+            .find(|uri| {
+                self.uri_resource_limits
+                    .as_ref()
+                    .is_some_and(|limits| limits.contains_key(uri))
+            })
+            .and_then(|uri| self.get_resource_limits_by_uri(uri))
+            .or(self.resource_limits)
+    }
+
+    /// Returns the WASI capabilities granted to the wrap at the given URI.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri`: `Uri` of the wrap.
+    pub fn get_wasi_policy_by_uri(&self, uri: &Uri) -> Option<WasiPolicy> {
+        self.uri_wasi_policies
+            .as_ref()
+            .and_then(|policies| policies.get(uri))
+            .cloned()
+    }
+
+    fn get_wasi_policy_from_resolution_path(&self, resolution_path: &[Uri]) -> Option<WasiPolicy> {
+        resolution_path
+            .iter()
+            .find_map(|uri| self.get_wasi_policy_by_uri(uri))
+    }
+
+    /// Combines the envs configured for the `Uri`s of `redirect_chain`, from the resolved one
+    /// to the invoked one, with the env passed to the invocation.
+    /// Subinvocations without a configured env may inherit the env of their parent instead.
+    fn merge_envs(
+        &self,
+        redirect_chain: &[Uri],
+        env: Option<&[u8]>,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let mut configured_envs: Vec<Vec<u8>> = redirect_chain
+            .iter()
+            .rev()
+            .filter_map(|uri| self.get_env_by_uri(uri))
+            .collect();
+
+        if configured_envs.is_empty() {
+            configured_envs.extend(self.inherited_env.clone());
+        }
+
+        let mut envs: Vec<&[u8]> = configured_envs.iter().map(Vec::as_slice).collect();
+        envs.extend(env);
+
+        self.env_merge_strategy.unwrap_or_default().merge(&envs)
+    }
+
+    fn check_invoke_options(&self, uri: &Uri, method: &str) -> Result<(), Error> {
+        match &self.invoke_options {
+            Some(options) => options.check(&format!("{uri}.{method}")),
+            None => Ok(()),
+        }
+    }
+
+    fn load_and_invoke(
+        &self,
+        uri: &Uri,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        resolution_context: Option<Arc<Mutex<UriResolutionContext>>>,
+    ) -> Result<Vec<u8>, Error> {
+        let resolution_context = match resolution_context {
+            None => Arc::new(Mutex::new(UriResolutionContext::new())),
+            Some(ctx) => ctx,
+        };
+
+        let loaded = self.load_for_invocation(uri, method, args, env, &resolution_context)?;
+
+        let mut res_context_guard = resolution_context.lock().unwrap();
+
+        self.invoke_loaded_wrapper(
+            &*loaded.wrapper,
+            uri,
+            method,
+            args,
+            loaded.env.as_deref(),
+            loaded.resource_limits,
+            loaded.wasi_policy,
+            Some(&mut res_context_guard),
+        )
+    }
+
+    async fn load_and_invoke_async(
         &self,
         uri: &Uri,
         method: &str,
@@ -114,6 +494,90 @@ impl Invoker for Client {
             Some(ctx) => ctx,
         };
 
+        let loaded = {
+            let client = self.clone();
+            let uri = uri.clone();
+            let method = method.to_string();
+            let args = args.map(|a| a.to_vec());
+            let env = env.map(|e| e.to_vec());
+            let resolution_context = resolution_context.clone();
+
+            spawn_blocking(move || {
+                client.load_for_invocation(
+                    &uri,
+                    &method,
+                    args.as_deref(),
+                    env.as_deref(),
+                    &resolution_context,
+                )
+            })
+            .await?
+        };
+
+        let Some(wrapper) = loaded.wrapper.as_async() else {
+            let client = self.clone();
+            let uri = uri.clone();
+            let method = method.to_string();
+            let args = args.map(|a| a.to_vec());
+
+            return spawn_blocking(move || {
+                let mut res_context_guard = resolution_context.lock().unwrap();
+
+                client.invoke_loaded_wrapper(
+                    &*loaded.wrapper,
+                    &uri,
+                    &method,
+                    args.as_deref(),
+                    loaded.env.as_deref(),
+                    loaded.resource_limits,
+                    loaded.wasi_policy,
+                    Some(&mut res_context_guard),
+                )
+            })
+            .await;
+        };
+
+        let subinvocation_context = resolution_context.lock().unwrap().create_sub_context();
+        let subinvocation_context = Arc::new(Mutex::new(subinvocation_context));
+
+        let subinvoker = self.create_subinvoker(
+            uri,
+            loaded.env.as_deref(),
+            loaded.resource_limits,
+            loaded.wasi_policy,
+            subinvocation_context.clone(),
+        );
+
+        let invoke_result = wrapper
+            .invoke_async(method, args, loaded.env.as_deref(), subinvoker)
+            .await
+            .map_err(|e| Error::InvokeError {
+                uri: uri.to_string(),
+                method: method.to_string(),
+                source: Box::new(e),
+            });
+
+        let mut res_context_guard = resolution_context.lock().unwrap();
+
+        track_invocation(
+            &mut res_context_guard,
+            uri,
+            &subinvocation_context,
+            invoke_result,
+        )
+    }
+
+    /// Loads the wrapper at `uri` and everything its invocation is configured with.
+    fn load_for_invocation(
+        &self,
+        uri: &Uri,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        resolution_context: &Mutex<UriResolutionContext>,
+    ) -> Result<LoadedInvocation, Error> {
+        self.check_invoke_options(uri, method)?;
+
         let loaded_wrapper_context = resolution_context.lock().unwrap().create_sub_context();
         let loaded_wrapper_context = Arc::new(Mutex::new(loaded_wrapper_context));
 
@@ -134,7 +598,10 @@ impl Invoker for Client {
                     sub_history: Some(loaded_wrapper_context.lock().unwrap().get_history().clone()),
                 });
 
-            return Err(Error::LoadWrapperError(uri.to_string(), error.to_string()));
+            return Err(Error::LoadWrapperError {
+                uri: uri.to_string(),
+                source: Box::new(error),
+            });
         }
 
         let resolution_path = loaded_wrapper_context.lock().unwrap().get_resolution_path();
@@ -146,6 +613,11 @@ impl Invoker for Client {
 
         let resolved_uri = resolution_path.last().unwrap();
 
+        // Not every resolver tracks the resolution path,
+        // so configuration is looked up through the redirects of the resolution history
+        let redirect_chain =
+            get_redirect_chain(uri, loaded_wrapper_context.lock().unwrap().get_history());
+
         let wrapper = load_result.unwrap();
 
         resolution_context
@@ -161,23 +633,213 @@ impl Invoker for Client {
                 sub_history: Some(loaded_wrapper_context.lock().unwrap().get_history().clone()),
             });
 
-        let env = if env.is_some() {
-            env.map(|e| e.to_vec())
-        } else {
-            get_env_from_resolution_path(&resolution_path, self)
+        let env = self.merge_envs(&redirect_chain, env)?;
+
+        // Resolution may have taken a while, e.g. when fetching the wrap
+        self.check_invoke_options(uri, method)?;
+
+        if self.validate_args.unwrap_or_default() {
+            self.check_args(uri, resolved_uri, method, args)?;
+        }
+
+        let resource_limits = self.get_resource_limits_from_resolution_path(&redirect_chain);
+        let wasi_policy = self.get_wasi_policy_from_resolution_path(&redirect_chain);
+
+        Ok(LoadedInvocation {
+            wrapper,
+            env,
+            resource_limits,
+            wasi_policy,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn invoke_loaded_wrapper(
+        &self,
+        wrapper: &dyn Wrapper,
+        uri: &Uri,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        resource_limits: Option<ResourceLimits>,
+        wasi_policy: Option<WasiPolicy>,
+        resolution_context: Option<&mut UriResolutionContext>,
+    ) -> Result<Vec<u8>, Error> {
+        let mut empty_res_context = UriResolutionContext::new();
+        let resolution_context = match resolution_context {
+            None => &mut empty_res_context,
+            Some(ctx) => ctx,
         };
 
-        let mut res_context_guard = resolution_context.lock().unwrap();
+        let subinvocation_context = resolution_context.create_sub_context();
+        let subinvocation_context = Arc::new(Mutex::new(subinvocation_context));
+
+        let subinvoker = self.create_subinvoker(
+            uri,
+            env,
+            resource_limits,
+            wasi_policy,
+            subinvocation_context.clone(),
+        );
 
-        let result = self.invoke_wrapper_raw(
-            &*wrapper,
+        let invoke_result = wrapper
+            .invoke(method, args, env, subinvoker)
+            .map_err(|e| Error::InvokeError {
+                uri: uri.to_string(),
+                method: method.to_string(),
+                source: Box::new(e),
+            });
+
+        track_invocation(
+            resolution_context,
             uri,
+            &subinvocation_context,
+            invoke_result,
+        )
+    }
+
+    fn create_subinvoker(
+        &self,
+        uri: &Uri,
+        env: Option<&[u8]>,
+        resource_limits: Option<ResourceLimits>,
+        wasi_policy: Option<WasiPolicy>,
+        subinvocation_context: Arc<Mutex<UriResolutionContext>>,
+    ) -> Arc<Subinvoker> {
+        let invoker = match self.env_inheritance.unwrap_or_default() {
+            EnvInheritance::None => self.clone(),
+            EnvInheritance::Fallback => Client {
+                inherited_env: env.map(|e| e.to_vec()),
+                ..self.clone()
+            },
+        };
+
+        Arc::new(
+            Subinvoker::new(Arc::new(invoker), subinvocation_context)
+                .with_resource_limits(resource_limits)
+                .with_wasi_policy(wasi_policy)
+                .with_wrap_uri(uri.clone()),
+        )
+    }
+}
+
+/// Reads the manifest of the package or wrapper a URI resolved to.
+fn read_manifest(uri_package_or_wrapper: UriPackageOrWrapper) -> Result<WrapManifest, Error> {
+    match uri_package_or_wrapper {
+        UriPackageOrWrapper::Package(_, package) => package.get_manifest(None),
+        UriPackageOrWrapper::Wrapper(_, wrapper) => {
+            let manifest = wrapper.get_file(&GetFileOptions {
+                path: "wrap.info".to_string(),
+                encoding: None,
+            })?;
+
+            deserialize_wrap_manifest(&manifest, None)
+                .map_err(|e| Error::ManifestError(e.to_string()))
+        }
+        UriPackageOrWrapper::Uri(uri) => Err(Error::UriNotFoundError(uri.to_string())),
+    }
+}
+
+/// Maximum number of URIs a client caches the ABI of.
+const MAX_CACHED_ABIS: usize = 256;
+
+/// ABIs of wraps by the URIs they were looked up with, evicted oldest first.
+///
+/// The URIs were resolved by a given resolver, and the cache is cleared once
+/// it's used with another one, e.g. after the resolver of the client was replaced.
+#[derive(Debug, Default)]
+struct AbiCache {
+    resolver: Option<Arc<dyn UriResolver>>,
+    abis: HashMap<Uri, Arc<Abi>>,
+    uris: VecDeque<Uri>,
+}
+
+impl AbiCache {
+    fn get(&mut self, resolver: &Arc<dyn UriResolver>, uri: &Uri) -> Option<Arc<Abi>> {
+        self.use_resolver(resolver);
+        self.abis.get(uri).cloned()
+    }
+
+    fn insert(&mut self, resolver: &Arc<dyn UriResolver>, uri: Uri, abi: Arc<Abi>) {
+        self.use_resolver(resolver);
+        if self.abis.insert(uri.clone(), abi).is_none() {
+            self.uris.push_back(uri);
+        }
+
+        if self.uris.len() > MAX_CACHED_ABIS {
+            if let Some(oldest_uri) = self.uris.pop_front() {
+                self.abis.remove(&oldest_uri);
+            }
+        }
+    }
+
+    // Compares data pointers only, as vtables of the same type may differ between codegen units
+    fn use_resolver(&mut self, resolver: &Arc<dyn UriResolver>) {
+        let is_same_resolver = self.resolver.as_ref().is_some_and(|cached_resolver| {
+            Arc::as_ptr(cached_resolver) as *const () == Arc::as_ptr(resolver) as *const ()
+        });
+
+        if !is_same_resolver {
+            self.abis.clear();
+            self.uris.clear();
+            self.resolver = Some(resolver.clone());
+        }
+    }
+}
+
+/// A loaded wrapper along with the configuration it is invoked with.
+struct LoadedInvocation {
+    wrapper: Arc<dyn Wrapper>,
+    env: Option<Vec<u8>>,
+    resource_limits: Option<ResourceLimits>,
+    wasi_policy: Option<WasiPolicy>,
+}
+
+fn track_invocation(
+    resolution_context: &mut UriResolutionContext,
+    uri: &Uri,
+    subinvocation_context: &Mutex<UriResolutionContext>,
+    invoke_result: Result<Vec<u8>, Error>,
+) -> Result<Vec<u8>, Error> {
+    let subinvocation_context = subinvocation_context.lock().unwrap();
+
+    resolution_context.track_step(UriResolutionStep {
+        source_uri: uri.clone(),
+        result: if invoke_result.is_ok() {
+            Ok(UriPackageOrWrapper::Uri(uri.clone()))
+        } else {
+            Err(invoke_result.clone().unwrap_err())
+        },
+        description: Some("Client.invokeWrapper".to_string()),
+        sub_history: Some(subinvocation_context.get_history().clone()),
+    });
+
+    invoke_result
+}
+
+impl Invoker for Client {
+    fn invoke_raw(
+        &self,
+        uri: &Uri,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        resolution_context: Option<Arc<Mutex<UriResolutionContext>>>,
+    ) -> Result<Vec<u8>, Error> {
+        let span = tracing::info_span!(
+            "invoke",
+            uri = %uri,
             method,
-            args,
-            env.as_deref(),
-            Some(res_context_guard.borrow_mut()),
+            otel.status_code = tracing::field::Empty,
+            error = tracing::field::Empty,
         );
+        let _enter = span.enter();
+
+        let result = self.intercept(uri, method, args, env, |uri, method, args, env| {
+            self.load_and_invoke(uri, method, args, env, resolution_context)
+        });
 
+        record_result(&span, &result);
         result
     }
 
@@ -200,6 +862,61 @@ impl Invoker for Client {
 
         None
     }
+
+    fn get_invoke_options(&self) -> Option<InvokeOptions> {
+        self.invoke_options.clone()
+    }
+
+    fn get_resource_limits(&self) -> Option<ResourceLimits> {
+        self.resource_limits
+    }
+
+    fn get_log_sink(&self) -> Option<Arc<dyn WrapLogSink>> {
+        self.log_sink.clone()
+    }
+
+    fn get_executor(&self) -> Option<Handle> {
+        self.executor.clone()
+    }
+}
+
+#[async_trait]
+impl AsyncInvoker for Client {
+    async fn invoke_raw_async(
+        &self,
+        uri: &Uri,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        resolution_context: Option<Arc<Mutex<UriResolutionContext>>>,
+    ) -> Result<Vec<u8>, Error> {
+        // Interceptors are synchronous, so intercepted invocations run on the blocking thread pool
+        if self
+            .interceptors
+            .as_ref()
+            .is_some_and(|interceptors| !interceptors.is_empty())
+        {
+            return Arc::new(self.clone())
+                .invoke_raw_async(uri, method, args, env, resolution_context)
+                .await;
+        }
+
+        let span = tracing::info_span!(
+            "invoke",
+            uri = %uri,
+            method,
+            otel.status_code = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+
+        let result = self
+            .load_and_invoke_async(uri, method, args, env, resolution_context)
+            .instrument(span.clone())
+            .await;
+
+        record_result(&span, &result);
+        result
+    }
 }
 
 impl WrapLoader for Client {
@@ -208,25 +925,44 @@ impl WrapLoader for Client {
         uri: &Uri,
         resolution_context: Option<Arc<Mutex<UriResolutionContext>>>,
     ) -> Result<Arc<dyn Wrapper>, Error> {
+        let span = tracing::info_span!(
+            "load_wrapper",
+            uri = %uri,
+            otel.status_code = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+        let _enter = span.enter();
+
         let resolution_context = match resolution_context {
             None => Arc::new(Mutex::new(UriResolutionContext::new())),
             Some(ctx) => ctx,
         };
 
-        let uri_package_or_wrapper = self
+        let result = self
             .try_resolve_uri(uri, Some(resolution_context))
-            .map_err(|e| Error::ResolutionError(e.to_string()))?;
-
-        match uri_package_or_wrapper {
-            UriPackageOrWrapper::Uri(uri) => Err(Error::UriNotFoundError(uri.to_string())),
-            UriPackageOrWrapper::Wrapper(_, wrapper) => Ok(wrapper),
-            UriPackageOrWrapper::Package(_, package) => {
-                let wrapper = package
+            .and_then(|uri_package_or_wrapper| match uri_package_or_wrapper {
+                UriPackageOrWrapper::Uri(uri) => Err(Error::UriNotFoundError(uri.to_string())),
+                UriPackageOrWrapper::Wrapper(_, wrapper) => Ok(wrapper),
+                UriPackageOrWrapper::Package(_, package) => package
                     .create_wrapper()
-                    .map_err(|e| Error::WrapperCreateError(e.to_string()))?;
-                Ok(wrapper)
-            }
-        }
+                    .map_err(|e| Error::WrapperCreateError(e.to_string())),
+            });
+
+        record_result(&span, &result);
+        result
+    }
+}
+
+#[async_trait]
+impl AsyncWrapLoader for Client {
+    async fn load_wrapper_async(
+        &self,
+        uri: &Uri,
+        resolution_context: Option<Arc<Mutex<UriResolutionContext>>>,
+    ) -> Result<Arc<dyn Wrapper>, Error> {
+        Arc::new(self.clone())
+            .load_wrapper_async(uri, resolution_context)
+            .await
     }
 }
 
@@ -240,38 +976,27 @@ impl WrapInvoker for Client {
         env: Option<&[u8]>,
         resolution_context: Option<&mut UriResolutionContext>,
     ) -> Result<Vec<u8>, Error> {
-        let mut empty_res_context = UriResolutionContext::new();
-        let resolution_context = match resolution_context {
-            None => &mut empty_res_context,
-            Some(ctx) => ctx,
-        };
-
-        let subinvocation_context = resolution_context.create_sub_context();
-        let subinvocation_context = Arc::new(Mutex::new(subinvocation_context));
-
-        let subinvoker = Arc::new(Subinvoker::new(
-            Arc::new(self.clone()),
-            subinvocation_context.clone(),
-        ));
-
-        let invoke_result = wrapper
-            .invoke(method, args, env, subinvoker)
-            .map_err(|e| Error::InvokeError(uri.to_string(), method.to_string(), e.to_string()));
-
-        let subinvocation_context = subinvocation_context.lock().unwrap();
-
-        resolution_context.track_step(UriResolutionStep {
-            source_uri: uri.clone(),
-            result: if invoke_result.is_ok() {
-                Ok(UriPackageOrWrapper::Uri(uri.clone()))
-            } else {
-                Err(invoke_result.clone().unwrap_err())
-            },
-            description: Some("Client.invokeWrapper".to_string()),
-            sub_history: Some(subinvocation_context.get_history().clone()),
-        });
+        self.intercept(uri, method, args, env, |invocation_uri, method, args, env| {
+            // The wrapper is given, so it can't be swapped for the wrap at another URI
+            if invocation_uri != uri {
+                return Err(Error::wrapper(format!(
+                    "Interceptors can't redirect an invocation of a given wrapper from `{uri}` to `{invocation_uri}`"
+                )));
+            }
 
-        invoke_result
+            let resource_limits = self.get_resource_limits_by_uri(uri);
+            let wasi_policy = self.get_wasi_policy_by_uri(uri);
+            self.invoke_loaded_wrapper(
+                wrapper,
+                uri,
+                method,
+                args,
+                env,
+                resource_limits,
+                wasi_policy,
+                resolution_context,
+            )
+        })
     }
 }
 
@@ -282,12 +1007,17 @@ impl UriResolverHandler for Client {
         resolution_context: Option<Arc<Mutex<UriResolutionContext>>>,
     ) -> Result<UriPackageOrWrapper, Error> {
         let uri_resolver = self.resolver.clone();
+        // Wraps invoked to resolve the URI don't inherit the env of the wrap subinvoking it
+        let invoker = Arc::new(Client {
+            inherited_env: None,
+            ..self.clone()
+        });
         let resolution_context = match resolution_context {
             Some(r) => r,
             None => Arc::new(Mutex::new(UriResolutionContext::new())),
         };
 
-        uri_resolver.try_resolve_uri(uri, Arc::new(self.clone()), resolution_context)
+        try_resolve_uri_in_span(uri_resolver.as_ref(), uri, invoker, resolution_context)
     }
 }
 
@@ -296,8 +1026,13 @@ impl CoreClient for Client {}
 #[cfg(test)]
 mod client_tests {
     use polywrap_core::{
-        client::CoreClientConfig, resolution::uri_resolution_context::UriPackageOrWrapper, uri::Uri,
-        uri_resolver_handler::UriResolverHandler, wrap_loader::WrapLoader,
+        async_wrap_loader::AsyncWrapLoader,
+        client::CoreClientConfig,
+        error::{Error, ErrorKind},
+        resolution::uri_resolution_context::UriPackageOrWrapper,
+        uri::Uri,
+        uri_resolver_handler::UriResolverHandler,
+        wrap_loader::WrapLoader,
     };
     use polywrap_msgpack_serde::from_slice;
     use polywrap_tests_utils::mocks::{get_mock_resolver, MockWrapper};
@@ -311,6 +1046,7 @@ mod client_tests {
             resolver: get_mock_resolver(),
             envs: None,
             interfaces: None,
+            ..Default::default()
         });
 
         let result = client
@@ -320,12 +1056,54 @@ mod client_tests {
         assert!(result);
     }
 
+    #[tokio::test]
+    async fn invoke_async() {
+        let client = Client::new(CoreClientConfig {
+            resolver: get_mock_resolver(),
+            envs: None,
+            interfaces: None,
+            ..Default::default()
+        });
+
+        let result = client
+            .invoke_async::<bool>(&"wrap/mock".try_into().unwrap(), "foo", None, None, None)
+            .await
+            .unwrap();
+
+        assert!(result);
+    }
+
+    #[test]
+    fn invoke_unresolvable_uri() {
+        let client = Client::new(CoreClientConfig {
+            resolver: get_mock_resolver(),
+            envs: None,
+            interfaces: None,
+            ..Default::default()
+        });
+
+        let error = client
+            .invoke::<bool>(&"wrap/not-found".try_into().unwrap(), "foo", None, None, None)
+            .unwrap_err();
+
+        match &error {
+            Error::LoadWrapperError { uri, source } => {
+                assert_eq!(uri, "wrap://wrap/not-found");
+                assert_eq!(source.kind(), ErrorKind::Resolution);
+            }
+            _ => panic!("Expected LoadWrapperError, got: {:?}", error),
+        }
+        assert_eq!(error.root_cause().kind(), ErrorKind::Resolution);
+        assert!(error.abort_info().is_none());
+    }
+
     #[test]
     fn invoke_wrapper() {
         let client = Client::new(CoreClientConfig {
             resolver: get_mock_resolver(),
             envs: None,
             interfaces: None,
+            ..Default::default()
         });
 
         let wrapper = MockWrapper {};
@@ -350,6 +1128,7 @@ mod client_tests {
             resolver: get_mock_resolver(),
             envs: None,
             interfaces: None,
+            ..Default::default()
         });
 
         let wrapper = client
@@ -361,12 +1140,32 @@ mod client_tests {
         assert!(from_slice::<bool>(&r).unwrap());
     }
 
+    #[tokio::test]
+    async fn load_wrapper_async() {
+        let client = Client::new(CoreClientConfig {
+            resolver: get_mock_resolver(),
+            envs: None,
+            interfaces: None,
+            ..Default::default()
+        });
+
+        let wrapper = client
+            .load_wrapper_async(&"wrap/mock".try_into().unwrap(), None)
+            .await
+            .unwrap();
+
+        let result = wrapper.invoke("foo", None, None, Arc::new(client));
+        let r = result.unwrap();
+        assert!(from_slice::<bool>(&r).unwrap());
+    }
+
     #[test]
     fn try_resolve_uri() {
         let client = Client::new(CoreClientConfig {
             resolver: get_mock_resolver(),
             envs: None,
             interfaces: None,
+            ..Default::default()
         });
         let uri: Uri = "wrap/mock".try_into().unwrap();
 
@@ -0,0 +1,455 @@
+//! Conversion between JSON values and the msgpack encoding of wrap arguments and results,
+//! driven by the types declared in the wrap's ABI.
+//!
+//! Arguments are coerced into the encoding each type expects: `BigInt` and `BigNumber`
+//! as strings, `Bytes` as binary, `JSON` as its serialized string, enums as the index
+//! of their constant and maps as the generic map extension type.
+
+use polywrap_core::error::Error;
+use serde_json::{Map, Value};
+use wrap_manifest_schemas::{
+    abi::{Abi, TypeKind, TypeRef},
+    versions::{MethodDefinition, PropertyDefinition},
+};
+
+use crate::{
+    abi_types::{integer_range, is_big_int, is_big_number, resolve, NamedType},
+    msgpack_value::{MsgpackValue, GENERIC_MAP_EXT_TYPE},
+};
+
+/// Encodes the JSON arguments of a method into msgpack.
+///
+/// # Arguments
+///
+/// * `abi`: ABI declaring the method and the types of its arguments.
+/// * `method`: Method the arguments are passed to.
+/// * `args`: JSON object of the arguments, or `null` if the method takes none.
+pub fn encode_args(abi: &Abi, method: &MethodDefinition, args: &Value) -> Result<Vec<u8>, Error> {
+    let arguments = method.arguments.as_deref().unwrap_or_default();
+    let empty = Map::new();
+    let values = match args {
+        Value::Object(values) => values,
+        Value::Null => &empty,
+        value => {
+            return Err(json_error(
+                "$",
+                format!("expected an object, got {}", kind_of(value)),
+            ))
+        }
+    };
+
+    let mut buf = Vec::new();
+    Encoder { abi, buf: &mut buf }.encode_properties(arguments, values, "$")?;
+    Ok(buf)
+}
+
+/// Decodes the msgpack result of a method into JSON.
+///
+/// # Arguments
+///
+/// * `abi`: ABI declaring the method and the type of its result.
+/// * `method`: Method the result was returned by.
+/// * `result`: msgpack encoded result.
+pub fn decode_result(abi: &Abi, method: &MethodDefinition, result: &[u8]) -> Result<Value, Error> {
+    let value = MsgpackValue::read(result)?;
+
+    match &method.return_ {
+        Some(return_) => Decoder { abi }.convert(&value, &return_.type_ref(), "$"),
+        None => Ok(value.to_json()),
+    }
+}
+
+fn json_error(path: &str, message: String) -> Error {
+    Error::JsonError {
+        path: path.to_string(),
+        message,
+    }
+}
+
+fn msgpack_error(path: &str, message: &str) -> Error {
+    Error::MsgpackError(polywrap_msgpack_serde::Error::Message(format!(
+        "Invalid result at `{path}`: {message}"
+    )))
+}
+
+fn kind_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+struct Encoder<'a> {
+    abi: &'a Abi,
+    buf: &'a mut Vec<u8>,
+}
+
+impl Encoder<'_> {
+    fn encode(&mut self, value: &Value, type_ref: &TypeRef, path: &str) -> Result<(), Error> {
+        if value.is_null() {
+            if type_ref.required {
+                return Err(json_error(path, format!("expected {type_ref}, got null")));
+            }
+            self.buf.push(0xc0);
+            return Ok(());
+        }
+
+        match &type_ref.kind {
+            TypeKind::Scalar(scalar) => self.encode_scalar(value, scalar, path),
+            TypeKind::Object(name) | TypeKind::Enum(name) | TypeKind::Unresolved(name) => {
+                match resolve(self.abi, name) {
+                    Some(NamedType::Object(properties)) => match value {
+                        Value::Object(values) => self.encode_properties(properties, values, path),
+                        value => Err(self.mismatch(path, type_ref, value)),
+                    },
+                    Some(NamedType::Enum(constants)) => {
+                        self.encode_enum(value, type_ref, constants, path)
+                    }
+                    None => Err(json_error(
+                        path,
+                        format!("type `{name}` isn't declared in the ABI"),
+                    )),
+                }
+            }
+            TypeKind::Array(item) => {
+                let Value::Array(items) = value else {
+                    return Err(self.mismatch(path, type_ref, value));
+                };
+
+                write_len(self.buf, items.len(), [0x90, 0xdc, 0xdd], 0x0f, path)?;
+                for (i, item_value) in items.iter().enumerate() {
+                    self.encode(item_value, item, &format!("{path}[{i}]"))?;
+                }
+                Ok(())
+            }
+            TypeKind::Map {
+                key,
+                value: map_value,
+            } => {
+                let Value::Object(entries) = value else {
+                    return Err(self.mismatch(path, type_ref, value));
+                };
+
+                let mut map = Vec::new();
+                write_len(&mut map, entries.len(), [0x80, 0xde, 0xdf], 0x0f, path)?;
+                let mut encoder = Encoder {
+                    abi: self.abi,
+                    buf: &mut map,
+                };
+                for (entry_key, entry_value) in entries {
+                    let entry_path = format!("{path}[{entry_key:?}]");
+                    encoder.encode_map_key(entry_key, key, &entry_path)?;
+                    encoder.encode(entry_value, map_value, &entry_path)?;
+                }
+
+                write_ext(self.buf, GENERIC_MAP_EXT_TYPE, &map, path)
+            }
+        }
+    }
+
+    fn encode_properties(
+        &mut self,
+        properties: &[Box<PropertyDefinition>],
+        values: &Map<String, Value>,
+        path: &str,
+    ) -> Result<(), Error> {
+        let names = properties
+            .iter()
+            .map(|property| property.name.as_deref().unwrap_or_default())
+            .collect::<Vec<_>>();
+        if let Some(unknown) = values.keys().find(|key| !names.contains(&key.as_str())) {
+            return Err(json_error(
+                &format!("{path}.{unknown}"),
+                format!("unknown property, expected one of: {}", names.join(", ")),
+            ));
+        }
+
+        write_len(self.buf, properties.len(), [0x80, 0xde, 0xdf], 0x0f, path)?;
+        for (property, name) in properties.iter().zip(names) {
+            write_str(self.buf, name, path)?;
+            let value = values.get(name).unwrap_or(&Value::Null);
+            self.encode(value, &property.type_ref(), &format!("{path}.{name}"))?;
+        }
+        Ok(())
+    }
+
+    fn encode_scalar(&mut self, value: &Value, scalar: &str, path: &str) -> Result<(), Error> {
+        let expected = |description: &str| {
+            json_error(
+                path,
+                format!("expected {description}, got {}", kind_of(value)),
+            )
+        };
+
+        if let Some((min, max)) = integer_range(scalar) {
+            let integer = value.as_i64().ok_or_else(|| expected("an integer"))?;
+            if integer < min || integer > max {
+                return Err(json_error(
+                    path,
+                    format!("{integer} is out of the range of {scalar} [{min}, {max}]"),
+                ));
+            }
+            write_int(self.buf, integer);
+            return Ok(());
+        }
+
+        match (scalar, value) {
+            ("String", Value::String(string)) => write_str(self.buf, string, path),
+            ("Boolean", Value::Bool(boolean)) => {
+                self.buf.push(if *boolean { 0xc3 } else { 0xc2 });
+                Ok(())
+            }
+            ("Bytes", Value::Array(items)) => {
+                let bytes = items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| {
+                        item.as_u64()
+                            .and_then(|byte| u8::try_from(byte).ok())
+                            .ok_or_else(|| {
+                                json_error(
+                                    &format!("{path}[{i}]"),
+                                    "expected a byte between 0 and 255".to_string(),
+                                )
+                            })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                write_len(
+                    self.buf,
+                    bytes.len(),
+                    [0xc4, 0xc5, 0xc6],
+                    u8::MAX as usize,
+                    path,
+                )?;
+                self.buf.extend_from_slice(&bytes);
+                Ok(())
+            }
+            ("BigInt", Value::String(string)) if is_big_int(string) => {
+                write_str(self.buf, string, path)
+            }
+            ("BigInt", Value::Number(number)) if number.is_i64() || number.is_u64() => {
+                write_str(self.buf, &number.to_string(), path)
+            }
+            ("BigNumber", Value::String(string)) if is_big_number(string) => {
+                write_str(self.buf, string, path)
+            }
+            ("BigNumber", Value::Number(number)) => write_str(self.buf, &number.to_string(), path),
+            ("JSON", value) => write_str(self.buf, &value.to_string(), path),
+            ("String", _) => Err(expected("a string")),
+            ("Boolean", _) => Err(expected("a boolean")),
+            ("Bytes", _) => Err(expected("an array of bytes")),
+            ("BigInt", _) => Err(expected("an integer or a string of an integer")),
+            ("BigNumber", _) => Err(expected("a number or a string of a number")),
+            (scalar, _) => Err(json_error(
+                path,
+                format!("unsupported scalar type `{scalar}`"),
+            )),
+        }
+    }
+
+    fn encode_enum(
+        &mut self,
+        value: &Value,
+        type_ref: &TypeRef,
+        constants: &[String],
+        path: &str,
+    ) -> Result<(), Error> {
+        let index = match value {
+            Value::String(name) => constants.iter().position(|constant| constant == name),
+            Value::Number(number) => number
+                .as_u64()
+                .and_then(|index| usize::try_from(index).ok())
+                .filter(|index| *index < constants.len()),
+            value => return Err(self.mismatch(path, type_ref, value)),
+        };
+
+        let index = index.ok_or_else(|| {
+            json_error(
+                path,
+                format!(
+                    "{value} isn't a constant of {}, expected one of: {}",
+                    type_ref.kind,
+                    constants.join(", ")
+                ),
+            )
+        })?;
+        write_int(self.buf, index as i64);
+        Ok(())
+    }
+
+    fn encode_map_key(&mut self, key: &str, key_type: &str, path: &str) -> Result<(), Error> {
+        if integer_range(key_type).is_none() {
+            return write_str(self.buf, key, path);
+        }
+
+        let key = key
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| json_error(path, format!("expected a key of type {key_type}")))?;
+        self.encode_scalar(&key, key_type, path)
+    }
+
+    fn mismatch(&self, path: &str, type_ref: &TypeRef, value: &Value) -> Error {
+        json_error(path, format!("expected {type_ref}, got {}", kind_of(value)))
+    }
+}
+
+fn write_int(buf: &mut Vec<u8>, value: i64) {
+    match value {
+        0..=0x7f => buf.push(value as u8),
+        -32..=-1 => buf.push(value as i8 as u8),
+        0x80..=0xff => buf.extend_from_slice(&[0xcc, value as u8]),
+        0x100..=0xffff => {
+            buf.push(0xcd);
+            buf.extend_from_slice(&(value as u16).to_be_bytes());
+        }
+        0x10000..=0xffff_ffff => {
+            buf.push(0xce);
+            buf.extend_from_slice(&(value as u32).to_be_bytes());
+        }
+        -0x80..=-33 => buf.extend_from_slice(&[0xd0, value as i8 as u8]),
+        -0x8000..=-0x81 => {
+            buf.push(0xd1);
+            buf.extend_from_slice(&(value as i16).to_be_bytes());
+        }
+        _ => {
+            buf.push(0xd2);
+            buf.extend_from_slice(&(value as i32).to_be_bytes());
+        }
+    }
+}
+
+fn write_str(buf: &mut Vec<u8>, value: &str, path: &str) -> Result<(), Error> {
+    match value.len() {
+        len if len <= 0x1f => buf.push(0xa0 | len as u8),
+        len => write_len(buf, len, [0xd9, 0xda, 0xdb], 0xff, path)?,
+    }
+    buf.extend_from_slice(value.as_bytes());
+    Ok(())
+}
+
+/// Writes the header of a value of the given length, from the markers of its short form
+/// (a fixed or 8 bit length, up to `small_max`), and its 16 and 32 bit length forms.
+fn write_len(
+    buf: &mut Vec<u8>,
+    len: usize,
+    [small, medium, large]: [u8; 3],
+    small_max: usize,
+    path: &str,
+) -> Result<(), Error> {
+    match len {
+        len if len <= small_max && small_max == 0x0f => buf.push(small | len as u8),
+        len if len <= small_max => buf.extend_from_slice(&[small, len as u8]),
+        len if len <= 0xffff => {
+            buf.push(medium);
+            buf.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            let len = u32::try_from(len)
+                .map_err(|_| json_error(path, "value is too large to be encoded".to_string()))?;
+            buf.push(large);
+            buf.extend_from_slice(&len.to_be_bytes());
+        }
+    }
+    Ok(())
+}
+
+fn write_ext(buf: &mut Vec<u8>, ext_type: u8, data: &[u8], path: &str) -> Result<(), Error> {
+    match data.len() {
+        len if len <= 0xff => buf.extend_from_slice(&[0xc7, len as u8]),
+        len if len <= 0xffff => {
+            buf.push(0xc8);
+            buf.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            let len = u32::try_from(len)
+                .map_err(|_| json_error(path, "value is too large to be encoded".to_string()))?;
+            buf.push(0xc9);
+            buf.extend_from_slice(&len.to_be_bytes());
+        }
+    }
+    buf.push(ext_type);
+    buf.extend_from_slice(data);
+    Ok(())
+}
+
+/// Converts msgpack into the JSON representation of its ABI type:
+/// `JSON` scalars are parsed and enums are named after their constant.
+struct Decoder<'a> {
+    abi: &'a Abi,
+}
+
+impl Decoder<'_> {
+    fn convert(
+        &self,
+        value: &MsgpackValue,
+        type_ref: &TypeRef,
+        path: &str,
+    ) -> Result<Value, Error> {
+        match (&type_ref.kind, value) {
+            (TypeKind::Scalar(scalar), MsgpackValue::Str(json)) if scalar == "JSON" => {
+                serde_json::from_str(json)
+                    .map_err(|e| msgpack_error(path, &format!("invalid JSON string: {e}")))
+            }
+            (TypeKind::Object(name) | TypeKind::Enum(name) | TypeKind::Unresolved(name), value) => {
+                match (resolve(self.abi, name), value) {
+                    (Some(NamedType::Object(properties)), MsgpackValue::Map(entries)) => {
+                        let values = entries
+                            .iter()
+                            .map(|(key, value)| {
+                                let key = key.to_key();
+                                let property = properties
+                                    .iter()
+                                    .find(|property| property.name.as_ref() == Some(&key));
+                                let value = match property {
+                                    Some(property) => self.convert(
+                                        value,
+                                        &property.type_ref(),
+                                        &format!("{path}.{key}"),
+                                    )?,
+                                    None => value.to_json(),
+                                };
+                                Ok((key, value))
+                            })
+                            .collect::<Result<_, Error>>()?;
+                        Ok(Value::Object(values))
+                    }
+                    (Some(NamedType::Enum(constants)), value) => Ok(value
+                        .as_i64()
+                        .and_then(|index| constants.get(usize::try_from(index).ok()?))
+                        .map_or_else(
+                            || value.to_json(),
+                            |constant| Value::from(constant.as_str()),
+                        )),
+                    (_, value) => Ok(value.to_json()),
+                }
+            }
+            (TypeKind::Array(item), MsgpackValue::Array(items)) => items
+                .iter()
+                .enumerate()
+                .map(|(i, value)| self.convert(value, item, &format!("{path}[{i}]")))
+                .collect(),
+            (
+                TypeKind::Map {
+                    value: map_value, ..
+                },
+                MsgpackValue::Map(entries) | MsgpackValue::ExtMap(entries),
+            ) => {
+                let entries = entries
+                    .iter()
+                    .map(|(key, value)| {
+                        let key = key.to_key();
+                        let value = self.convert(value, map_value, &format!("{path}[{key:?}]"))?;
+                        Ok((key, value))
+                    })
+                    .collect::<Result<_, Error>>()?;
+                Ok(Value::Object(entries))
+            }
+            (_, value) => Ok(value.to_json()),
+        }
+    }
+}
@@ -1,5 +1,9 @@
+mod abi_types;
 pub mod client;
+pub mod json;
+mod msgpack_value;
 pub mod subinvoker;
+pub mod validation;
 
 pub use polywrap_client_builder as builder;
 pub use polywrap_core as core;
@@ -0,0 +1,209 @@
+//! Untyped representation of msgpack values, keeping the distinctions wrap types rely on
+//! (binaries, generic maps) which are lost when decoding into JSON.
+
+use polywrap_core::error::Error;
+use serde_json::{Map, Number, Value};
+
+pub(crate) const GENERIC_MAP_EXT_TYPE: u8 = 1;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum MsgpackValue {
+    Nil,
+    Bool(bool),
+    /// Negative integer, or positive integer encoded as signed
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Str(String),
+    Bin(Vec<u8>),
+    Array(Vec<MsgpackValue>),
+    Map(Vec<(MsgpackValue, MsgpackValue)>),
+    /// Map encoded as the generic map extension type, as wrap `Map`s are
+    ExtMap(Vec<(MsgpackValue, MsgpackValue)>),
+}
+
+impl MsgpackValue {
+    /// Reads a single msgpack value spanning the whole buffer.
+    pub(crate) fn read(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader { bytes, offset: 0 };
+        let value = reader.read_value()?;
+        if reader.offset != bytes.len() {
+            return Err(invalid("unexpected bytes after the value"));
+        }
+        Ok(value)
+    }
+
+    pub(crate) fn as_i64(&self) -> Option<i64> {
+        match self {
+            MsgpackValue::Int(value) => Some(*value),
+            MsgpackValue::UInt(value) => i64::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the key of a map entry as a string, stringifying keys which aren't.
+    pub(crate) fn to_key(&self) -> String {
+        match self {
+            MsgpackValue::Str(key) => key.clone(),
+            key => key.to_json().to_string(),
+        }
+    }
+
+    pub(crate) fn type_name(&self) -> &'static str {
+        match self {
+            MsgpackValue::Nil => "nil",
+            MsgpackValue::Bool(_) => "bool",
+            MsgpackValue::Int(_) | MsgpackValue::UInt(_) => "int",
+            MsgpackValue::Float(_) => "float",
+            MsgpackValue::Str(_) => "string",
+            MsgpackValue::Bin(_) => "bytes",
+            MsgpackValue::Array(_) => "array",
+            MsgpackValue::Map(_) => "map",
+            MsgpackValue::ExtMap(_) => "generic map",
+        }
+    }
+
+    /// Converts the value into JSON without type information:
+    /// binaries become arrays of bytes and the keys of maps are stringified.
+    pub(crate) fn to_json(&self) -> Value {
+        match self {
+            MsgpackValue::Nil => Value::Null,
+            MsgpackValue::Bool(value) => Value::Bool(*value),
+            MsgpackValue::Int(value) => Value::from(*value),
+            MsgpackValue::UInt(value) => Value::from(*value),
+            MsgpackValue::Float(value) => {
+                Number::from_f64(*value).map_or(Value::Null, Value::Number)
+            }
+            MsgpackValue::Str(value) => Value::from(value.as_str()),
+            MsgpackValue::Bin(value) => Value::from(value.clone()),
+            MsgpackValue::Array(items) => items.iter().map(MsgpackValue::to_json).collect(),
+            MsgpackValue::Map(entries) | MsgpackValue::ExtMap(entries) => Value::Object(
+                entries
+                    .iter()
+                    .map(|(key, value)| (key.to_key(), value.to_json()))
+                    .collect::<Map<_, _>>(),
+            ),
+        }
+    }
+}
+
+fn invalid(message: &str) -> Error {
+    Error::MsgpackError(polywrap_msgpack_serde::Error::Message(format!(
+        "Invalid msgpack: {message}"
+    )))
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], Error> {
+        let bytes = self
+            .bytes
+            .get(self.offset..self.offset + len)
+            .ok_or_else(|| invalid("unexpected end of buffer"))?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn read_uint(&mut self, size: usize) -> Result<u64, Error> {
+        Ok(self
+            .take(size)?
+            .iter()
+            .fold(0, |value, byte| (value << 8) | *byte as u64))
+    }
+
+    fn read_int(&mut self, size: usize) -> Result<i64, Error> {
+        let value = self.read_uint(size)?;
+        let shift = 64 - 8 * size as u32;
+        Ok(((value << shift) as i64) >> shift)
+    }
+
+    fn read_len(&mut self, size: usize) -> Result<usize, Error> {
+        let len = self.read_uint(size)? as usize;
+        // Every item takes at least a byte, which bounds the allocations of corrupted lengths
+        if len > self.bytes.len() - self.offset {
+            return Err(invalid("unexpected end of buffer"));
+        }
+        Ok(len)
+    }
+
+    fn read_value(&mut self) -> Result<MsgpackValue, Error> {
+        let marker = self.take(1)?[0];
+
+        match marker {
+            0x00..=0x7f => Ok(MsgpackValue::UInt(marker.into())),
+            0xe0..=0xff => Ok(MsgpackValue::Int((marker as i8).into())),
+            0x80..=0x8f => self
+                .read_map((marker & 0x0f) as usize)
+                .map(MsgpackValue::Map),
+            0x90..=0x9f => self.read_array((marker & 0x0f) as usize),
+            0xa0..=0xbf => self.read_str((marker & 0x1f) as usize),
+            0xc0 => Ok(MsgpackValue::Nil),
+            0xc2 => Ok(MsgpackValue::Bool(false)),
+            0xc3 => Ok(MsgpackValue::Bool(true)),
+            0xc4..=0xc6 => {
+                let len = self.read_len(1 << (marker - 0xc4))?;
+                Ok(MsgpackValue::Bin(self.take(len)?.to_vec()))
+            }
+            0xc7..=0xc9 => {
+                let len = self.read_len(1 << (marker - 0xc7))?;
+                self.read_ext(len)
+            }
+            0xca => Ok(MsgpackValue::Float(
+                f32::from_bits(self.read_uint(4)? as u32).into(),
+            )),
+            0xcb => Ok(MsgpackValue::Float(f64::from_bits(self.read_uint(8)?))),
+            0xcc..=0xcf => Ok(MsgpackValue::UInt(self.read_uint(1 << (marker - 0xcc))?)),
+            0xd0..=0xd3 => Ok(MsgpackValue::Int(self.read_int(1 << (marker - 0xd0))?)),
+            0xd4..=0xd8 => self.read_ext(1 << (marker - 0xd4)),
+            0xd9..=0xdb => {
+                let len = self.read_len(1 << (marker - 0xd9))?;
+                self.read_str(len)
+            }
+            0xdc | 0xdd => {
+                let len = self.read_len(2 << (marker - 0xdc))?;
+                self.read_array(len)
+            }
+            0xde | 0xdf => {
+                let len = self.read_len(2 << (marker - 0xde))?;
+                self.read_map(len).map(MsgpackValue::Map)
+            }
+            0xc1 => Err(invalid("reserved marker 0xc1")),
+        }
+    }
+
+    fn read_str(&mut self, len: usize) -> Result<MsgpackValue, Error> {
+        let string = std::str::from_utf8(self.take(len)?)
+            .map_err(|_| invalid("string isn't valid UTF-8"))?;
+        Ok(MsgpackValue::Str(string.to_string()))
+    }
+
+    fn read_array(&mut self, len: usize) -> Result<MsgpackValue, Error> {
+        let items = (0..len)
+            .map(|_| self.read_value())
+            .collect::<Result<_, _>>()?;
+        Ok(MsgpackValue::Array(items))
+    }
+
+    fn read_map(&mut self, len: usize) -> Result<Vec<(MsgpackValue, MsgpackValue)>, Error> {
+        (0..len)
+            .map(|_| Ok((self.read_value()?, self.read_value()?)))
+            .collect()
+    }
+
+    fn read_ext(&mut self, len: usize) -> Result<MsgpackValue, Error> {
+        let ext_type = self.take(1)?[0];
+        if ext_type != GENERIC_MAP_EXT_TYPE {
+            return Err(invalid(&format!("unsupported extension type {ext_type}")));
+        }
+
+        let end = self.offset + len;
+        match self.read_value()? {
+            MsgpackValue::Map(entries) if self.offset == end => Ok(MsgpackValue::ExtMap(entries)),
+            _ => Err(invalid("malformed generic map extension")),
+        }
+    }
+}
@@ -1,14 +1,20 @@
 use std::sync::{Arc, Mutex};
 
 use polywrap_core::{
-    error::Error, interface_implementation::InterfaceImplementations, invoker::Invoker,
-    resolution::uri_resolution_context::UriResolutionContext, uri::Uri,
+    error::Error, interface_implementation::InterfaceImplementations,
+    invoke_options::InvokeOptions, invoker::Invoker,
+    resolution::uri_resolution_context::UriResolutionContext, resource_limits::ResourceLimits,
+    uri::Uri, wasi_policy::WasiPolicy, wrap_log::WrapLogSink,
 };
+use tokio::runtime::Handle;
 
 /// `Subinvoker` implements wrap invocation capabilities, and is used by the `Client` to invoke wraps.
 pub struct Subinvoker {
     resolution_context: Arc<Mutex<UriResolutionContext>>,
     invoker: Arc<dyn Invoker>,
+    resource_limits: Option<ResourceLimits>,
+    wasi_policy: Option<WasiPolicy>,
+    wrap_uri: Option<Uri>,
 }
 
 impl Subinvoker {
@@ -19,8 +25,29 @@ impl Subinvoker {
         Self {
             invoker,
             resolution_context,
+            resource_limits: None,
+            wasi_policy: None,
+            wrap_uri: None,
         }
     }
+
+    /// Sets the resource limits of the wrap invoked with this subinvoker.
+    pub fn with_resource_limits(mut self, resource_limits: Option<ResourceLimits>) -> Self {
+        self.resource_limits = resource_limits;
+        self
+    }
+
+    /// Sets the WASI capabilities of the wrap invoked with this subinvoker.
+    pub fn with_wasi_policy(mut self, wasi_policy: Option<WasiPolicy>) -> Self {
+        self.wasi_policy = wasi_policy;
+        self
+    }
+
+    /// Sets the URI of the wrap invoked with this subinvoker.
+    pub fn with_wrap_uri(mut self, wrap_uri: Uri) -> Self {
+        self.wrap_uri = Some(wrap_uri);
+        self
+    }
 }
 
 impl Invoker for Subinvoker {
@@ -45,4 +72,27 @@ impl Invoker for Subinvoker {
     fn get_env_by_uri(&self, uri: &Uri) -> Option<Vec<u8>> {
         self.invoker.get_env_by_uri(uri)
     }
+    fn get_invoke_options(&self) -> Option<InvokeOptions> {
+        self.invoker.get_invoke_options()
+    }
+    fn get_resource_limits(&self) -> Option<ResourceLimits> {
+        self.resource_limits
+            .or_else(|| self.invoker.get_resource_limits())
+    }
+
+    fn get_wasi_policy(&self) -> Option<WasiPolicy> {
+        self.wasi_policy.clone()
+    }
+
+    fn get_wrap_uri(&self) -> Option<Uri> {
+        self.wrap_uri.clone()
+    }
+
+    fn get_log_sink(&self) -> Option<Arc<dyn WrapLogSink>> {
+        self.invoker.get_log_sink()
+    }
+
+    fn get_executor(&self) -> Option<Handle> {
+        self.invoker.get_executor()
+    }
 }
@@ -0,0 +1,213 @@
+//! Validation of msgpack encoded invocation arguments against the ABI of a wrap.
+
+use polywrap_core::error::ArgsMismatch;
+use wrap_manifest_schemas::{
+    abi::{Abi, TypeKind, TypeRef},
+    versions::PropertyDefinition,
+};
+
+use crate::{
+    abi_types::{integer_range, is_big_int, is_big_number, resolve, NamedType},
+    msgpack_value::MsgpackValue,
+};
+
+/// Checks the arguments of an invocation against the ABI of the invoked method,
+/// and returns every mismatch found. Arguments are valid if none are returned.
+///
+/// # Arguments
+///
+/// * `abi`: ABI of the invoked wrap.
+/// * `method`: Name of the invoked method.
+/// * `args`: Optional msgpack buffer representing the arguments.
+pub fn validate_args(abi: &Abi, method: &str, args: Option<&[u8]>) -> Vec<ArgsMismatch> {
+    let mut validator = Validator {
+        abi,
+        mismatches: Vec::new(),
+    };
+
+    let Some(method_definition) = abi.method(method) else {
+        validator.mismatch("$", format!("method `{method}` isn't declared in the ABI"));
+        return validator.mismatches;
+    };
+    let arguments = method_definition.arguments.as_deref().unwrap_or_default();
+
+    let args = match args.map(MsgpackValue::read).transpose() {
+        Ok(args) => args.unwrap_or(MsgpackValue::Nil),
+        Err(error) => {
+            validator.mismatch("$", error.to_string());
+            return validator.mismatches;
+        }
+    };
+
+    match &args {
+        MsgpackValue::Map(entries) => validator.check_properties(arguments, entries, "$"),
+        MsgpackValue::Nil => validator.check_properties(arguments, &[], "$"),
+        args => validator.mismatch(
+            "$",
+            format!("expected a map of arguments, got {}", args.type_name()),
+        ),
+    }
+
+    validator.mismatches
+}
+
+struct Validator<'a> {
+    abi: &'a Abi,
+    mismatches: Vec<ArgsMismatch>,
+}
+
+impl Validator<'_> {
+    fn mismatch(&mut self, path: &str, message: String) {
+        self.mismatches.push(ArgsMismatch {
+            path: path.to_string(),
+            message,
+        });
+    }
+
+    fn expected(&mut self, path: &str, type_ref: &TypeRef, value: &MsgpackValue) {
+        self.mismatch(
+            path,
+            format!("expected {type_ref}, got {}", value.type_name()),
+        );
+    }
+
+    fn check(&mut self, value: &MsgpackValue, type_ref: &TypeRef, path: &str) {
+        if *value == MsgpackValue::Nil {
+            if type_ref.required {
+                self.expected(path, type_ref, value);
+            }
+            return;
+        }
+
+        match &type_ref.kind {
+            TypeKind::Scalar(scalar) => self.check_scalar(value, type_ref, scalar, path),
+            TypeKind::Object(name) | TypeKind::Enum(name) | TypeKind::Unresolved(name) => {
+                match (resolve(self.abi, name), value) {
+                    (Some(NamedType::Object(properties)), MsgpackValue::Map(entries)) => {
+                        self.check_properties(properties, entries, path)
+                    }
+                    (Some(NamedType::Enum(constants)), MsgpackValue::Str(constant)) => {
+                        if !constants.contains(constant) {
+                            self.mismatch(
+                                path,
+                                format!("`{constant}` isn't a constant of {}", type_ref.kind),
+                            );
+                        }
+                    }
+                    (Some(NamedType::Enum(constants)), value) if value.as_i64().is_some() => {
+                        let index = value.as_i64().unwrap_or_default();
+                        if usize::try_from(index).map_or(true, |index| index >= constants.len()) {
+                            self.mismatch(
+                                path,
+                                format!(
+                                    "{index} is out of the range of {} [0, {}]",
+                                    type_ref.kind,
+                                    constants.len() as i64 - 1
+                                ),
+                            );
+                        }
+                    }
+                    (Some(_), value) => self.expected(path, type_ref, value),
+                    (None, _) => {
+                        self.mismatch(path, format!("type `{name}` isn't declared in the ABI"))
+                    }
+                }
+            }
+            TypeKind::Array(item) => match value {
+                MsgpackValue::Array(items) => {
+                    for (i, item_value) in items.iter().enumerate() {
+                        self.check(item_value, item, &format!("{path}[{i}]"));
+                    }
+                }
+                value => self.expected(path, type_ref, value),
+            },
+            TypeKind::Map {
+                key,
+                value: map_value,
+            } => match value {
+                MsgpackValue::ExtMap(entries) | MsgpackValue::Map(entries) => {
+                    let key_type = TypeRef {
+                        kind: TypeKind::Scalar(key.clone()),
+                        required: true,
+                    };
+                    for (entry_key, entry_value) in entries {
+                        let entry_path = format!("{path}[{:?}]", entry_key.to_key());
+                        self.check(entry_key, &key_type, &entry_path);
+                        self.check(entry_value, map_value, &entry_path);
+                    }
+                }
+                value => self.expected(path, type_ref, value),
+            },
+        }
+    }
+
+    fn check_scalar(&mut self, value: &MsgpackValue, type_ref: &TypeRef, scalar: &str, path: &str) {
+        if let Some((min, max)) = integer_range(scalar) {
+            match value.as_i64() {
+                Some(integer) if integer < min || integer > max => self.mismatch(
+                    path,
+                    format!("{integer} is out of the range of {scalar} [{min}, {max}]"),
+                ),
+                Some(_) => {}
+                None => self.expected(path, type_ref, value),
+            }
+            return;
+        }
+
+        match (scalar, value) {
+            ("String", MsgpackValue::Str(_))
+            | ("Boolean", MsgpackValue::Bool(_))
+            | ("Bytes", MsgpackValue::Bin(_)) => {}
+            ("BigInt", MsgpackValue::Str(value)) => {
+                if !is_big_int(value) {
+                    self.mismatch(path, format!("`{value}` isn't a BigInt"));
+                }
+            }
+            ("BigNumber", MsgpackValue::Str(value)) => {
+                if !is_big_number(value) {
+                    self.mismatch(path, format!("`{value}` isn't a BigNumber"));
+                }
+            }
+            ("JSON", MsgpackValue::Str(value)) => {
+                if let Err(e) = serde_json::from_str::<serde_json::Value>(value) {
+                    self.mismatch(path, format!("invalid JSON string: {e}"));
+                }
+            }
+            ("String" | "Boolean" | "Bytes" | "BigInt" | "BigNumber" | "JSON", value) => {
+                self.expected(path, type_ref, value)
+            }
+            (scalar, _) => self.mismatch(path, format!("unsupported scalar type `{scalar}`")),
+        }
+    }
+
+    fn check_properties(
+        &mut self,
+        properties: &[Box<PropertyDefinition>],
+        entries: &[(MsgpackValue, MsgpackValue)],
+        path: &str,
+    ) {
+        for (key, _) in entries {
+            let is_declared = match key {
+                MsgpackValue::Str(key) => properties
+                    .iter()
+                    .any(|property| property.name.as_ref() == Some(key)),
+                _ => false,
+            };
+            if !is_declared {
+                self.mismatch(
+                    &format!("{path}.{}", key.to_key()),
+                    "unknown property".to_string(),
+                );
+            }
+        }
+
+        for property in properties {
+            let name = property.name.as_deref().unwrap_or_default();
+            let value = entries
+                .iter()
+                .find(|(key, _)| matches!(key, MsgpackValue::Str(key) if key == name))
+                .map_or(&MsgpackValue::Nil, |(_, value)| value);
+            self.check(value, &property.type_ref(), &format!("{path}.{name}"));
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use polywrap_client::client::Client;
+use polywrap_client_builder::{ClientConfig, ClientConfigBuilder};
+use polywrap_core::{
+    error::{ArgsMismatch, Error},
+    invoker::Invoker,
+    macros::uri,
+    package::{GetManifestOptions, WrapPackage},
+    uri::Uri,
+    wrapper::{GetFileOptions, Wrapper},
+};
+use polywrap_msgpack_serde::to_vec;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use wrap_manifest_schemas::versions::{WrapManifest, WrapManifestAbi};
+
+fn scalar(name: &str, type_: &str, required: bool) -> Value {
+    let scalar = json!({ "kind": 4, "name": name, "required": required, "type": type_ });
+    json!({ "kind": 34, "name": name, "required": required, "type": type_, "scalar": scalar })
+}
+
+fn get_manifest() -> WrapManifest {
+    let arguments = json!([
+        {
+            "kind": 34,
+            "name": "item",
+            "required": true,
+            "type": "Item",
+            "object": { "kind": 8192, "name": "item", "required": true, "type": "Item" }
+        },
+        {
+            "kind": 34,
+            "name": "tags",
+            "required": true,
+            "type": "[String]",
+            "array": {
+                "kind": 18,
+                "name": "tags",
+                "required": true,
+                "type": "[String]",
+                "item": { "kind": 4, "name": "tags", "required": true, "type": "String" },
+                "scalar": { "kind": 4, "name": "tags", "required": true, "type": "String" }
+            }
+        },
+        {
+            "kind": 34,
+            "name": "counts",
+            "type": "Map<String, Int8>",
+            "map": {
+                "kind": 262146,
+                "name": "counts",
+                "type": "Map<String, Int8>",
+                "key": { "kind": 4, "name": "counts", "required": true, "type": "String" },
+                "value": { "kind": 4, "name": "counts", "required": true, "type": "Int8" },
+                "scalar": { "kind": 4, "name": "counts", "required": true, "type": "Int8" }
+            }
+        },
+        {
+            "kind": 34,
+            "name": "kind",
+            "type": "Kind",
+            "enum": { "kind": 16384, "name": "kind", "type": "Kind" }
+        }
+    ]);
+
+    WrapManifest {
+        name: "Items".to_string(),
+        type_: "wasm".to_string(),
+        version: "0.1".to_string(),
+        abi: serde_json::from_value::<WrapManifestAbi>(json!({
+            "version": "0.1",
+            "moduleType": {
+                "kind": 128,
+                "type": "Module",
+                "methods": [{
+                    "kind": 64,
+                    "name": "add",
+                    "required": true,
+                    "type": "Method",
+                    "arguments": arguments,
+                    "return": scalar("add", "Boolean", true)
+                }]
+            },
+            "objectTypes": [{
+                "kind": 1,
+                "type": "Item",
+                "properties": [scalar("name", "String", true), scalar("amount", "BigInt", true)]
+            }],
+            "enumTypes": [{ "kind": 8, "type": "Kind", "constants": ["SMALL", "LARGE"] }]
+        }))
+        .unwrap(),
+    }
+}
+
+#[derive(Debug)]
+struct ItemsWrapper;
+
+impl Wrapper for ItemsWrapper {
+    fn invoke(
+        &self,
+        _: &str,
+        _: Option<&[u8]>,
+        _: Option<&[u8]>,
+        _: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, Error> {
+        Ok(to_vec(&true)?)
+    }
+
+    fn get_file(&self, _: &GetFileOptions) -> Result<Vec<u8>, Error> {
+        unimplemented!()
+    }
+}
+
+#[derive(Debug, Default)]
+struct ItemsPackage {
+    manifest_reads: AtomicUsize,
+}
+
+impl WrapPackage for ItemsPackage {
+    fn create_wrapper(&self) -> Result<Arc<dyn Wrapper>, Error> {
+        Ok(Arc::new(ItemsWrapper))
+    }
+
+    fn get_manifest(&self, _: Option<&GetManifestOptions>) -> Result<WrapManifest, Error> {
+        self.manifest_reads.fetch_add(1, Ordering::SeqCst);
+        Ok(get_manifest())
+    }
+}
+
+#[derive(Serialize)]
+struct Args {
+    item: Item,
+    tags: Vec<String>,
+    counts: Option<BTreeMap<String, i32>>,
+    kind: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct Item {
+    name: String,
+    amount: String,
+}
+
+#[derive(Serialize)]
+struct InvalidArgs {
+    item: InvalidItem,
+    tags: Vec<Option<String>>,
+    counts: BTreeMap<String, i32>,
+    kind: u32,
+    color: String,
+}
+
+#[derive(Serialize)]
+struct InvalidItem {
+    amount: String,
+}
+
+#[derive(Serialize)]
+struct WrongShapeArgs {
+    tags: String,
+}
+
+fn get_valid_args() -> Vec<u8> {
+    to_vec(&Args {
+        item: Item {
+            name: "apple".to_string(),
+            amount: "10000000000000000000".to_string(),
+        },
+        tags: vec!["fruit".to_string()],
+        counts: Some(BTreeMap::from([("a".to_string(), -1)])),
+        kind: Some(1),
+    })
+    .unwrap()
+}
+
+fn get_invalid_args() -> Vec<u8> {
+    to_vec(&InvalidArgs {
+        item: InvalidItem {
+            amount: "1.5".to_string(),
+        },
+        tags: vec![Some("fruit".to_string()), None],
+        counts: BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 200)]),
+        kind: 2,
+        color: "red".to_string(),
+    })
+    .unwrap()
+}
+
+fn build_client(validate_args: bool) -> Client {
+    let mut config = ClientConfig::new();
+    config.add_package(uri!("mock/items"), Arc::new(ItemsPackage::default()));
+    if validate_args {
+        config.set_args_validation(true);
+    }
+
+    Client::new(config.try_into().unwrap())
+}
+
+fn invoke(client: &Client, method: &str, args: &[u8]) -> Result<bool, Error> {
+    client.invoke::<bool>(&uri!("mock/items"), method, Some(args), None, None)
+}
+
+fn mismatch(path: &str, message: &str) -> ArgsMismatch {
+    ArgsMismatch {
+        path: path.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[test]
+fn args_are_not_validated_by_default() {
+    let client = build_client(false);
+
+    assert!(invoke(&client, "add", &get_invalid_args()).unwrap());
+}
+
+#[test]
+fn valid_args_are_accepted() {
+    let client = build_client(true);
+
+    assert!(invoke(&client, "add", &get_valid_args()).unwrap());
+}
+
+#[test]
+fn every_mismatch_is_listed() {
+    let client = build_client(true);
+
+    let error = invoke(&client, "add", &get_invalid_args()).unwrap_err();
+
+    let Error::ArgsValidationError {
+        uri,
+        method,
+        mismatches,
+    } = error
+    else {
+        panic!("Expected an args validation error, got: {error:?}");
+    };
+    assert_eq!(uri, "wrap://mock/items");
+    assert_eq!(method, "add");
+    assert_eq!(
+        mismatches,
+        vec![
+            mismatch("$.color", "unknown property"),
+            mismatch("$.item.name", "expected String!, got nil"),
+            mismatch("$.item.amount", "`1.5` isn't a BigInt"),
+            mismatch("$.tags[1]", "expected String!, got nil"),
+            mismatch(
+                "$.counts[\"b\"]",
+                "200 is out of the range of Int8 [-128, 127]"
+            ),
+            mismatch("$.kind", "2 is out of the range of Kind [0, 1]"),
+        ]
+    );
+}
+
+#[test]
+fn args_of_the_wrong_shape_are_rejected() {
+    let client = build_client(true);
+
+    let args = to_vec(&WrongShapeArgs {
+        tags: "fruit".to_string(),
+    })
+    .unwrap();
+    let error = invoke(&client, "add", &args).unwrap_err();
+
+    let Error::ArgsValidationError { mismatches, .. } = error else {
+        panic!("Expected an args validation error, got: {error:?}");
+    };
+    assert_eq!(
+        mismatches,
+        vec![
+            mismatch("$.item", "expected Item!, got nil"),
+            mismatch("$.tags", "expected [String!]!, got string"),
+        ]
+    );
+}
+
+#[test]
+fn unknown_methods_are_rejected() {
+    let client = build_client(true);
+
+    let error = invoke(&client, "remove", &get_valid_args()).unwrap_err();
+
+    let Error::ArgsValidationError { mismatches, .. } = error else {
+        panic!("Expected an args validation error, got: {error:?}");
+    };
+    assert_eq!(
+        mismatches,
+        vec![mismatch("$", "method `remove` isn't declared in the ABI")]
+    );
+}
+
+#[test]
+fn abi_is_read_once_per_wrap() {
+    let package = Arc::new(ItemsPackage::default());
+    let mut config = ClientConfig::new();
+    config
+        .add_package(uri!("mock/items"), package.clone())
+        .set_args_validation(true);
+    let client = Client::new(config.try_into().unwrap());
+
+    for _ in 0..3 {
+        assert!(invoke(&client, "add", &get_valid_args()).unwrap());
+    }
+
+    assert_eq!(package.manifest_reads.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,222 @@
+use polywrap_client::client::Client;
+use polywrap_client_builder::{ClientConfig, ClientConfigBuilder};
+use polywrap_core::{
+    env_merge::EnvMergeStrategy,
+    error::Error,
+    invoker::Invoker,
+    macros::uri,
+    uri::Uri,
+    wrapper::{GetFileOptions, Wrapper},
+};
+use polywrap_msgpack_serde::to_vec;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug)]
+struct EnvWrapper;
+
+impl Wrapper for EnvWrapper {
+    fn invoke(
+        &self,
+        _: &str,
+        _: Option<&[u8]>,
+        env: Option<&[u8]>,
+        _: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, Error> {
+        Ok(env.unwrap_or(&to_vec(&Env::default()).unwrap()).to_vec())
+    }
+
+    fn get_file(&self, _: &GetFileOptions) -> Result<Vec<u8>, Error> {
+        unimplemented!()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+struct Env {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    endpoint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    connection: Option<Connection>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+struct Connection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeout: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retries: Option<u32>,
+}
+
+fn get_configured_env() -> Env {
+    Env {
+        api_key: Some("secret".to_string()),
+        endpoint: Some("https://configured.io".to_string()),
+        connection: Some(Connection {
+            timeout: Some(10),
+            retries: Some(3),
+        }),
+    }
+}
+
+fn get_invocation_env() -> Env {
+    Env {
+        api_key: None,
+        endpoint: Some("https://invocation.io".to_string()),
+        connection: Some(Connection {
+            timeout: Some(5),
+            retries: None,
+        }),
+    }
+}
+
+fn build_client(strategy: Option<EnvMergeStrategy>, envs: &[(Uri, &Env)]) -> Client {
+    let mut config = ClientConfig::new();
+    config
+        .add_wrapper(uri!("mock/env"), Arc::new(EnvWrapper))
+        .add_redirect(uri!("mock/alias"), uri!("mock/env"));
+
+    for (uri, env) in envs {
+        config.add_env(uri.clone(), to_vec(env).unwrap());
+    }
+
+    if let Some(strategy) = strategy {
+        config.set_env_merge_strategy(strategy);
+    }
+
+    Client::new(config.try_into().unwrap())
+}
+
+fn invoke(client: &Client, uri: &Uri, env: Option<&Env>) -> Env {
+    let env = env.map(|env| to_vec(env).unwrap());
+    client
+        .invoke::<Env>(uri, "getEnv", None, env.as_deref(), None)
+        .unwrap()
+}
+
+#[test]
+fn invocation_env_replaces_configured_env_by_default() {
+    let client = build_client(None, &[(uri!("mock/env"), &get_configured_env())]);
+
+    let result = invoke(&client, &uri!("mock/env"), Some(&get_invocation_env()));
+
+    assert_eq!(result, get_invocation_env());
+}
+
+#[test]
+fn invocation_env_is_shallow_merged_with_configured_env() {
+    let client = build_client(
+        Some(EnvMergeStrategy::Shallow),
+        &[(uri!("mock/env"), &get_configured_env())],
+    );
+
+    let result = invoke(&client, &uri!("mock/env"), Some(&get_invocation_env()));
+
+    assert_eq!(
+        result,
+        Env {
+            api_key: Some("secret".to_string()),
+            endpoint: Some("https://invocation.io".to_string()),
+            connection: Some(Connection {
+                timeout: Some(5),
+                retries: None,
+            }),
+        }
+    );
+}
+
+#[test]
+fn invocation_env_is_deep_merged_with_configured_env() {
+    let client = build_client(
+        Some(EnvMergeStrategy::Deep),
+        &[(uri!("mock/env"), &get_configured_env())],
+    );
+
+    let result = invoke(&client, &uri!("mock/env"), Some(&get_invocation_env()));
+
+    assert_eq!(
+        result,
+        Env {
+            api_key: Some("secret".to_string()),
+            endpoint: Some("https://invocation.io".to_string()),
+            connection: Some(Connection {
+                timeout: Some(5),
+                retries: Some(3),
+            }),
+        }
+    );
+}
+
+#[test]
+fn configured_env_is_used_without_invocation_env() {
+    let client = build_client(
+        Some(EnvMergeStrategy::Deep),
+        &[(uri!("mock/env"), &get_configured_env())],
+    );
+
+    let result = invoke(&client, &uri!("mock/env"), None);
+
+    assert_eq!(result, get_configured_env());
+}
+
+#[test]
+fn redirected_uri_env_overrides_resolved_uri_env() {
+    let alias_env = Env {
+        api_key: None,
+        endpoint: Some("https://alias.io".to_string()),
+        connection: Some(Connection {
+            timeout: None,
+            retries: Some(1),
+        }),
+    };
+    let client = build_client(
+        Some(EnvMergeStrategy::Deep),
+        &[
+            (uri!("mock/env"), &get_configured_env()),
+            (uri!("mock/alias"), &alias_env),
+        ],
+    );
+
+    let invocation_env = Env {
+        api_key: None,
+        endpoint: None,
+        connection: Some(Connection {
+            timeout: Some(5),
+            retries: None,
+        }),
+    };
+    let result = invoke(&client, &uri!("mock/alias"), Some(&invocation_env));
+
+    assert_eq!(
+        result,
+        Env {
+            api_key: Some("secret".to_string()),
+            endpoint: Some("https://alias.io".to_string()),
+            connection: Some(Connection {
+                timeout: Some(5),
+                retries: Some(1),
+            }),
+        }
+    );
+}
+
+#[test]
+fn redirected_uri_env_replaces_resolved_uri_env_by_default() {
+    let alias_env = Env {
+        api_key: None,
+        endpoint: Some("https://alias.io".to_string()),
+        connection: None,
+    };
+    let client = build_client(
+        None,
+        &[
+            (uri!("mock/env"), &get_configured_env()),
+            (uri!("mock/alias"), &alias_env),
+        ],
+    );
+
+    let result = invoke(&client, &uri!("mock/alias"), None);
+
+    assert_eq!(result, alias_env);
+}
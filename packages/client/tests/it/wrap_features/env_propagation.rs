@@ -0,0 +1,200 @@
+use polywrap_client::client::Client;
+use polywrap_client_builder::{ClientConfig, ClientConfigBuilder};
+use polywrap_core::{
+    client::CoreClientConfig,
+    env_inheritance::EnvInheritance,
+    error::Error,
+    invoker::Invoker,
+    macros::uri,
+    resolution::uri_resolution_context::UriPackageOrWrapper,
+    uri::Uri,
+    wrapper::{GetFileOptions, Wrapper},
+};
+use polywrap_msgpack_serde::{from_slice, to_vec};
+use polywrap_resolvers::{base_resolver::BaseResolver, static_resolver::StaticResolver};
+use std::{collections::HashMap, sync::Arc};
+
+/// Subinvokes the first URI of its arguments with the remaining ones,
+/// or returns its env once none are left.
+#[derive(Debug)]
+struct ChainWrapper;
+
+impl Wrapper for ChainWrapper {
+    fn invoke(
+        &self,
+        _: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        invoker: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, Error> {
+        let uris: Vec<String> = from_slice(args.unwrap()).unwrap();
+
+        match uris.split_first() {
+            Some((uri, rest)) => invoker.invoke_raw(
+                &uri.parse().unwrap(),
+                "call",
+                Some(&to_vec(&rest).unwrap()),
+                None,
+                None,
+            ),
+            None => {
+                let env = env.map(|env| from_slice::<String>(env).unwrap());
+                Ok(to_vec(&env).unwrap())
+            }
+        }
+    }
+
+    fn get_file(&self, _: &GetFileOptions) -> Result<Vec<u8>, Error> {
+        unimplemented!()
+    }
+}
+
+fn build_config(envs: &[(Uri, &str)]) -> ClientConfig {
+    let mut config = ClientConfig::new();
+    config
+        .add_wrapper(uri!("mock/parent"), Arc::new(ChainWrapper))
+        .add_wrapper(uri!("mock/middle"), Arc::new(ChainWrapper))
+        .add_wrapper(uri!("mock/child"), Arc::new(ChainWrapper))
+        .add_redirect(uri!("mock/alias"), uri!("mock/child"));
+
+    for (uri, env) in envs {
+        config.add_env(uri.clone(), to_vec(env).unwrap());
+    }
+
+    config
+}
+
+fn invoke_chain(client: &Client, uris: &[&str]) -> Option<String> {
+    client
+        .invoke::<Option<String>>(
+            &uri!("mock/parent"),
+            "call",
+            Some(&to_vec(&uris).unwrap()),
+            None,
+            None,
+        )
+        .unwrap()
+}
+
+#[test]
+fn subinvoked_env_is_looked_up_through_redirects() {
+    let config = build_config(&[
+        (uri!("mock/parent"), "parent"),
+        (uri!("mock/child"), "child"),
+    ]);
+    let client = Client::new(config.try_into().unwrap());
+
+    assert_eq!(
+        invoke_chain(&client, &["mock/alias"]),
+        Some("child".to_string())
+    );
+}
+
+#[test]
+fn env_of_redirected_uri_takes_precedence() {
+    let config = build_config(&[(uri!("mock/alias"), "alias"), (uri!("mock/child"), "child")]);
+    let client = Client::new(config.try_into().unwrap());
+
+    assert_eq!(
+        invoke_chain(&client, &["mock/alias"]),
+        Some("alias".to_string())
+    );
+    assert_eq!(
+        invoke_chain(&client, &["mock/child"]),
+        Some("child".to_string())
+    );
+}
+
+#[test]
+fn subinvoked_env_is_looked_up_through_untracked_redirects() {
+    // Unlike the default resolver, `BaseResolver` doesn't track the resolution path
+    let redirects = StaticResolver::new(HashMap::from([(
+        uri!("mock/alias"),
+        UriPackageOrWrapper::Uri(uri!("mock/child")),
+    )]));
+    let wrappers = StaticResolver::new(HashMap::from([
+        (
+            uri!("mock/parent"),
+            UriPackageOrWrapper::Wrapper(uri!("mock/parent"), Arc::new(ChainWrapper)),
+        ),
+        (
+            uri!("mock/child"),
+            UriPackageOrWrapper::Wrapper(uri!("mock/child"), Arc::new(ChainWrapper)),
+        ),
+    ]));
+
+    let client = Client::new(CoreClientConfig {
+        resolver: Arc::new(BaseResolver::new(Box::new(wrappers), Box::new(redirects))),
+        envs: Some(HashMap::from([(
+            uri!("mock/child"),
+            to_vec(&"child").unwrap(),
+        )])),
+        interfaces: None,
+        ..Default::default()
+    });
+
+    assert_eq!(
+        invoke_chain(&client, &["mock/alias"]),
+        Some("child".to_string())
+    );
+}
+
+#[test]
+fn subinvoked_env_is_not_inherited_by_default() {
+    let config = build_config(&[(uri!("mock/parent"), "parent")]);
+    let client = Client::new(config.try_into().unwrap());
+
+    assert_eq!(invoke_chain(&client, &["mock/alias"]), None);
+    assert_eq!(invoke_chain(&client, &["mock/child"]), None);
+}
+
+#[test]
+fn subinvoked_env_falls_back_to_parent_env() {
+    let mut config = build_config(&[(uri!("mock/parent"), "parent")]);
+    config.set_env_inheritance(EnvInheritance::Fallback);
+    let client = Client::new(config.try_into().unwrap());
+
+    assert_eq!(
+        invoke_chain(&client, &["mock/alias"]),
+        Some("parent".to_string())
+    );
+    assert_eq!(
+        invoke_chain(&client, &["mock/middle", "mock/alias"]),
+        Some("parent".to_string())
+    );
+}
+
+#[test]
+fn configured_env_takes_precedence_over_inherited_env() {
+    let mut config = build_config(&[
+        (uri!("mock/parent"), "parent"),
+        (uri!("mock/middle"), "middle"),
+        (uri!("mock/child"), "child"),
+    ]);
+    config.set_env_inheritance(EnvInheritance::Fallback);
+    let client = Client::new(config.try_into().unwrap());
+
+    assert_eq!(
+        invoke_chain(&client, &["mock/alias"]),
+        Some("child".to_string())
+    );
+    assert_eq!(
+        invoke_chain(&client, &["mock/middle"]),
+        Some("middle".to_string())
+    );
+}
+
+#[test]
+fn inherited_env_is_the_env_of_the_direct_parent() {
+    let mut config = build_config(&[
+        (uri!("mock/parent"), "parent"),
+        (uri!("mock/middle"), "middle"),
+    ]);
+    config.set_env_inheritance(EnvInheritance::Fallback);
+    let client = Client::new(config.try_into().unwrap());
+
+    assert_eq!(
+        invoke_chain(&client, &["mock/middle", "mock/alias"]),
+        Some("middle".to_string())
+    );
+}
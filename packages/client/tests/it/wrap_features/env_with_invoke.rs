@@ -90,6 +90,7 @@ fn build_client(uri: &Uri, env: Option<&[u8]>) -> Client {
         envs: Some(envs),
         resolver: Arc::new(base_resolver),
         interfaces: None,
+        ..Default::default()
     };
 
     Client::new(config)
@@ -232,6 +233,7 @@ fn env_can_be_registered_for_any_uri_in_resolution_path() {
                     fs_resolver,
                 ])),
                 interfaces: None,
+                ..Default::default()
             };
 
             Client::new(config)
@@ -270,6 +272,7 @@ fn env_can_be_registered_for_any_uri_in_resolution_path() {
                     fs_resolver,
                 ])),
                 interfaces: None,
+                ..Default::default()
             };
 
             Client::new(config)
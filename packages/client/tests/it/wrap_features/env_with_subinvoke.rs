@@ -110,6 +110,7 @@ fn build_client(subinvoker_env: Option<&[u8]>, subinvoked_env: Option<&[u8]>) ->
         envs: Some(envs),
         resolver: Arc::new(base_resolver),
         interfaces: None,
+        ..Default::default()
     };
 
     Client::new(config)
@@ -317,6 +318,7 @@ fn subinvoker_env_does_not_override_subinvoked_env() {
             envs: Some(envs),
             resolver: Arc::new(base_resolver),
             interfaces: None,
+            ..Default::default()
         };
 
         Client::new(config)
@@ -0,0 +1,226 @@
+use polywrap_client::client::Client;
+use polywrap_client_builder::{ClientConfig, ClientConfigBuilder};
+use polywrap_core::{
+    error::Error,
+    interceptor::{Invocation, InvokeInterceptor},
+    invoker::Invoker,
+    macros::uri,
+    uri::Uri,
+    wrapper::{GetFileOptions, Wrapper},
+};
+use polywrap_msgpack_serde::to_vec;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug)]
+struct EchoWrapper;
+
+impl Wrapper for EchoWrapper {
+    fn invoke(
+        &self,
+        method: &str,
+        args: Option<&[u8]>,
+        _: Option<&[u8]>,
+        _: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, Error> {
+        match method {
+            "echo" => Ok(args.unwrap_or_default().to_vec()),
+            _ => Err(Error::wrapper(format!("Unknown method: {method}"))),
+        }
+    }
+
+    fn get_file(&self, _: &GetFileOptions) -> Result<Vec<u8>, Error> {
+        unimplemented!()
+    }
+}
+
+#[derive(Debug)]
+struct CallerWrapper;
+
+impl Wrapper for CallerWrapper {
+    fn invoke(
+        &self,
+        _: &str,
+        args: Option<&[u8]>,
+        _: Option<&[u8]>,
+        invoker: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, Error> {
+        invoker.invoke_raw(&uri!("mock/echo"), "echo", args, None, None)
+    }
+
+    fn get_file(&self, _: &GetFileOptions) -> Result<Vec<u8>, Error> {
+        unimplemented!()
+    }
+}
+
+#[derive(Debug, Default)]
+struct RecordingInterceptor {
+    calls: Mutex<Vec<String>>,
+}
+
+impl InvokeInterceptor for RecordingInterceptor {
+    fn before_invoke(&self, invocation: &mut Invocation) -> Option<Result<Vec<u8>, Error>> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("before {}.{}", invocation.uri, invocation.method));
+        None
+    }
+
+    fn after_invoke(
+        &self,
+        invocation: &Invocation,
+        result: Result<Vec<u8>, Error>,
+    ) -> Result<Vec<u8>, Error> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("after {}.{}", invocation.uri, invocation.method));
+        result
+    }
+}
+
+#[derive(Debug)]
+struct RewritingInterceptor;
+
+impl InvokeInterceptor for RewritingInterceptor {
+    fn before_invoke(&self, invocation: &mut Invocation) -> Option<Result<Vec<u8>, Error>> {
+        match invocation.method.as_str() {
+            "blocked" => Some(Ok(to_vec(&"blocked").unwrap())),
+            _ => {
+                invocation.args = Some(to_vec(&"rewritten").unwrap());
+                None
+            }
+        }
+    }
+
+    fn after_invoke(
+        &self,
+        _: &Invocation,
+        result: Result<Vec<u8>, Error>,
+    ) -> Result<Vec<u8>, Error> {
+        result.map_err(|e| Error::OtherError(format!("intercepted: {e}")))
+    }
+}
+
+#[derive(Debug)]
+struct RedirectingInterceptor;
+
+impl InvokeInterceptor for RedirectingInterceptor {
+    fn before_invoke(&self, invocation: &mut Invocation) -> Option<Result<Vec<u8>, Error>> {
+        invocation.uri = uri!("mock/echo");
+        None
+    }
+}
+
+fn build_client(interceptors: Vec<Arc<dyn InvokeInterceptor>>) -> Client {
+    let mut config = ClientConfig::new();
+    config
+        .add_wrapper(uri!("mock/echo"), Arc::new(EchoWrapper))
+        .add_wrapper(uri!("mock/caller"), Arc::new(CallerWrapper))
+        .add_interceptors(interceptors);
+
+    Client::new(config.try_into().unwrap())
+}
+
+#[test]
+fn interceptor_is_called_around_subinvocations() {
+    let recorder = Arc::new(RecordingInterceptor::default());
+    let client = build_client(vec![recorder.clone()]);
+
+    let result = client
+        .invoke::<String>(
+            &uri!("mock/caller"),
+            "call",
+            Some(&to_vec(&"hello").unwrap()),
+            None,
+            None,
+        )
+        .unwrap();
+
+    assert_eq!(result, "hello");
+    assert_eq!(
+        *recorder.calls.lock().unwrap(),
+        vec![
+            "before wrap://mock/caller.call",
+            "before wrap://mock/echo.echo",
+            "after wrap://mock/echo.echo",
+            "after wrap://mock/caller.call",
+        ]
+    );
+}
+
+#[test]
+fn interceptor_can_modify_args() {
+    let client = build_client(vec![Arc::new(RewritingInterceptor)]);
+
+    let result = client
+        .invoke::<String>(
+            &uri!("mock/echo"),
+            "echo",
+            Some(&to_vec(&"hello").unwrap()),
+            None,
+            None,
+        )
+        .unwrap();
+
+    assert_eq!(result, "rewritten");
+}
+
+#[test]
+fn interceptor_can_short_circuit() {
+    let recorder = Arc::new(RecordingInterceptor::default());
+    let client = build_client(vec![Arc::new(RewritingInterceptor), recorder.clone()]);
+
+    let result = client
+        .invoke::<String>(&uri!("mock/echo"), "blocked", None, None, None)
+        .unwrap();
+
+    assert_eq!(result, "blocked");
+    assert!(recorder.calls.lock().unwrap().is_empty());
+}
+
+#[test]
+fn interceptor_can_transform_errors() {
+    let client = build_client(vec![Arc::new(RewritingInterceptor)]);
+
+    let error = client
+        .invoke::<String>(&uri!("mock/echo"), "unknown", None, None, None)
+        .unwrap_err();
+
+    assert!(matches!(error, Error::OtherError(message) if message.starts_with("intercepted: ")));
+}
+
+#[test]
+fn interceptor_can_redirect_invocations() {
+    let client = build_client(vec![Arc::new(RedirectingInterceptor)]);
+
+    let result = client
+        .invoke::<String>(
+            &uri!("mock/other"),
+            "echo",
+            Some(&to_vec(&"hello").unwrap()),
+            None,
+            None,
+        )
+        .unwrap();
+
+    assert_eq!(result, "hello");
+}
+
+#[test]
+fn interceptor_cant_redirect_invocations_of_a_given_wrapper() {
+    let client = build_client(vec![Arc::new(RedirectingInterceptor)]);
+
+    let error = client
+        .invoke_wrapper::<String, _>(
+            &CallerWrapper,
+            &uri!("mock/caller"),
+            "call",
+            Some(&to_vec(&"hello").unwrap()),
+            None,
+            None,
+        )
+        .unwrap_err();
+
+    assert!(matches!(error, Error::WrapperError { message, .. } if message.contains("mock/echo")));
+}
@@ -60,6 +60,7 @@ fn test_interface_implementation() {
         envs: None,
         interfaces: Some(interfaces),
         resolver: Arc::new(base_resolver),
+        ..Default::default()
     });
 
     let mock_response = ModuleMethodResponse {
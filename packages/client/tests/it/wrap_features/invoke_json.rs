@@ -0,0 +1,393 @@
+use polywrap_client::client::Client;
+use polywrap_client_builder::{ClientConfig, ClientConfigBuilder};
+use polywrap_core::{
+    error::{Error, ErrorKind},
+    invoker::Invoker,
+    macros::uri,
+    package::{GetManifestOptions, WrapPackage},
+    resolution::{
+        uri_resolution_context::{UriPackageOrWrapper, UriResolutionContext},
+        uri_resolver::UriResolver,
+    },
+    uri::Uri,
+    wrapper::{GetFileOptions, Wrapper},
+};
+use polywrap_msgpack_serde::{from_slice, to_vec, BigIntWrapper, BigNumber, JSONString};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+use wrap_manifest_schemas::versions::{WrapManifest, WrapManifestAbi};
+
+fn scalar(name: &str, type_: &str, required: bool) -> Value {
+    let scalar = json!({ "kind": 4, "name": name, "required": required, "type": type_ });
+    json!({ "kind": 34, "name": name, "required": required, "type": type_, "scalar": scalar })
+}
+
+fn object(name: &str, type_: &str, required: bool) -> Value {
+    let object = json!({ "kind": 8192, "name": name, "required": required, "type": type_ });
+    json!({ "kind": 34, "name": name, "required": required, "type": type_, "object": object })
+}
+
+fn get_manifest() -> WrapManifest {
+    let item_properties = json!([
+        scalar("id", "BigInt", true),
+        scalar("amount", "BigNumber", false),
+        scalar("data", "Bytes", true),
+        scalar("meta", "JSON", false),
+        {
+            "kind": 34,
+            "name": "kind",
+            "required": true,
+            "type": "Kind",
+            "enum": { "kind": 16384, "name": "kind", "required": true, "type": "Kind" }
+        },
+        {
+            "kind": 34,
+            "name": "tags",
+            "type": "[String]",
+            "array": {
+                "kind": 18,
+                "name": "tags",
+                "type": "[String]",
+                "item": { "kind": 4, "name": "tags", "required": true, "type": "String" },
+                "scalar": { "kind": 4, "name": "tags", "required": true, "type": "String" }
+            }
+        },
+        {
+            "kind": 34,
+            "name": "counts",
+            "required": true,
+            "type": "Map<String, UInt8>",
+            "map": {
+                "kind": 262146,
+                "name": "counts",
+                "required": true,
+                "type": "Map<String, UInt8>",
+                "key": { "kind": 4, "name": "counts", "required": true, "type": "String" },
+                "value": { "kind": 4, "name": "counts", "required": true, "type": "UInt8" },
+                "scalar": { "kind": 4, "name": "counts", "required": true, "type": "UInt8" }
+            }
+        }
+    ]);
+
+    let method = |name: &str, return_: Value| {
+        json!({
+            "kind": 64,
+            "name": name,
+            "required": true,
+            "type": "Method",
+            "arguments": [object("item", "Item", true), scalar("count", "Int8", false)],
+            "return": return_
+        })
+    };
+
+    WrapManifest {
+        name: "Items".to_string(),
+        type_: "wasm".to_string(),
+        version: "0.1".to_string(),
+        abi: serde_json::from_value::<WrapManifestAbi>(json!({
+            "version": "0.1",
+            "moduleType": {
+                "kind": 128,
+                "type": "Module",
+                "methods": [
+                    method("echo", object("echo", "Echo", true)),
+                    method("check", scalar("check", "Boolean", true))
+                ]
+            },
+            "objectTypes": [
+                { "kind": 1, "type": "Item", "properties": item_properties },
+                {
+                    "kind": 1,
+                    "type": "Echo",
+                    "properties": [object("item", "Item", true), scalar("count", "Int8", false)]
+                }
+            ],
+            "enumTypes": [{ "kind": 8, "type": "Kind", "constants": ["SMALL", "LARGE"] }]
+        }))
+        .unwrap(),
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+struct Args {
+    item: Item,
+    count: Option<i8>,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+struct Item {
+    id: BigIntWrapper,
+    amount: Option<BigNumber>,
+    #[serde(with = "serde_bytes")]
+    data: Vec<u8>,
+    meta: Option<JSONString>,
+    kind: u32,
+    tags: Option<Vec<String>>,
+    counts: BTreeMap<String, u8>,
+}
+
+/// Echoes its arguments, or checks that they decode into `Args`.
+#[derive(Debug)]
+struct ItemsWrapper;
+
+impl Wrapper for ItemsWrapper {
+    fn invoke(
+        &self,
+        method: &str,
+        args: Option<&[u8]>,
+        _: Option<&[u8]>,
+        _: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, Error> {
+        let args = args.unwrap();
+        match method {
+            "echo" => Ok(args.to_vec()),
+            "check" => {
+                let args: Args = from_slice(args)?;
+                Ok(to_vec(&(args == get_expected_args()))?)
+            }
+            _ => unimplemented!(),
+        }
+    }
+
+    fn get_file(&self, _: &GetFileOptions) -> Result<Vec<u8>, Error> {
+        unimplemented!()
+    }
+}
+
+#[derive(Debug)]
+struct ItemsPackage;
+
+impl WrapPackage for ItemsPackage {
+    fn create_wrapper(&self) -> Result<Arc<dyn Wrapper>, Error> {
+        Ok(Arc::new(ItemsWrapper))
+    }
+
+    fn get_manifest(&self, _: Option<&GetManifestOptions>) -> Result<WrapManifest, Error> {
+        Ok(get_manifest())
+    }
+}
+
+/// Package of a later version of the wrap, whose `check` method was renamed `verify`.
+#[derive(Debug)]
+struct RenamedItemsPackage;
+
+impl WrapPackage for RenamedItemsPackage {
+    fn create_wrapper(&self) -> Result<Arc<dyn Wrapper>, Error> {
+        Ok(Arc::new(ItemsWrapper))
+    }
+
+    fn get_manifest(&self, _: Option<&GetManifestOptions>) -> Result<WrapManifest, Error> {
+        let mut manifest = get_manifest();
+        let mut abi = serde_json::to_value(&manifest.abi).unwrap();
+        abi["moduleType"]["methods"][1]["name"] = json!("verify");
+        manifest.abi = serde_json::from_value(abi).unwrap();
+        Ok(manifest)
+    }
+}
+
+/// Counts the URIs resolved by the resolver it wraps.
+#[derive(Debug)]
+struct CountingResolver {
+    resolver: Arc<dyn UriResolver>,
+    resolutions: AtomicUsize,
+}
+
+impl UriResolver for CountingResolver {
+    fn try_resolve_uri(
+        &self,
+        uri: &Uri,
+        client: Arc<dyn Invoker>,
+        resolution_context: Arc<Mutex<UriResolutionContext>>,
+    ) -> Result<UriPackageOrWrapper, Error> {
+        self.resolutions.fetch_add(1, Ordering::SeqCst);
+        self.resolver
+            .try_resolve_uri(uri, client, resolution_context)
+    }
+}
+
+fn build_client() -> Client {
+    let mut config = ClientConfig::new();
+    config.add_package(uri!("mock/items"), Arc::new(ItemsPackage));
+
+    Client::new(config.try_into().unwrap())
+}
+
+fn get_args() -> Value {
+    json!({
+        "item": {
+            "id": "123456789012345678901234567890",
+            "amount": 1.5,
+            "data": [1, 2, 255],
+            "meta": { "source": "test", "tags": [1, 2] },
+            "kind": "LARGE",
+            "tags": ["a", "b"],
+            "counts": { "x": 1, "y": 200 }
+        },
+        "count": -3
+    })
+}
+
+fn get_expected_args() -> Args {
+    Args {
+        item: Item {
+            id: BigIntWrapper("123456789012345678901234567890".parse().unwrap()),
+            amount: Some("1.5".parse().unwrap()),
+            data: vec![1, 2, 255],
+            meta: Some(json!({ "source": "test", "tags": [1, 2] }).into()),
+            kind: 1,
+            tags: Some(vec!["a".to_string(), "b".to_string()]),
+            counts: BTreeMap::from([("x".to_string(), 1), ("y".to_string(), 200)]),
+        },
+        count: Some(-3),
+    }
+}
+
+fn invoke_error(method: &str, args: Value) -> Error {
+    build_client()
+        .invoke_json(&uri!("mock/items"), method, args)
+        .unwrap_err()
+}
+
+fn assert_json_error(error: Error, expected_path: &str) {
+    match error {
+        Error::JsonError { path, .. } => assert_eq!(path, expected_path),
+        error => panic!("Expected a JSON error, got: {error:?}"),
+    }
+}
+
+#[test]
+fn args_are_encoded_with_wrap_types() {
+    let result = build_client()
+        .invoke_json(&uri!("mock/items"), "check", get_args())
+        .unwrap();
+
+    assert_eq!(result, json!(true));
+}
+
+#[test]
+fn result_is_decoded_with_wrap_types() {
+    let result = build_client()
+        .invoke_json(&uri!("mock/items"), "echo", get_args())
+        .unwrap();
+
+    let mut expected = get_args();
+    expected["item"]["amount"] = json!("1.5");
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn missing_optional_args_are_null() {
+    let mut args = get_args();
+    let item = args["item"].as_object_mut().unwrap();
+    item.remove("amount");
+    item.remove("meta");
+    item.remove("tags");
+    item["kind"] = json!(0);
+    args.as_object_mut().unwrap().remove("count");
+
+    let result = build_client()
+        .invoke_json(&uri!("mock/items"), "echo", args)
+        .unwrap();
+
+    assert_eq!(result["item"]["amount"], Value::Null);
+    assert_eq!(result["item"]["meta"], Value::Null);
+    assert_eq!(result["item"]["tags"], Value::Null);
+    assert_eq!(result["item"]["kind"], json!("SMALL"));
+    assert_eq!(result["count"], Value::Null);
+}
+
+#[test]
+fn invalid_args_point_at_their_path() {
+    let mut args = get_args();
+    args["item"]["counts"]["y"] = json!(300);
+    assert_json_error(invoke_error("echo", args), "$.item.counts[\"y\"]");
+
+    let mut args = get_args();
+    args["item"]["tags"][1] = json!(2);
+    assert_json_error(invoke_error("echo", args), "$.item.tags[1]");
+
+    let mut args = get_args();
+    args["item"]["data"][0] = json!(-1);
+    assert_json_error(invoke_error("echo", args), "$.item.data[0]");
+
+    let mut args = get_args();
+    args["item"]["id"] = json!("12.5");
+    assert_json_error(invoke_error("echo", args), "$.item.id");
+
+    let mut args = get_args();
+    args["item"]["kind"] = json!("MEDIUM");
+    assert_json_error(invoke_error("echo", args), "$.item.kind");
+
+    let mut args = get_args();
+    args["count"] = json!(128);
+    assert_json_error(invoke_error("echo", args), "$.count");
+}
+
+#[test]
+fn missing_required_and_unknown_args_are_rejected() {
+    let mut args = get_args();
+    args["item"].as_object_mut().unwrap().remove("id");
+    assert_json_error(invoke_error("echo", args), "$.item.id");
+
+    let mut args = get_args();
+    args["item"]["color"] = json!("red");
+    assert_json_error(invoke_error("echo", args), "$.item.color");
+
+    assert_json_error(invoke_error("echo", json!([1, 2])), "$");
+}
+
+#[test]
+fn abi_is_cached_by_aliased_uri() {
+    let mut config = ClientConfig::new();
+    config
+        .add_package(uri!("mock/items"), Arc::new(ItemsPackage))
+        .add_redirect(uri!("mock/alias"), uri!("mock/items"));
+    let mut client = Client::new(config.try_into().unwrap());
+    let resolver = Arc::new(CountingResolver {
+        resolver: client.resolver.clone(),
+        resolutions: AtomicUsize::new(0),
+    });
+    client.resolver = resolver.clone();
+
+    client
+        .invoke_json(&uri!("mock/alias"), "check", get_args())
+        .unwrap();
+    let resolutions = resolver.resolutions.load(Ordering::SeqCst);
+    client
+        .invoke_json(&uri!("mock/alias"), "check", get_args())
+        .unwrap();
+
+    // Only the invocation resolves the alias again
+    assert_eq!(resolver.resolutions.load(Ordering::SeqCst) - resolutions, 1);
+}
+
+#[test]
+fn abi_is_read_again_once_the_resolver_changes() {
+    let mut client = build_client();
+    client
+        .invoke_json(&uri!("mock/items"), "check", get_args())
+        .unwrap();
+
+    let mut config = ClientConfig::new();
+    config.add_package(uri!("mock/items"), Arc::new(RenamedItemsPackage));
+    client.resolver = Client::new(config.try_into().unwrap()).resolver;
+
+    let error = client
+        .invoke_json(&uri!("mock/items"), "check", get_args())
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Manifest);
+}
+
+#[test]
+fn unknown_method_is_rejected() {
+    let error = invoke_error("delete", json!({}));
+
+    assert_eq!(error.kind(), ErrorKind::Manifest);
+}
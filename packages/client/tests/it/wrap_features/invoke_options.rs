@@ -0,0 +1,145 @@
+use polywrap_client::client::Client;
+use polywrap_client_builder::{ClientConfig, ClientConfigBuilder};
+use polywrap_core::{
+    error::{Error, ErrorKind},
+    invoke_options::{CancellationToken, InvokeOptions},
+    invoker::Invoker,
+    macros::uri,
+    uri::Uri,
+    wrapper::{GetFileOptions, Wrapper},
+};
+use polywrap_msgpack_serde::to_vec;
+use std::{sync::Arc, time::Duration};
+
+// Reports whether the invocation has a deadline
+#[derive(Debug)]
+struct DeadlineWrapper;
+
+impl Wrapper for DeadlineWrapper {
+    fn invoke(
+        &self,
+        _: &str,
+        _: Option<&[u8]>,
+        _: Option<&[u8]>,
+        invoker: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, Error> {
+        let has_deadline = invoker
+            .get_invoke_options()
+            .is_some_and(|options| options.deadline.is_some());
+
+        Ok(to_vec(&has_deadline)?)
+    }
+
+    fn get_file(&self, _: &GetFileOptions) -> Result<Vec<u8>, Error> {
+        unimplemented!()
+    }
+}
+
+#[derive(Debug)]
+struct CallerWrapper;
+
+impl Wrapper for CallerWrapper {
+    fn invoke(
+        &self,
+        _: &str,
+        _: Option<&[u8]>,
+        _: Option<&[u8]>,
+        invoker: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, Error> {
+        invoker.invoke_raw(&uri!("mock/deadline"), "check", None, None, None)
+    }
+
+    fn get_file(&self, _: &GetFileOptions) -> Result<Vec<u8>, Error> {
+        unimplemented!()
+    }
+}
+
+fn build_client() -> Client {
+    let mut config = ClientConfig::new();
+    config
+        .add_wrapper(uri!("mock/deadline"), Arc::new(DeadlineWrapper))
+        .add_wrapper(uri!("mock/caller"), Arc::new(CallerWrapper));
+
+    Client::new(config.try_into().unwrap())
+}
+
+#[test]
+fn deadline_carries_through_subinvocations() {
+    let client = build_client();
+    let options = InvokeOptions::with_timeout(Duration::from_secs(60));
+
+    let result = client
+        .invoke_with_options::<bool>(&uri!("mock/caller"), "call", None, None, None, &options)
+        .unwrap();
+    assert!(result);
+
+    let result = client
+        .invoke::<bool>(&uri!("mock/caller"), "call", None, None, None)
+        .unwrap();
+    assert!(!result);
+}
+
+#[test]
+fn expired_deadline_fails_with_timeout() {
+    let client = build_client();
+    let options = InvokeOptions::with_timeout(Duration::ZERO);
+
+    let error = client
+        .invoke_with_options::<bool>(&uri!("mock/caller"), "call", None, None, None, &options)
+        .unwrap_err();
+
+    assert_eq!(error.root_cause().kind(), ErrorKind::Timeout);
+}
+
+#[test]
+fn cancelled_token_fails_with_cancelled() {
+    let client = build_client();
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let error = client
+        .invoke_with_options::<bool>(
+            &uri!("mock/caller"),
+            "call",
+            None,
+            None,
+            None,
+            &InvokeOptions::with_cancellation_token(token),
+        )
+        .unwrap_err();
+
+    assert_eq!(error.root_cause().kind(), ErrorKind::Cancelled);
+}
+
+#[tokio::test]
+async fn deadline_carries_through_async_invocations() {
+    let client = build_client();
+    let options = InvokeOptions::with_timeout(Duration::from_secs(60));
+
+    let result = client
+        .invoke_with_options_async::<bool>(&uri!("mock/caller"), "call", None, None, None, &options)
+        .await
+        .unwrap();
+    assert!(result);
+}
+
+#[tokio::test]
+async fn cancelled_token_fails_async_invocation_with_cancelled() {
+    let client = build_client();
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let error = client
+        .invoke_with_options_async::<bool>(
+            &uri!("mock/caller"),
+            "call",
+            None,
+            None,
+            None,
+            &InvokeOptions::with_cancellation_token(token),
+        )
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.root_cause().kind(), ErrorKind::Cancelled);
+}
@@ -0,0 +1,377 @@
+use polywrap_client::client::Client;
+use polywrap_client_builder::{ClientConfig, ClientConfigBuilder};
+use polywrap_core::{
+    error::{Error, ErrorKind},
+    invoker::Invoker,
+    macros::uri,
+    package::{GetManifestOptions, WrapPackage},
+    uri::Uri,
+    wrapper::{GetFileOptions, Wrapper},
+};
+use polywrap_msgpack_serde::to_vec;
+use serde_json::json;
+use std::sync::Arc;
+use wrap_manifest_schemas::{
+    abi::{Abi, AbiTypeDefinition, TypeKind, TypeRef},
+    versions::{WrapManifest, WrapManifestAbi},
+};
+
+fn get_manifest() -> WrapManifest {
+    WrapManifest {
+        name: "Http".to_string(),
+        type_: "wasm".to_string(),
+        version: "0.1".to_string(),
+        abi: serde_json::from_value::<WrapManifestAbi>(json!({
+          "version": "0.1",
+          "moduleType": {
+            "kind": 128,
+            "type": "Module",
+            "imports": [{ "type": "Logger_Module" }],
+            "methods": [
+              {
+                "kind": 64,
+                "name": "get",
+                "required": true,
+                "type": "Method",
+                "arguments": [
+                  {
+                    "kind": 34,
+                    "name": "url",
+                    "required": true,
+                    "type": "String",
+                    "scalar": { "kind": 4, "name": "url", "required": true, "type": "String" }
+                  },
+                  {
+                    "kind": 34,
+                    "name": "request",
+                    "type": "Request",
+                    "object": { "kind": 8192, "name": "request", "type": "Request" }
+                  }
+                ],
+                "return": {
+                  "kind": 34,
+                  "name": "get",
+                  "type": "Response",
+                  "object": { "kind": 8192, "name": "get", "type": "Response" }
+                }
+              },
+              {
+                "kind": 64,
+                "name": "log",
+                "required": true,
+                "type": "Method",
+                "env": { "required": true },
+                "arguments": [
+                  {
+                    "kind": 34,
+                    "name": "level",
+                    "required": true,
+                    "type": "Logger_LogLevel",
+                    "enum": { "kind": 16384, "name": "level", "required": true, "type": "Logger_LogLevel" }
+                  }
+                ],
+                "return": {
+                  "kind": 34,
+                  "name": "log",
+                  "required": true,
+                  "type": "Boolean",
+                  "scalar": { "kind": 4, "name": "log", "required": true, "type": "Boolean" }
+                }
+              }
+            ]
+          },
+          "objectTypes": [
+            {
+              "kind": 1,
+              "type": "Request",
+              "properties": [
+                {
+                  "kind": 34,
+                  "name": "headers",
+                  "type": "Map<String, String>",
+                  "map": {
+                    "kind": 262146,
+                    "name": "headers",
+                    "type": "Map<String, String>",
+                    "key": { "kind": 4, "name": "headers", "required": true, "type": "String" },
+                    "value": { "kind": 4, "name": "headers", "required": true, "type": "String" },
+                    "scalar": { "kind": 4, "name": "headers", "required": true, "type": "String" }
+                  }
+                },
+                {
+                  "kind": 34,
+                  "name": "responseType",
+                  "required": true,
+                  "type": "ResponseType",
+                  "enum": { "kind": 16384, "name": "responseType", "required": true, "type": "ResponseType" }
+                }
+              ]
+            },
+            {
+              "kind": 1,
+              "type": "Response",
+              "properties": [
+                {
+                  "kind": 34,
+                  "name": "chunks",
+                  "required": true,
+                  "type": "[[Bytes]]",
+                  "array": {
+                    "kind": 18,
+                    "name": "chunks",
+                    "required": true,
+                    "type": "[[Bytes]]",
+                    "item": { "kind": 18, "name": "chunks", "type": "[Bytes]" },
+                    "array": {
+                      "kind": 18,
+                      "name": "chunks",
+                      "type": "[Bytes]",
+                      "item": { "kind": 4, "name": "chunks", "required": true, "type": "Bytes" },
+                      "scalar": { "kind": 4, "name": "chunks", "required": true, "type": "Bytes" }
+                    }
+                  }
+                }
+              ]
+            }
+          ],
+          "enumTypes": [
+            { "kind": 8, "type": "ResponseType", "constants": ["TEXT", "BINARY"] }
+          ],
+          "importedModuleTypes": [
+            {
+              "kind": 256,
+              "type": "Logger_Module",
+              "namespace": "Logger",
+              "nativeType": "Module",
+              "uri": "wrapscan.io/polywrap/logger@1.0",
+              "isInterface": false,
+              "methods": [
+                {
+                  "kind": 64,
+                  "name": "log",
+                  "required": true,
+                  "type": "Method",
+                  "arguments": [],
+                  "return": {
+                    "kind": 34,
+                    "name": "log",
+                    "required": true,
+                    "type": "Boolean",
+                    "scalar": { "kind": 4, "name": "log", "required": true, "type": "Boolean" }
+                  }
+                }
+              ]
+            }
+          ],
+          "importedEnumTypes": [
+            {
+              "kind": 520,
+              "type": "Logger_LogLevel",
+              "namespace": "Logger",
+              "nativeType": "LogLevel",
+              "uri": "wrapscan.io/polywrap/logger@1.0",
+              "constants": ["DEBUG", "INFO", "WARN", "ERROR"]
+            }
+          ]
+        }))
+        .unwrap(),
+    }
+}
+
+#[derive(Debug)]
+struct ManifestPackage;
+
+impl WrapPackage for ManifestPackage {
+    fn create_wrapper(&self) -> Result<Arc<dyn Wrapper>, Error> {
+        Ok(Arc::new(ManifestWrapper))
+    }
+
+    fn get_manifest(&self, _: Option<&GetManifestOptions>) -> Result<WrapManifest, Error> {
+        Ok(get_manifest())
+    }
+}
+
+#[derive(Debug)]
+struct ManifestWrapper;
+
+impl Wrapper for ManifestWrapper {
+    fn invoke(
+        &self,
+        _: &str,
+        _: Option<&[u8]>,
+        _: Option<&[u8]>,
+        _: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, Error> {
+        unimplemented!()
+    }
+
+    fn get_file(&self, options: &GetFileOptions) -> Result<Vec<u8>, Error> {
+        match options.path.as_str() {
+            "wrap.info" => Ok(to_vec(&get_manifest()).unwrap()),
+            path => Err(Error::FileReadError(path.to_string())),
+        }
+    }
+}
+
+fn build_client() -> Client {
+    let mut config = ClientConfig::new();
+    config
+        .add_package(uri!("mock/package"), Arc::new(ManifestPackage))
+        .add_wrapper(uri!("mock/wrapper"), Arc::new(ManifestWrapper))
+        .add_redirect(uri!("mock/alias"), uri!("mock/package"));
+
+    Client::new(config.try_into().unwrap())
+}
+
+fn get_abi(uri: &Uri) -> Abi {
+    let manifest = build_client().get_manifest(uri).unwrap();
+    Abi::try_from(&manifest.abi).unwrap()
+}
+
+#[test]
+fn get_manifest_of_packages_and_wrappers() {
+    let client = build_client();
+
+    for uri in [
+        uri!("mock/package"),
+        uri!("mock/wrapper"),
+        uri!("mock/alias"),
+    ] {
+        let manifest = client.get_manifest(&uri).unwrap();
+        assert_eq!(manifest.name, "Http");
+        assert_eq!(manifest.abi, get_manifest().abi);
+    }
+}
+
+#[test]
+fn get_manifest_of_unresolvable_uri() {
+    let error = build_client()
+        .get_manifest(&uri!("mock/not-found"))
+        .unwrap_err();
+
+    assert_eq!(error.kind(), ErrorKind::UriNotFound);
+}
+
+#[test]
+fn list_modules_and_methods() {
+    let abi = get_abi(&uri!("mock/package"));
+
+    let modules = abi
+        .modules()
+        .iter()
+        .map(|module| {
+            let methods = module
+                .methods
+                .iter()
+                .map(|method| method.name.clone().unwrap())
+                .collect::<Vec<_>>();
+            (module.namespace, module.uri, methods)
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        modules,
+        vec![
+            (None, None, vec!["get".to_string(), "log".to_string()]),
+            (
+                Some("Logger"),
+                Some("wrapscan.io/polywrap/logger@1.0"),
+                vec!["log".to_string()]
+            ),
+        ]
+    );
+    assert!(abi.method("get").is_some());
+    assert!(abi.method("post").is_none());
+}
+
+#[test]
+fn resolve_argument_types() {
+    let abi = get_abi(&uri!("mock/package"));
+    let method = abi.method("get").unwrap();
+    let arguments = method.arguments.as_ref().unwrap();
+
+    assert_eq!(
+        arguments[0].type_ref(),
+        TypeRef {
+            kind: TypeKind::Scalar("String".to_string()),
+            required: true,
+        }
+    );
+    assert_eq!(
+        arguments[1].type_ref(),
+        TypeRef {
+            kind: TypeKind::Object("Request".to_string()),
+            required: false,
+        }
+    );
+
+    let Some(AbiTypeDefinition::Object(request)) = abi.resolve_type("Request") else {
+        panic!("Request should be an object type");
+    };
+    let properties = request.properties.as_ref().unwrap();
+    assert_eq!(
+        properties[0].type_ref(),
+        TypeRef {
+            kind: TypeKind::Map {
+                key: "String".to_string(),
+                value: Box::new(TypeRef {
+                    kind: TypeKind::Scalar("String".to_string()),
+                    required: true,
+                }),
+            },
+            required: false,
+        }
+    );
+    assert_eq!(
+        properties[1].type_ref(),
+        TypeRef {
+            kind: TypeKind::Enum("ResponseType".to_string()),
+            required: true,
+        }
+    );
+
+    let Some(AbiTypeDefinition::Enum(response_type)) = abi.resolve_type("ResponseType") else {
+        panic!("ResponseType should be an enum type");
+    };
+    assert_eq!(
+        response_type.constants,
+        Some(vec!["TEXT".to_string(), "BINARY".to_string()])
+    );
+}
+
+#[test]
+fn resolve_imported_types() {
+    let abi = get_abi(&uri!("mock/package"));
+
+    let Some(AbiTypeDefinition::ImportedEnum(log_level)) = abi.resolve_type("Logger_LogLevel")
+    else {
+        panic!("Logger_LogLevel should be an imported enum type");
+    };
+    assert_eq!(log_level.namespace, "Logger");
+    assert_eq!(log_level.uri, "wrapscan.io/polywrap/logger@1.0");
+    assert!(abi.resolve_type("Logger_Unknown").is_none());
+}
+
+#[test]
+fn print_method_signatures() {
+    let abi = get_abi(&uri!("mock/package"));
+
+    let signatures = abi
+        .methods()
+        .iter()
+        .map(|method| method.signature())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        signatures,
+        vec![
+            "get(url: String!, request: Request): Response",
+            "log(level: Logger_LogLevel!): Boolean!",
+        ]
+    );
+
+    let Some(AbiTypeDefinition::Object(response)) = abi.resolve_type("Response") else {
+        panic!("Response should be an object type");
+    };
+    let chunks = &response.properties.as_ref().unwrap()[0];
+    assert_eq!(chunks.type_ref().to_string(), "[[Bytes!]]!");
+}
@@ -1,7 +1,16 @@
+pub mod args_validation;
+pub mod env_merge;
+pub mod env_propagation;
 pub mod env_with_invoke;
 pub mod env_with_subinvoke;
+pub mod interceptor;
 pub mod interface_implementation;
+pub mod invoke_json;
+pub mod invoke_options;
+pub mod manifest;
 pub mod plugin_invocation;
+pub mod resource_limits;
+pub mod spans;
 pub mod subinvoke;
 
 pub mod plugin_state;
\ No newline at end of file
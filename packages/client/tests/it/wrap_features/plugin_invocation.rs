@@ -1,15 +1,33 @@
 use polywrap_client::client::Client;
 use polywrap_client_builder::{ClientConfig, ClientConfigBuilder};
 use polywrap_core::{
-    client::CoreClientConfig, error::Error, macros::uri, package::WrapPackage, uri::Uri,
+    client::{CoreClientConfig, CoreClientConfigBuilder},
+    error::Error,
+    macros::uri,
+    package::WrapPackage,
+    uri::Uri,
+    wrap_loader::WrapLoader,
+    wrapper::GetFileOptions,
 };
 use polywrap_msgpack_serde::to_vec;
 use polywrap_resolvers::static_resolver::{StaticResolver, StaticResolverLike};
-use polywrap_tests_utils::mocks::{ArgsSetData, MemoryStoragePlugin, PluginEnv, ArgsGetData};
-use serde::Serialize;
-use std::{collections::HashMap, sync::Arc};
+use polywrap_tests_utils::mocks::{
+    async_sleep, concurrent_sleep, ArgsGetData, ArgsSetData, ArgsSleep, AsyncSleepPlugin,
+    ConcurrentSleepPlugin, MemoryStoragePlugin, PluginEnv,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{atomic::Ordering, Arc, Mutex},
+    thread,
+};
 
-use polywrap_plugin::{error::PluginError, package::PluginPackage};
+use polywrap_plugin::{
+    error::PluginError,
+    implementor::WrapObject,
+    package::{ConcurrentPluginPackage, PluginPackage},
+    PluginBuilder,
+};
 
 #[derive(Serialize)]
 struct CheckEnvArgs {
@@ -39,6 +57,7 @@ fn invoke_with_env() {
         envs: Some(envs),
         interfaces: None,
         resolver: Arc::new(static_resolver),
+        ..Default::default()
     });
 
     let env_val = to_vec(&CheckEnvArgs {
@@ -68,7 +87,7 @@ fn invoke_methods() {
         Arc::new(PluginPackage::from(MemoryStoragePlugin { value: 1 })),
     );
 
-    let client = Client::new(config.into());
+    let client = Client::new(config.try_into().unwrap());
 
     let result = client
         .invoke::<i32>(&plugin_uri, "getData", Some(&to_vec(&ArgsGetData {}).unwrap()), None, None)
@@ -103,24 +122,404 @@ fn invoke_non_existent_method_should_err() {
         Arc::new(PluginPackage::from(MemoryStoragePlugin { value: 1 })),
     );
 
-    let client = Client::new(config.into());
+    let client = Client::new(config.try_into().unwrap());
 
     let result = client.invoke::<i32>(&plugin_uri, &method, None, None, None);
 
     if let Err(err) = result {
         assert_eq!(
             err.to_string(),
-            Error::InvokeError(
-                plugin_uri.to_string(),
-                method.clone(),
-                Error::from(PluginError::InvocationError {
+            Error::InvokeError {
+                uri: plugin_uri.to_string(),
+                method: method.clone(),
+                source: Box::new(Error::from(PluginError::InvocationError {
                     exception: PluginError::MethodNotFoundError(method).to_string()
-                })
-                .to_string()
-            )
+                })),
+            }
             .to_string()
         );
     } else {
         panic!("Expected error, got result: {:?}", result);
     }
 }
+
+#[test]
+fn invoke_with_malformed_args_should_err() {
+    let plugin_uri = uri!("mock/plugin");
+
+    let mut config = ClientConfig::new();
+    config.add_package(
+        plugin_uri.clone(),
+        Arc::new(PluginPackage::from(MemoryStoragePlugin { value: 1 })),
+    );
+
+    let client = Client::new(config.try_into().unwrap());
+
+    let result = client.invoke::<bool>(
+        &plugin_uri,
+        "setData",
+        Some(&to_vec(&ArgsGetData {}).unwrap()),
+        None,
+        None,
+    );
+
+    let err = result.unwrap_err().to_string();
+    assert!(
+        err.contains("Failed to decode `args.value` of method 'setData'"),
+        "{err}"
+    );
+    assert!(err.contains("value"), "{err}");
+}
+
+#[test]
+fn invoke_with_malformed_env_should_err() {
+    let plugin_uri = uri!("plugin/env");
+
+    let mut config = ClientConfig::new();
+    config
+        .add_package(
+            plugin_uri.clone(),
+            Arc::new(PluginPackage::from(PluginEnv {})),
+        )
+        .add_env(
+            plugin_uri.clone(),
+            to_vec(&CheckEnvArgs {
+                key: "foo".to_string(),
+            })
+            .unwrap(),
+        );
+
+    let client = Client::new(config.try_into().unwrap());
+
+    let args = to_vec(&CheckEnvArgs {
+        key: "foo".to_string(),
+    })
+    .unwrap();
+    let result = client.invoke::<bool>(&plugin_uri, "checkEnvIsBar", Some(&args), None, None);
+
+    let err = result.unwrap_err().to_string();
+    assert!(
+        err.contains("Failed to decode `env.foo` of method 'checkEnvIsBar'"),
+        "{err}"
+    );
+}
+
+#[test]
+fn add_plugin_not_implementing_its_abi_should_err() {
+    let package = PluginPackage::new(
+        Arc::new(Mutex::new(MemoryStoragePlugin { value: 1 })),
+        concurrent_sleep::get_manifest(),
+    );
+
+    let mut config = ClientConfig::new();
+    let result = config.try_add_package(uri!("mock/plugin"), Arc::new(package));
+
+    let err = result.err().unwrap().to_string();
+    assert!(
+        err.contains("method 'sleep' of the ABI isn't implemented"),
+        "{err}"
+    );
+    assert!(config.packages.is_none());
+}
+
+#[test]
+fn build_config_with_plugin_not_implementing_its_abi_should_err() {
+    let package = PluginPackage::new(
+        Arc::new(Mutex::new(MemoryStoragePlugin { value: 1 })),
+        concurrent_sleep::get_manifest(),
+    );
+
+    let mut config = ClientConfig::new();
+    config.add_packages(vec![(uri!("mock/plugin"), Arc::new(package))]);
+
+    let err = config.build().err().unwrap().to_string();
+    assert!(
+        err.contains("Package added at `wrap://mock/plugin` doesn't match its manifest"),
+        "{err}"
+    );
+    assert!(
+        err.contains("method 'sleep' of the ABI isn't implemented"),
+        "{err}"
+    );
+}
+
+#[test]
+fn add_plugin_implementing_its_abi() {
+    let package = PluginPackage::from(MemoryStoragePlugin { value: 1 });
+
+    let mut config = ClientConfig::new();
+    config
+        .try_add_package(uri!("mock/plugin"), Arc::new(package))
+        .unwrap();
+
+    assert_eq!(config.packages.unwrap().len(), 1);
+}
+
+#[derive(Deserialize, Serialize, WrapObject)]
+struct ArgsAdd {
+    a: i32,
+    b: i32,
+}
+
+#[derive(Deserialize, Serialize, WrapObject)]
+struct ArgsGreet {
+    name: String,
+}
+
+#[derive(Deserialize, WrapObject)]
+struct GreetEnv {
+    foo: String,
+}
+
+#[test]
+fn invoke_plugin_built_from_closures() {
+    let plugin_uri = uri!("plugin/closures");
+    let package = PluginBuilder::new()
+        .method("add", |args: ArgsAdd, _: (), _| Ok(args.a + args.b))
+        .method("greet", |args: ArgsGreet, env: Option<GreetEnv>, _| {
+            let greeting = env.map(|env| env.foo).unwrap_or("Hello".to_string());
+            Ok(format!("{greeting} {}", args.name))
+        })
+        .method("welcome", |args: ArgsGreet, env: GreetEnv, _| {
+            Ok(format!("{} {}", env.foo, args.name))
+        })
+        .build_concurrent();
+
+    let mut config = ClientConfig::new();
+    config.add_package(plugin_uri.clone(), Arc::new(package));
+    let client = Client::new(config.try_into().unwrap());
+
+    let args = to_vec(&ArgsAdd { a: 1, b: 2 }).unwrap();
+    let result = client
+        .invoke::<i32>(&plugin_uri, "add", Some(&args), None, None)
+        .unwrap();
+    assert_eq!(result, 3);
+
+    let args = to_vec(&ArgsGreet {
+        name: "polywrap".to_string(),
+    })
+    .unwrap();
+    let result = client
+        .invoke::<String>(&plugin_uri, "greet", Some(&args), None, None)
+        .unwrap();
+    assert_eq!(result, "Hello polywrap");
+
+    let env = to_vec(&EnvVal {
+        foo: "Hi".to_string(),
+    })
+    .unwrap();
+    let result = client
+        .invoke::<String>(&plugin_uri, "greet", Some(&args), Some(&env), None)
+        .unwrap();
+    assert_eq!(result, "Hi polywrap");
+
+    let result = client
+        .invoke::<String>(&plugin_uri, "welcome", Some(&args), Some(&env), None)
+        .unwrap();
+    assert_eq!(result, "Hi polywrap");
+
+    let err = client
+        .invoke::<String>(&plugin_uri, "welcome", Some(&args), None, None)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("Env must be defined for method 'welcome'"),
+        "{err}"
+    );
+
+    let err = client
+        .invoke::<i32>(&plugin_uri, "add", Some(&args), None, None)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("Failed to decode `args.a` of method 'add'"),
+        "{err}"
+    );
+}
+
+#[test]
+fn get_plugin_files() {
+    let plugin_uri = uri!("mock/plugin");
+    let package = PluginPackage::from(MemoryStoragePlugin { value: 1 })
+        .with_file("README.md", b"# Memory storage".to_vec());
+
+    let mut config = ClientConfig::new();
+    config.add_package(plugin_uri.clone(), Arc::new(package));
+
+    let client = Client::new(config.try_into().unwrap());
+    let wrapper = client.load_wrapper(&plugin_uri, None).unwrap();
+    let get_file = |path: &str| {
+        wrapper.get_file(&GetFileOptions {
+            path: path.to_string(),
+            encoding: None,
+        })
+    };
+
+    assert_eq!(get_file("README.md").unwrap(), b"# Memory storage");
+    assert!(get_file("icon.png").is_err());
+
+    let manifest = client.get_manifest(&plugin_uri).unwrap();
+    assert_eq!(
+        get_file("wrap.info").unwrap(),
+        to_vec(&manifest).unwrap()
+    );
+}
+
+#[test]
+fn invoke_concurrent_plugin_in_parallel() {
+    let plugin_uri = uri!("mock/plugin");
+    let plugin = Arc::new(ConcurrentSleepPlugin::default());
+
+    let mut config = ClientConfig::new();
+    config.add_package(
+        plugin_uri.clone(),
+        Arc::new(ConcurrentPluginPackage::new(
+            plugin.clone(),
+            concurrent_sleep::get_manifest(),
+        )),
+    );
+
+    let client = Client::new(config.try_into().unwrap());
+    let args = to_vec(&ArgsSleep { ms: 200 }).unwrap();
+
+    thread::scope(|scope| {
+        let invocations = (0..2)
+            .map(|_| {
+                scope.spawn(|| {
+                    client.invoke::<bool>(&plugin_uri, "sleep", Some(&args), None, None)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for invocation in invocations {
+            assert!(invocation.join().unwrap().unwrap());
+        }
+    });
+
+    assert_eq!(plugin.max_in_flight.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn invoke_concurrent_plugin_through_a_lock() {
+    let plugin_uri = uri!("mock/plugin");
+    let plugin = Arc::new(Mutex::new(ConcurrentSleepPlugin::default()));
+
+    let mut config = ClientConfig::new();
+    config.add_package(
+        plugin_uri.clone(),
+        Arc::new(PluginPackage::new(
+            plugin.clone(),
+            concurrent_sleep::get_manifest(),
+        )),
+    );
+
+    let client = Client::new(config.try_into().unwrap());
+    let args = to_vec(&ArgsSleep { ms: 50 }).unwrap();
+
+    thread::scope(|scope| {
+        let invocations = (0..2)
+            .map(|_| {
+                scope.spawn(|| {
+                    client.invoke::<bool>(&plugin_uri, "sleep", Some(&args), None, None)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for invocation in invocations {
+            assert!(invocation.join().unwrap().unwrap());
+        }
+    });
+
+    let plugin = plugin.lock().unwrap();
+    assert_eq!(plugin.max_in_flight.load(Ordering::SeqCst), 1);
+}
+
+fn build_async_sleep_client(plugin: Arc<AsyncSleepPlugin>, mut config: ClientConfig) -> Client {
+    config.add_package(
+        uri!("mock/plugin"),
+        Arc::new(ConcurrentPluginPackage::new(
+            plugin,
+            async_sleep::get_manifest(),
+        )),
+    );
+
+    Client::new(config.try_into().unwrap())
+}
+
+#[tokio::test]
+async fn invoke_async_plugin_inside_runtime() {
+    let plugin = Arc::new(AsyncSleepPlugin::default());
+    let client = build_async_sleep_client(plugin.clone(), ClientConfig::new());
+    let args = to_vec(&ArgsSleep { ms: 10 }).unwrap();
+
+    let result = client.invoke::<bool>(&uri!("mock/plugin"), "sleep", Some(&args), None, None);
+
+    assert!(result.unwrap());
+    assert_eq!(plugin.invoked_on.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn invoke_async_plugin_natively() {
+    let plugin = Arc::new(AsyncSleepPlugin::default());
+    let client = build_async_sleep_client(plugin.clone(), ClientConfig::new());
+    let args = to_vec(&ArgsSleep { ms: 10 }).unwrap();
+
+    let result = client
+        .invoke_async::<bool>(&uri!("mock/plugin"), "sleep", Some(&args), None, None)
+        .await;
+
+    assert!(result.unwrap());
+    assert_eq!(
+        *plugin.invoked_on.lock().unwrap(),
+        vec![thread::current().id()]
+    );
+}
+
+#[tokio::test]
+async fn invoke_async_plugin_through_a_lock() {
+    let plugin = Arc::new(Mutex::new(AsyncSleepPlugin::default()));
+    let mut config = ClientConfig::new();
+    config.add_package(
+        uri!("mock/plugin"),
+        Arc::new(PluginPackage::new(
+            plugin.clone(),
+            async_sleep::get_manifest(),
+        )),
+    );
+    let client = Client::new(config.try_into().unwrap());
+    let args = to_vec(&ArgsSleep { ms: 10 }).unwrap();
+
+    let result = client
+        .invoke_async::<bool>(&uri!("mock/plugin"), "sleep", Some(&args), None, None)
+        .await;
+
+    assert!(result.unwrap());
+    let plugin = plugin.lock().unwrap();
+    assert_ne!(
+        *plugin.invoked_on.lock().unwrap(),
+        vec![thread::current().id()]
+    );
+}
+
+#[test]
+fn invoke_async_plugin_on_configured_executor() {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .thread_name("plugin-executor")
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let plugin = Arc::new(AsyncSleepPlugin::default());
+    let mut config = ClientConfig::new();
+    config.set_executor(runtime.handle().clone());
+    let client = build_async_sleep_client(plugin.clone(), config);
+    let args = to_vec(&ArgsSleep { ms: 10 }).unwrap();
+
+    let result = client.invoke::<bool>(&uri!("mock/plugin"), "sleep", Some(&args), None, None);
+
+    assert!(result.unwrap());
+    assert_eq!(
+        *plugin.spawned_on.lock().unwrap(),
+        vec![Some("plugin-executor".to_string())]
+    );
+}
@@ -0,0 +1,146 @@
+use polywrap_client::client::Client;
+use polywrap_client_builder::{ClientConfig, ClientConfigBuilder};
+use polywrap_core::{
+    error::ErrorKind,
+    file_reader::SimpleFileReader,
+    invoke_options::InvokeOptions,
+    macros::uri,
+    resource_limits::{ResourceLimits, ResourceUsage},
+    uri::Uri,
+};
+use polywrap_wasm::wasm_wrapper::WasmWrapper;
+use std::sync::{Arc, Mutex};
+use tracing::{
+    field::{Field, Visit},
+    span::{Id, Record},
+    Subscriber,
+};
+use tracing_subscriber::{
+    layer::{Context, SubscriberExt},
+    Layer, Registry,
+};
+
+const LOOP_MODULE: &str = r#"
+    (module
+      (import "env" "memory" (memory 1))
+      (func (export "_wrap_invoke") (param i32 i32 i32) (result i32)
+        (loop $forever
+          (br $forever))
+        (i32.const 1)))
+    "#;
+
+// Returns msgpack `true`
+const RESULT_MODULE: &str = r#"
+    (module
+      (import "env" "memory" (memory 1))
+      (import "wrap" "__wrap_invoke_result" (func $result (param i32 i32)))
+      (data (i32.const 0) "\c3")
+      (func (export "_wrap_invoke") (param i32 i32 i32) (result i32)
+        (call $result (i32.const 0) (i32.const 1))
+        (i32.const 1)))
+    "#;
+
+fn wasm_wrapper(module: &str) -> Arc<WasmWrapper> {
+    let bytes = wat::parse_str(module).unwrap();
+    Arc::new(WasmWrapper::try_from_bytecode(&bytes, Arc::new(SimpleFileReader::new())).unwrap())
+}
+
+fn build_client() -> Client {
+    let mut config = ClientConfig::new();
+    config
+        .add_wrapper(uri!("mock/loop"), wasm_wrapper(LOOP_MODULE))
+        .add_wrapper(uri!("mock/result"), wasm_wrapper(RESULT_MODULE))
+        .set_resource_limits(ResourceLimits {
+            fuel: Some(1),
+            max_memory_pages: None,
+        })
+        .add_resource_limits(
+            uri!("mock/result"),
+            ResourceLimits {
+                fuel: Some(1_000),
+                max_memory_pages: None,
+            },
+        );
+
+    Client::new(config.try_into().unwrap())
+}
+
+#[test]
+fn client_limits_apply_unless_overridden_for_uri() {
+    let client = build_client();
+
+    let result = client
+        .invoke::<bool>(&uri!("mock/result"), "method", None, None, None)
+        .unwrap();
+    assert!(result);
+
+    let error = client
+        .invoke::<bool>(&uri!("mock/loop"), "method", None, None, None)
+        .unwrap_err();
+    assert_eq!(error.root_cause().kind(), ErrorKind::ResourceLimit);
+}
+
+#[derive(Default)]
+struct FuelVisitor(Option<u64>);
+
+impl Visit for FuelVisitor {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "fuel_consumed" {
+            self.0 = Some(value);
+        }
+    }
+
+    fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+}
+
+#[derive(Clone, Default)]
+struct FuelRecorder {
+    fuel_consumed: Arc<Mutex<Vec<u64>>>,
+}
+
+impl<S: Subscriber> Layer<S> for FuelRecorder {
+    fn on_record(&self, _: &Id, values: &Record<'_>, _: Context<'_, S>) {
+        let mut visitor = FuelVisitor::default();
+        values.record(&mut visitor);
+
+        if let Some(fuel_consumed) = visitor.0 {
+            self.fuel_consumed.lock().unwrap().push(fuel_consumed);
+        }
+    }
+}
+
+#[test]
+fn fuel_consumed_is_recorded_on_span() {
+    let client = build_client();
+
+    let recorder = FuelRecorder::default();
+    let subscriber = Registry::default().with(recorder.clone());
+
+    let result = tracing::subscriber::with_default(subscriber, || {
+        client.invoke::<bool>(&uri!("mock/result"), "method", None, None, None)
+    });
+    assert!(result.unwrap());
+
+    let fuel_consumed = recorder.fuel_consumed.lock().unwrap();
+    assert_eq!(fuel_consumed.len(), 1);
+    assert!(fuel_consumed[0] > 0 && fuel_consumed[0] <= 1_000);
+}
+
+#[test]
+fn fuel_consumed_is_reported_to_resource_usage() {
+    let client = build_client();
+    let resource_usage = ResourceUsage::new();
+
+    let result = client.invoke_with_options::<bool>(
+        &uri!("mock/result"),
+        "method",
+        None,
+        None,
+        None,
+        &InvokeOptions::with_resource_usage(resource_usage.clone()),
+    );
+    assert!(result.unwrap());
+
+    let fuel_consumed = resource_usage.fuel_consumed();
+    assert!(fuel_consumed > 0 && fuel_consumed <= 1_000);
+}
@@ -0,0 +1,266 @@
+use polywrap_client::client::Client;
+use polywrap_client_builder::{ClientConfig, ClientConfigBuilder};
+use polywrap_core::{
+    error::Error,
+    file_reader::SimpleFileReader,
+    invoker::Invoker,
+    macros::uri,
+    uri::Uri,
+    wrapper::{GetFileOptions, Wrapper},
+};
+use polywrap_tests_utils::mocks::MockWrapper;
+use polywrap_wasm::wasm_wrapper::WasmWrapper;
+use std::sync::{Arc, Mutex};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Subscriber,
+};
+use tracing_subscriber::{
+    layer::{Context, SubscriberExt},
+    registry::LookupSpan,
+    Layer, Registry,
+};
+
+#[derive(Debug)]
+struct CallerWrapper;
+
+impl Wrapper for CallerWrapper {
+    fn invoke(
+        &self,
+        _: &str,
+        _: Option<&[u8]>,
+        _: Option<&[u8]>,
+        invoker: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, Error> {
+        invoker.invoke_raw(&uri!("wrap/mock"), "foo", None, None, None)
+    }
+
+    fn get_file(&self, _: &GetFileOptions) -> Result<Vec<u8>, Error> {
+        unimplemented!()
+    }
+}
+
+#[derive(Default)]
+struct UriVisitor(Option<String>);
+
+impl Visit for UriVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "uri" {
+            self.0 = Some(format!("{:?}", value));
+        }
+    }
+}
+
+// A span labelled as "name(uri)", along with the label of its parent
+type RecordedSpan = (String, Option<String>);
+
+#[derive(Clone, Default)]
+struct SpanRecorder {
+    spans: Arc<Mutex<Vec<RecordedSpan>>>,
+}
+
+// The label of a span, along with its index in the recorded spans
+struct SpanLabel(String, usize);
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for SpanRecorder {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = UriVisitor::default();
+        attrs.record(&mut visitor);
+
+        let label = match visitor.0 {
+            Some(uri) => format!("{}({})", attrs.metadata().name(), uri),
+            None => attrs.metadata().name().to_string(),
+        };
+
+        let span = ctx.span(id).unwrap();
+        let parent = span
+            .parent()
+            .and_then(|p| p.extensions().get::<SpanLabel>().map(|l| l.0.clone()));
+
+        let mut spans = self.spans.lock().unwrap();
+        span.extensions_mut()
+            .insert(SpanLabel(label.clone(), spans.len()));
+        spans.push((label, parent));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let mut visitor = UriVisitor::default();
+        values.record(&mut visitor);
+
+        if let Some(uri) = visitor.0 {
+            let span = ctx.span(id).unwrap();
+            let mut extensions = span.extensions_mut();
+            let label = extensions.get_mut::<SpanLabel>().unwrap();
+
+            label.0 = format!("{}({})", span.name(), uri);
+            self.spans.lock().unwrap()[label.1].0 = label.0.clone();
+        }
+    }
+}
+
+#[test]
+fn spans_follow_subinvocation_tree() {
+    let mut config = ClientConfig::new();
+    config
+        .add_wrapper(uri!("mock/caller"), Arc::new(CallerWrapper))
+        .add_wrapper(uri!("wrap/mock"), Arc::new(MockWrapper));
+    let client = Client::new(config.try_into().unwrap());
+
+    let recorder = SpanRecorder::default();
+    let subscriber = Registry::default().with(recorder.clone());
+
+    let result = tracing::subscriber::with_default(subscriber, || {
+        client.invoke::<bool>(&uri!("mock/caller"), "call", None, None, None)
+    });
+    assert!(result.unwrap());
+
+    let spans = recorder.spans.lock().unwrap();
+    let parent_of = |label: &str| {
+        spans
+            .iter()
+            .find(|(l, _)| l == label)
+            .unwrap_or_else(|| panic!("Span {label} not found in {:?}", spans))
+            .1
+            .clone()
+    };
+
+    assert_eq!(parent_of("invoke(wrap://mock/caller)"), None);
+    assert_eq!(
+        parent_of("load_wrapper(wrap://mock/caller)").as_deref(),
+        Some("invoke(wrap://mock/caller)")
+    );
+    assert_eq!(
+        parent_of("try_resolve_uri(wrap://mock/caller)").as_deref(),
+        Some("load_wrapper(wrap://mock/caller)")
+    );
+    assert_eq!(
+        parent_of("invoke(wrap://wrap/mock)").as_deref(),
+        Some("invoke(wrap://mock/caller)")
+    );
+    assert_eq!(
+        parent_of("load_wrapper(wrap://wrap/mock)").as_deref(),
+        Some("invoke(wrap://wrap/mock)")
+    );
+}
+
+#[derive(Default)]
+struct ResolverVisitor(Option<String>);
+
+impl Visit for ResolverVisitor {
+    fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "resolver" {
+            // Type names are recorded, of which only the last segment is kept
+            self.0 = value.rsplit("::").next().map(String::from);
+        }
+    }
+}
+
+// The resolver of a `try_resolve_uri` span, along with the resolver of its parent span
+type RecordedResolver = (String, Option<String>);
+
+#[derive(Clone, Default)]
+struct ResolverRecorder {
+    resolvers: Arc<Mutex<Vec<RecordedResolver>>>,
+}
+
+struct ResolverLabel(String);
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for ResolverRecorder {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = ResolverVisitor::default();
+        attrs.record(&mut visitor);
+
+        if let Some(resolver) = visitor.0 {
+            let span = ctx.span(id).unwrap();
+            let parent = span
+                .parent()
+                .and_then(|p| p.extensions().get::<ResolverLabel>().map(|l| l.0.clone()));
+
+            span.extensions_mut()
+                .insert(ResolverLabel(resolver.clone()));
+            self.resolvers.lock().unwrap().push((resolver, parent));
+        }
+    }
+}
+
+#[test]
+fn spans_follow_resolver_tree() {
+    let mut config = ClientConfig::new();
+    config.add_wrapper(uri!("wrap/mock"), Arc::new(MockWrapper));
+    let client = Client::new(config.try_into().unwrap());
+
+    let recorder = ResolverRecorder::default();
+    let subscriber = Registry::default().with(recorder.clone());
+
+    let result = tracing::subscriber::with_default(subscriber, || {
+        client.invoke::<bool>(&uri!("wrap/mock"), "foo", None, None, None)
+    });
+    assert!(result.unwrap());
+
+    // Every resolver gets its own span, under the span of the resolver calling it
+    let resolvers = recorder.resolvers.lock().unwrap();
+    let parent_of = |resolver: &str| {
+        resolvers
+            .iter()
+            .find(|(r, _)| r == resolver)
+            .unwrap_or_else(|| panic!("Resolver {resolver} not found in {:?}", resolvers))
+            .1
+            .clone()
+    };
+
+    assert_eq!(parent_of("RecursiveResolver"), None);
+    assert_eq!(
+        parent_of("ResolutionResultCacheResolver").as_deref(),
+        Some("RecursiveResolver")
+    );
+    assert_eq!(
+        parent_of("UriResolverAggregator").as_deref(),
+        Some("ResolutionResultCacheResolver")
+    );
+    assert_eq!(
+        parent_of("StaticResolver").as_deref(),
+        Some("UriResolverAggregator")
+    );
+}
+
+#[test]
+fn wasm_invoke_span_records_uri() {
+    // Returns msgpack `true`
+    let module = wat::parse_str(
+        r#"
+        (module
+          (import "env" "memory" (memory 1))
+          (import "wrap" "__wrap_invoke_result" (func $result (param i32 i32)))
+          (data (i32.const 0) "\c3")
+          (func (export "_wrap_invoke") (param i32 i32 i32) (result i32)
+            (call $result (i32.const 0) (i32.const 1))
+            (i32.const 1)))
+        "#,
+    )
+    .unwrap();
+    let wrapper =
+        WasmWrapper::try_from_bytecode(&module, Arc::new(SimpleFileReader::new())).unwrap();
+
+    let mut config = ClientConfig::new();
+    config.add_wrapper(uri!("mock/wasm"), Arc::new(wrapper));
+    let client = Client::new(config.try_into().unwrap());
+
+    let recorder = SpanRecorder::default();
+    let subscriber = Registry::default().with(recorder.clone());
+
+    let result = tracing::subscriber::with_default(subscriber, || {
+        client.invoke::<bool>(&uri!("mock/wasm"), "method", None, None, None)
+    });
+    assert!(result.unwrap());
+
+    let spans = recorder.spans.lock().unwrap();
+    let (_, parent) = spans
+        .iter()
+        .find(|(l, _)| l == "wasm_invoke(wrap://mock/wasm)")
+        .unwrap_or_else(|| panic!("Span wasm_invoke not found in {:?}", spans));
+
+    assert_eq!(parent.as_deref(), Some("invoke(wrap://mock/wasm)"));
+}
@@ -49,6 +49,7 @@ fn subinvoke_test() {
         resolver: Arc::new(base_resolver),
         envs: None,
         interfaces: None,
+        ..Default::default()
     };
     let client = Client::new(config);
 
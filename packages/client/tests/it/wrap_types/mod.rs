@@ -40,6 +40,7 @@ pub fn get_client(static_resolvers: Option<HashMap<Uri, UriPackageOrWrapper>>) -
         resolver: Arc::new(base_resolver),
         envs: None,
         interfaces: None,
+        ..Default::default()
     };
     Client::new(config)
 }
@@ -0,0 +1,82 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use crate::{
+    error::Error, invoker::Invoker, resolution::uri_resolution_context::UriResolutionContext,
+    uri::Uri,
+};
+
+/// Defines an object capable of invoking on URIs without blocking the calling task.
+///
+/// Every `Arc` of a synchronous `Invoker` is also an `AsyncInvoker`: the invocation
+/// is moved to the blocking thread pool of the current Tokio runtime, so existing
+/// wraps and plugins can be invoked from async code without any change.
+#[async_trait]
+pub trait AsyncInvoker: Send + Sync {
+    /// Invokes a method on a given URI with optional arguments and environment.
+    /// The returned future resolves to either the msgpack buffer of the response or an Error.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - A reference to the Uri to invoke the method on.
+    /// * `method` - The name of the method to invoke.
+    /// * `args` - Optional msgpack buffer representing the arguments to the method.
+    /// * `env` - Optional msgpack buffer representing the environment for the method.
+    /// * `resolution_context` - Optional resolution context of invocation.
+    async fn invoke_raw_async(
+        &self,
+        uri: &Uri,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        resolution_context: Option<Arc<Mutex<UriResolutionContext>>>,
+    ) -> Result<Vec<u8>, Error>;
+}
+
+#[async_trait]
+impl<T: Invoker + ?Sized + 'static> AsyncInvoker for Arc<T> {
+    async fn invoke_raw_async(
+        &self,
+        uri: &Uri,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        resolution_context: Option<Arc<Mutex<UriResolutionContext>>>,
+    ) -> Result<Vec<u8>, Error> {
+        let invoker = self.clone();
+        let uri = uri.clone();
+        let method = method.to_string();
+        let args = args.map(|a| a.to_vec());
+        let env = env.map(|e| e.to_vec());
+
+        spawn_blocking(move || {
+            invoker.invoke_raw(
+                &uri,
+                &method,
+                args.as_deref(),
+                env.as_deref(),
+                resolution_context,
+            )
+        })
+        .await
+    }
+}
+
+/// Runs a blocking closure on the blocking thread pool of the current Tokio runtime,
+/// inside the current tracing span.
+///
+/// # Panics
+///
+/// Panics if called outside of a Tokio runtime.
+pub async fn spawn_blocking<F, R>(f: F) -> Result<R, Error>
+where
+    F: FnOnce() -> Result<R, Error> + Send + 'static,
+    R: Send + 'static,
+{
+    let span = tracing::Span::current();
+
+    tokio::task::spawn_blocking(move || span.in_scope(f))
+        .await
+        .map_err(|e| Error::RuntimeError(e.to_string()))?
+}
@@ -0,0 +1,40 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use crate::{
+    async_invoker::spawn_blocking, error::Error,
+    resolution::uri_resolution_context::UriResolutionContext, uri::Uri, wrap_loader::WrapLoader,
+    wrapper::Wrapper,
+};
+
+/// Capable of loading wrappers from URIs without blocking the calling task.
+///
+/// Every `Arc` of a synchronous `WrapLoader` is also an `AsyncWrapLoader`.
+#[async_trait]
+pub trait AsyncWrapLoader: Send + Sync {
+    /// Loads a wrapper from a given URI. On success, resolves to a `Wrapper`. On failure, resolves to an `Error`
+    ///
+    /// # Arguments
+    /// - `uri`: The `Uri` from which to load the wrapper.
+    /// - `resolution_context`: An optional resolution context of invocation.
+    async fn load_wrapper_async(
+        &self,
+        uri: &Uri,
+        resolution_context: Option<Arc<Mutex<UriResolutionContext>>>,
+    ) -> Result<Arc<dyn Wrapper>, Error>;
+}
+
+#[async_trait]
+impl<T: WrapLoader + ?Sized + 'static> AsyncWrapLoader for Arc<T> {
+    async fn load_wrapper_async(
+        &self,
+        uri: &Uri,
+        resolution_context: Option<Arc<Mutex<UriResolutionContext>>>,
+    ) -> Result<Arc<dyn Wrapper>, Error> {
+        let loader = self.clone();
+        let uri = uri.clone();
+
+        spawn_blocking(move || loader.load_wrapper(&uri, resolution_context)).await
+    }
+}
@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{error::Error, invoker::Invoker};
+
+/// Wrapper whose invocations can be awaited natively, without blocking a thread.
+/// It is exposed by `Wrapper::as_async`, and used by the async API of the client.
+#[async_trait]
+pub trait AsyncWrapper: Send + Sync {
+    /// Invokes the wrapper with a method, arguments, environment, and invoker.
+    /// The returned future resolves to either the msgpack buffer of the response or an Error.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The name of the method to invoke.
+    /// * `args` - Optional msgpack buffer representing the arguments to the method.
+    /// * `env` - Optional msgpack buffer representing the environment for the method.
+    /// * `invoker` - `Invoker` to invoke this wrapper with.
+    async fn invoke_async(
+        &self,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        invoker: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, Error>;
+}
@@ -1,11 +1,21 @@
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use tokio::runtime::Handle;
+
+use crate::env_inheritance::EnvInheritance;
+use crate::env_merge::EnvMergeStrategy;
+use crate::error::Error;
+use crate::interceptor::InvokeInterceptor;
 use crate::interface_implementation::InterfaceImplementations;
 use crate::invoker::Invoker;
+use crate::resource_limits::ResourceLimits;
+use crate::resolution::uri_resolution_context::{UriPackageOrWrapper, UriResolutionContext};
 use crate::resolution::uri_resolver::UriResolver;
 use crate::uri::Uri;
 use crate::uri_resolver_handler::UriResolverHandler;
+use crate::wasi_policy::WasiPolicy;
+use crate::wrap_log::WrapLogSink;
 use crate::wrap_invoker::WrapInvoker;
 use crate::wrap_loader::WrapLoader;
 
@@ -46,14 +56,77 @@ pub struct CoreClientConfig {
     /// Environment variables configuration.
     /// Should be a `HashMap` of `Uri` keys and msgpack buffer values
     pub envs: Option<HashMap<Uri, Vec<u8>>>,
+    /// Env received by subinvoked wraps without a configured env.
+    /// Defaults to `EnvInheritance::None`
+    pub env_inheritance: Option<EnvInheritance>,
+    /// How the envs applying to an invocation are combined.
+    /// Defaults to `EnvMergeStrategy::Replace`
+    pub env_merge_strategy: Option<EnvMergeStrategy>,
     /// Interface implementations
     pub interfaces: Option<InterfaceImplementations>,
+    /// Interceptors called around every invocation, in order
+    pub interceptors: Option<Vec<Arc<dyn InvokeInterceptor>>>,
+    /// Resource limits of every wrap, unless overridden for its `Uri`
+    pub resource_limits: Option<ResourceLimits>,
+    /// Resource limits of specific wraps.
+    /// Limits left unset fall back to `resource_limits`
+    pub uri_resource_limits: Option<HashMap<Uri, ResourceLimits>>,
+    /// WASI capabilities of specific wraps.
+    /// Wraps without a policy are denied every capability
+    pub uri_wasi_policies: Option<HashMap<Uri, WasiPolicy>>,
+    /// Sink receiving the events logged by wraps.
+    /// Defaults to emitting them as `tracing` events
+    pub log_sink: Option<Arc<dyn WrapLogSink>>,
+    /// Whether the arguments of invocations are checked against the ABI of the invoked wrap
+    /// before it executes. Defaults to `false`
+    pub validate_args: Option<bool>,
+    /// Multi-threaded Tokio runtime driving the async methods of plugins invoked synchronously.
+    /// Defaults to a runtime shared by every client
+    pub executor: Option<Handle>,
+}
+
+/// Resolver of the default `CoreClientConfig`, which resolves no URI.
+#[derive(Debug)]
+struct NoopResolver;
+
+impl UriResolver for NoopResolver {
+    fn try_resolve_uri(
+        &self,
+        uri: &Uri,
+        _: Arc<dyn Invoker>,
+        _: Arc<Mutex<UriResolutionContext>>,
+    ) -> Result<UriPackageOrWrapper, Error> {
+        Ok(UriPackageOrWrapper::Uri(uri.clone()))
+    }
+}
+
+/// Config without any wrap, whose options take their default values.
+/// Meant to be completed with struct update syntax, e.g.
+/// `CoreClientConfig { resolver, ..Default::default() }`
+impl Default for CoreClientConfig {
+    fn default() -> Self {
+        Self {
+            resolver: Arc::new(NoopResolver),
+            envs: None,
+            env_inheritance: None,
+            env_merge_strategy: None,
+            interfaces: None,
+            interceptors: None,
+            resource_limits: None,
+            uri_resource_limits: None,
+            uri_wasi_policies: None,
+            log_sink: None,
+            validate_args: None,
+            executor: None,
+        }
+    }
 }
 
 /// Defines a type that can build a `CoreClientConfig`.
 pub trait CoreClientConfigBuilder {
-    /// Builds a `CoreClientConfig` instance.
-    fn build(self) -> CoreClientConfig;
+    /// Builds a `CoreClientConfig` instance, failing if the config is invalid,
+    /// e.g. if a package doesn't match its manifest.
+    fn build(self) -> Result<CoreClientConfig, Error>;
 }
 
 /// Defines a type that represents a Polywrap Client.
@@ -0,0 +1,14 @@
+/// Defines which env a wrap receives when it is subinvoked by another wrap.
+///
+/// Envs passed explicitly to the subinvocation always take precedence.
+/// Otherwise, the env configured for the first `Uri` of the subinvoked `Uri`'s
+/// redirect chain having one is used.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EnvInheritance {
+    /// Subinvoked wraps only receive the envs configured for them.
+    #[default]
+    None,
+    /// Subinvoked wraps without a configured env receive the env
+    /// of the wrap subinvoking them.
+    Fallback,
+}
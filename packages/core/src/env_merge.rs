@@ -0,0 +1,158 @@
+use crate::error::Error;
+
+/// Defines how the envs applying to an invocation are combined.
+///
+/// From least to most specific, those are the env configured for the resolved `Uri`,
+/// the envs configured for the `Uri`s redirected to it, and the env passed to the invocation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EnvMergeStrategy {
+    /// The most specific env is used as is.
+    #[default]
+    Replace,
+    /// Fields of more specific envs replace the fields of less specific ones.
+    Shallow,
+    /// Like `Shallow`, except that fields which are maps in both envs are merged recursively.
+    Deep,
+}
+
+impl EnvMergeStrategy {
+    /// Combines msgpack encoded envs, ordered from least to most specific.
+    /// Envs which aren't maps replace the previous ones whatever the strategy.
+    pub fn merge(&self, envs: &[&[u8]]) -> Result<Option<Vec<u8>>, Error> {
+        let mut merged: Option<Vec<u8>> = None;
+
+        for env in envs.iter() {
+            merged = Some(match (&merged, self) {
+                (Some(base), EnvMergeStrategy::Shallow) => merge_maps(base, env, false)?,
+                (Some(base), EnvMergeStrategy::Deep) => merge_maps(base, env, true)?,
+                _ => env.to_vec(),
+            });
+        }
+
+        Ok(merged)
+    }
+}
+
+fn merge_maps(base: &[u8], overrides: &[u8], deep: bool) -> Result<Vec<u8>, Error> {
+    let (Some(base_entries), Some(override_entries)) = (read_map(base)?, read_map(overrides)?)
+    else {
+        return Ok(overrides.to_vec());
+    };
+
+    let mut entries: Vec<(&[u8], Vec<u8>)> = base_entries
+        .into_iter()
+        .map(|(key, value)| (key, value.to_vec()))
+        .collect();
+
+    for (key, value) in override_entries {
+        match entries.iter_mut().find(|(base_key, _)| *base_key == key) {
+            Some((_, base_value)) if deep => *base_value = merge_maps(base_value, value, true)?,
+            Some((_, base_value)) => *base_value = value.to_vec(),
+            None => entries.push((key, value.to_vec())),
+        }
+    }
+
+    let mut merged = map_header(entries.len())?;
+    for (key, value) in entries {
+        merged.extend_from_slice(key);
+        merged.extend_from_slice(&value);
+    }
+
+    Ok(merged)
+}
+
+/// Encoded keys and values of a msgpack map.
+type MapEntries<'a> = Vec<(&'a [u8], &'a [u8])>;
+
+/// Splits a msgpack map into its entries, or returns `None` if it isn't a map.
+fn read_map(bytes: &[u8]) -> Result<Option<MapEntries<'_>>, Error> {
+    let (len, mut offset) = match bytes.first() {
+        Some(marker @ 0x80..=0x8f) => ((marker & 0x0f) as usize, 1),
+        Some(0xde) => (read_uint(bytes, 1, 2)?, 3),
+        Some(0xdf) => (read_uint(bytes, 1, 4)?, 5),
+        _ => return Ok(None),
+    };
+
+    let mut entries = Vec::with_capacity(len);
+    for _ in 0..len {
+        let key_len = value_len(&bytes[offset..])?;
+        let key = &bytes[offset..offset + key_len];
+        offset += key_len;
+
+        let value_len = value_len(&bytes[offset..])?;
+        let value = &bytes[offset..offset + value_len];
+        offset += value_len;
+
+        entries.push((key, value));
+    }
+
+    Ok(Some(entries))
+}
+
+/// Returns the length of the msgpack value at the start of `bytes`.
+fn value_len(bytes: &[u8]) -> Result<usize, Error> {
+    let marker = *bytes.first().ok_or_else(truncated)?;
+
+    let (header_len, data_len, items) = match marker {
+        0x00..=0x7f | 0xe0..=0xff | 0xc0 | 0xc2 | 0xc3 => (1, 0, 0),
+        0x80..=0x8f => (1, 0, 2 * (marker & 0x0f) as usize),
+        0x90..=0x9f => (1, 0, (marker & 0x0f) as usize),
+        0xa0..=0xbf => (1, (marker & 0x1f) as usize, 0),
+        0xcc | 0xd0 => (1, 1, 0),
+        0xcd | 0xd1 => (1, 2, 0),
+        0xca | 0xce | 0xd2 => (1, 4, 0),
+        0xcb | 0xcf | 0xd3 => (1, 8, 0),
+        0xc4 | 0xd9 => (2, read_uint(bytes, 1, 1)?, 0),
+        0xc5 | 0xda => (3, read_uint(bytes, 1, 2)?, 0),
+        0xc6 | 0xdb => (5, read_uint(bytes, 1, 4)?, 0),
+        0xd4 => (2, 1, 0),
+        0xd5 => (2, 2, 0),
+        0xd6 => (2, 4, 0),
+        0xd7 => (2, 8, 0),
+        0xd8 => (2, 16, 0),
+        0xc7 => (3, read_uint(bytes, 1, 1)?, 0),
+        0xc8 => (4, read_uint(bytes, 1, 2)?, 0),
+        0xc9 => (6, read_uint(bytes, 1, 4)?, 0),
+        0xdc => (3, 0, read_uint(bytes, 1, 2)?),
+        0xdd => (5, 0, read_uint(bytes, 1, 4)?),
+        0xde => (3, 0, 2 * read_uint(bytes, 1, 2)?),
+        0xdf => (5, 0, 2 * read_uint(bytes, 1, 4)?),
+        0xc1 => {
+            return Err(Error::OtherError(
+                "Invalid msgpack env: reserved marker 0xc1".to_string(),
+            ))
+        }
+    };
+
+    let mut len = header_len + data_len;
+    if len > bytes.len() {
+        return Err(truncated());
+    }
+
+    for _ in 0..items {
+        len += value_len(&bytes[len..])?;
+    }
+
+    Ok(len)
+}
+
+fn read_uint(bytes: &[u8], offset: usize, size: usize) -> Result<usize, Error> {
+    let bytes = bytes.get(offset..offset + size).ok_or_else(truncated)?;
+    Ok(bytes
+        .iter()
+        .fold(0, |value, byte| (value << 8) | *byte as usize))
+}
+
+fn map_header(len: usize) -> Result<Vec<u8>, Error> {
+    match len {
+        0..=0x0f => Ok(vec![0x80 | len as u8]),
+        0x10..=0xffff => Ok([&[0xde][..], &(len as u16).to_be_bytes()].concat()),
+        _ => u32::try_from(len)
+            .map(|len| [&[0xdf][..], &len.to_be_bytes()].concat())
+            .map_err(|_| Error::OtherError("Merged env has too many fields".to_string())),
+    }
+}
+
+fn truncated() -> Error {
+    Error::OtherError("Invalid msgpack env: unexpected end of buffer".to_string())
+}
@@ -1,26 +1,47 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
 use polywrap_msgpack_serde::Error as MsgpackError;
 
 /// An enum that represents all possible errors the Polywrap Client can throw.
+///
+/// Errors raised while invoking or loading a wrap keep their cause as `source()`,
+/// so nested subinvocation failures can be inspected with `root_cause` or `abort_info`.
 #[derive(thiserror::Error, Debug, Clone)]
 pub enum Error {
     #[error("Error parsing URI: `{0}`")]
     UriParseError(#[from] polywrap_uri::ParseError),
     #[error("`{0}`\nResolution Stack: `{1:#?}`")]
     RedirectsError(String, HashMap<String, String>),
-    #[error("`{0}`")]
-    WrapperError(String),
+    #[error("`{message}`")]
+    WrapperError {
+        message: String,
+        #[source]
+        source: Option<Box<Error>>,
+    },
     #[error("Failed to create wrapper: `{0}`")]
     WrapperCreateError(String),
-    #[error("Failed to invoke wrapper, uri: `{0}`, method: `{1}`: `{2}`")]
-    InvokeError(String, String, String),
-    #[error("Error loading wrapper, uri: {0}: `{1}`")]
-    LoadWrapperError(String, String),
+    #[error("Failed to invoke wrapper, uri: `{uri}`, method: `{method}`: `{source}`")]
+    InvokeError {
+        uri: String,
+        method: String,
+        source: Box<Error>,
+    },
+    #[error("Error loading wrapper, uri: {uri}: `{source}`")]
+    LoadWrapperError { uri: String, source: Box<Error> },
+    #[error("__wrap_abort: {}\nFile: {}\nLocation: [{},{}]", .info.message, .info.file, .info.line, .info.column)]
+    WrapAbort {
+        info: WrapAbortInfo,
+        #[source]
+        source: Option<Box<Error>>,
+    },
     #[error("WasmWrapper error: `{0}`")]
     WasmWrapperError(String),
-    #[error("Failed to resolve wrapper: `{0}`")]
-    ResolutionError(String),
+    #[error("Failed to resolve wrapper: `{message}`")]
+    ResolutionError {
+        message: String,
+        #[source]
+        source: Option<Box<Error>>,
+    },
     #[error("URI not found: `{0}`")]
     UriNotFoundError(String),
     #[error(transparent)]
@@ -35,6 +56,153 @@ pub enum Error {
     PluginError(String),
     #[error("`{0}`")]
     RuntimeError(String),
+    #[error("Invocation timed out: `{0}`")]
+    TimeoutError(String),
+    #[error("Invocation cancelled: `{0}`")]
+    CancelledError(String),
+    #[error("Resource limit exceeded: `{0}`")]
+    ResourceLimitError(String),
+    #[error("Invalid JSON at `{path}`: {message}")]
+    JsonError { path: String, message: String },
+    #[error("Invalid arguments, uri: `{uri}`, method: `{method}`:{}", format_mismatches(.mismatches))]
+    ArgsValidationError {
+        uri: String,
+        method: String,
+        mismatches: Vec<ArgsMismatch>,
+    },
     #[error("`{0}`")]
     OtherError(String),
 }
+
+/// Information passed by a wrap to `__wrap_abort` when it throws.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrapAbortInfo {
+    pub message: String,
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+/// Argument of an invocation which doesn't match the ABI of the invoked method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgsMismatch {
+    /// Path of the argument, e.g. `$.request.headers[0]`
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for ArgsMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.path, self.message)
+    }
+}
+
+fn format_mismatches(mismatches: &[ArgsMismatch]) -> String {
+    mismatches
+        .iter()
+        .map(|mismatch| format!("\n  {mismatch}"))
+        .collect()
+}
+
+/// The kind of an `Error`, used to match on errors without inspecting their messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    UriParse,
+    Redirects,
+    Wrapper,
+    WrapperCreate,
+    Invoke,
+    LoadWrapper,
+    WrapAbort,
+    WasmWrapper,
+    Resolution,
+    UriNotFound,
+    Msgpack,
+    Manifest,
+    FileRead,
+    Resolver,
+    Plugin,
+    Runtime,
+    Timeout,
+    Cancelled,
+    ResourceLimit,
+    Json,
+    ArgsValidation,
+    Other,
+}
+
+impl Error {
+    /// Creates a `WrapperError` without a cause.
+    pub fn wrapper(message: impl Into<String>) -> Self {
+        Error::WrapperError {
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Creates a `ResolutionError` without a cause.
+    pub fn resolution(message: impl Into<String>) -> Self {
+        Error::ResolutionError {
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Returns the kind of this error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::UriParseError(_) => ErrorKind::UriParse,
+            Error::RedirectsError(_, _) => ErrorKind::Redirects,
+            Error::WrapperError { .. } => ErrorKind::Wrapper,
+            Error::WrapperCreateError(_) => ErrorKind::WrapperCreate,
+            Error::InvokeError { .. } => ErrorKind::Invoke,
+            Error::LoadWrapperError { .. } => ErrorKind::LoadWrapper,
+            Error::WrapAbort { .. } => ErrorKind::WrapAbort,
+            Error::WasmWrapperError(_) => ErrorKind::WasmWrapper,
+            Error::ResolutionError { .. } => ErrorKind::Resolution,
+            Error::UriNotFoundError(_) => ErrorKind::UriNotFound,
+            Error::MsgpackError(_) => ErrorKind::Msgpack,
+            Error::ManifestError(_) => ErrorKind::Manifest,
+            Error::FileReadError(_) => ErrorKind::FileRead,
+            Error::ResolverError(_) => ErrorKind::Resolver,
+            Error::PluginError(_) => ErrorKind::Plugin,
+            Error::RuntimeError(_) => ErrorKind::Runtime,
+            Error::TimeoutError(_) => ErrorKind::Timeout,
+            Error::CancelledError(_) => ErrorKind::Cancelled,
+            Error::ResourceLimitError(_) => ErrorKind::ResourceLimit,
+            Error::JsonError { .. } => ErrorKind::Json,
+            Error::ArgsValidationError { .. } => ErrorKind::ArgsValidation,
+            Error::OtherError(_) => ErrorKind::Other,
+        }
+    }
+
+    /// Returns the error that caused this one, if any.
+    pub fn cause(&self) -> Option<&Error> {
+        match self {
+            Error::InvokeError { source, .. } => Some(source),
+            Error::LoadWrapperError { source, .. } => Some(source),
+            Error::WrapAbort { source, .. } => source.as_deref(),
+            Error::WrapperError { source, .. } => source.as_deref(),
+            Error::ResolutionError { source, .. } => source.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over this error and all of its causes, starting with this error.
+    pub fn chain(&self) -> impl Iterator<Item = &Error> {
+        std::iter::successors(Some(self), |e| e.cause())
+    }
+
+    /// Returns the innermost cause of this error.
+    pub fn root_cause(&self) -> &Error {
+        self.chain().last().unwrap_or(self)
+    }
+
+    /// Returns the abort information of the first wrap that aborted along the cause chain.
+    pub fn abort_info(&self) -> Option<&WrapAbortInfo> {
+        self.chain().find_map(|e| match e {
+            Error::WrapAbort { info, .. } => Some(info),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,49 @@
+use std::fmt::Debug;
+
+use crate::{error::Error, uri::Uri};
+
+/// An invocation as seen by an `InvokeInterceptor`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Invocation {
+    /// `Uri` of the invoked wrap.
+    pub uri: Uri,
+    /// Name of the invoked method.
+    pub method: String,
+    /// Optional msgpack buffer representing the arguments.
+    pub args: Option<Vec<u8>>,
+    /// Optional msgpack buffer representing the environment.
+    pub env: Option<Vec<u8>>,
+}
+
+/// Hooks into every invocation made through a client, subinvocations included.
+///
+/// Interceptors run in the order they were registered before an invocation,
+/// and in reverse order after it.
+pub trait InvokeInterceptor: Send + Sync + Debug {
+    /// Called before the wrap is invoked.
+    /// The invocation can be modified in place, and returning `Some` skips the wrap
+    /// (and the remaining interceptors) with the given result.
+    /// Invocations of a given wrapper, through `WrapInvoker::invoke_wrapper_raw`,
+    /// fail if their URI is changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `invocation` - The invocation about to be executed.
+    fn before_invoke(&self, _invocation: &mut Invocation) -> Option<Result<Vec<u8>, Error>> {
+        None
+    }
+
+    /// Called with the result of the invocation. The returned result replaces it.
+    ///
+    /// # Arguments
+    ///
+    /// * `invocation` - The executed invocation, as modified by the interceptors.
+    /// * `result` - The msgpack buffer of the response, or the error of the invocation.
+    fn after_invoke(
+        &self,
+        _invocation: &Invocation,
+        result: Result<Vec<u8>, Error>,
+    ) -> Result<Vec<u8>, Error> {
+        result
+    }
+}
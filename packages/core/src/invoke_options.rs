@@ -0,0 +1,178 @@
+use std::{
+    fmt::{Debug, Formatter},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{error::Error, resource_limits::ResourceUsage};
+
+/// Options that bound the execution of an invocation and of all its subinvocations.
+#[derive(Clone, Debug, Default)]
+pub struct InvokeOptions {
+    /// Instant after which the invocation fails with `Error::TimeoutError`.
+    pub deadline: Option<Instant>,
+    /// Token that fails the invocation with `Error::CancelledError` once cancelled.
+    pub cancellation_token: Option<CancellationToken>,
+    /// Handle the fuel consumed by the invocation is added to.
+    pub resource_usage: Option<ResourceUsage>,
+}
+
+impl InvokeOptions {
+    /// Creates options with a deadline `timeout` from now.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            deadline: Some(Instant::now() + timeout),
+            cancellation_token: None,
+            resource_usage: None,
+        }
+    }
+
+    /// Creates options that can be cancelled through the given token.
+    pub fn with_cancellation_token(cancellation_token: CancellationToken) -> Self {
+        Self {
+            deadline: None,
+            cancellation_token: Some(cancellation_token),
+            resource_usage: None,
+        }
+    }
+
+    /// Creates options reporting the resources used by the invocation to `resource_usage`.
+    pub fn with_resource_usage(resource_usage: ResourceUsage) -> Self {
+        Self {
+            deadline: None,
+            cancellation_token: None,
+            resource_usage: Some(resource_usage),
+        }
+    }
+
+    /// Returns the options an invocation made within `parent` runs with:
+    /// the earliest of both deadlines, and this token and usage handle or else the parent's.
+    pub fn within(&self, parent: Option<&InvokeOptions>) -> Self {
+        let Some(parent) = parent else {
+            return self.clone();
+        };
+
+        let deadline = match (self.deadline, parent.deadline) {
+            (Some(deadline), Some(parent_deadline)) => Some(deadline.min(parent_deadline)),
+            (deadline, parent_deadline) => deadline.or(parent_deadline),
+        };
+
+        Self {
+            deadline,
+            cancellation_token: self
+                .cancellation_token
+                .clone()
+                .or_else(|| parent.cancellation_token.clone()),
+            resource_usage: self
+                .resource_usage
+                .clone()
+                .or_else(|| parent.resource_usage.clone()),
+        }
+    }
+
+    /// Returns an error if the invocation has been cancelled or its deadline has passed.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - Description of the invocation, used in the error message.
+    pub fn check(&self, context: &str) -> Result<(), Error> {
+        if let Some(token) = &self.cancellation_token {
+            if token.is_cancelled() {
+                return Err(Error::CancelledError(context.to_string()));
+            }
+        }
+
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                return Err(Error::TimeoutError(context.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+type CancelCallback = Box<dyn FnOnce() + Send>;
+
+#[derive(Default)]
+struct CancellationState {
+    cancelled: AtomicBool,
+    next_id: AtomicU64,
+    callbacks: Mutex<Vec<(u64, CancelCallback)>>,
+}
+
+/// A cloneable handle used to cancel running invocations.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<CancellationState>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token, running every registered callback.
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::SeqCst);
+
+        let callbacks = std::mem::take(&mut *self.state.callbacks.lock().unwrap());
+        for (_, callback) in callbacks {
+            callback();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Registers a callback to run when the token is cancelled, or right away if it already is.
+    /// The callback is unregistered when the returned guard is dropped.
+    pub fn on_cancel(&self, callback: impl FnOnce() + Send + 'static) -> CancellationGuard {
+        let mut callbacks = self.state.callbacks.lock().unwrap();
+
+        if self.is_cancelled() {
+            drop(callbacks);
+            callback();
+            return CancellationGuard { token: None, id: 0 };
+        }
+
+        let id = self.state.next_id.fetch_add(1, Ordering::Relaxed);
+        callbacks.push((id, Box::new(callback)));
+
+        CancellationGuard {
+            token: Some(self.clone()),
+            id,
+        }
+    }
+}
+
+impl Debug for CancellationToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// Unregisters a `CancellationToken` callback when dropped.
+pub struct CancellationGuard {
+    token: Option<CancellationToken>,
+    id: u64,
+}
+
+impl Drop for CancellationGuard {
+    fn drop(&mut self) {
+        if let Some(token) = &self.token {
+            token
+                .state
+                .callbacks
+                .lock()
+                .unwrap()
+                .retain(|(id, _)| *id != self.id);
+        }
+    }
+}
@@ -1,8 +1,12 @@
 use std::sync::{Arc, Mutex};
 
+use tokio::runtime::Handle;
+
 use crate::{
     error::Error, interface_implementation::InterfaceImplementations,
-    resolution::uri_resolution_context::UriResolutionContext, uri::Uri,
+    invoke_options::InvokeOptions, resolution::uri_resolution_context::UriResolutionContext,
+    resource_limits::ResourceLimits, uri::Uri, wasi_policy::WasiPolicy,
+    wrap_log::WrapLogSink,
 };
 
 /// Defines an object capable of invoking on URIs
@@ -42,4 +46,35 @@ pub trait Invoker: Send + Sync {
     ///
     /// * `uri` - A reference to the Uri to get the environment for.
     fn get_env_by_uri(&self, uri: &Uri) -> Option<Vec<u8>>;
-}
\ No newline at end of file
+
+    /// Returns the options bounding the invocation this invoker runs within, if any.
+    /// Wrappers use them to stop executing once the invocation times out or is cancelled.
+    fn get_invoke_options(&self) -> Option<InvokeOptions> {
+        None
+    }
+
+    /// Returns the resource limits of the wrap invoked with this invoker, if any.
+    fn get_resource_limits(&self) -> Option<ResourceLimits> {
+        None
+    }
+
+    /// Returns the WASI capabilities of the wrap invoked with this invoker, if any.
+    fn get_wasi_policy(&self) -> Option<WasiPolicy> {
+        None
+    }
+
+    /// Returns the URI of the wrap invoked with this invoker, if known.
+    fn get_wrap_uri(&self) -> Option<Uri> {
+        None
+    }
+
+    /// Returns the sink receiving the events logged by wraps, if any.
+    fn get_log_sink(&self) -> Option<Arc<dyn WrapLogSink>> {
+        None
+    }
+
+    /// Returns the runtime driving the async methods of plugins invoked synchronously, if any.
+    fn get_executor(&self) -> Option<Handle> {
+        None
+    }
+}
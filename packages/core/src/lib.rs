@@ -1,14 +1,25 @@
+pub mod async_invoker;
+pub mod async_wrap_loader;
+pub mod async_wrapper;
 pub mod client;
+pub mod env_inheritance;
+pub mod env_merge;
 pub mod error;
 pub mod file_reader;
+pub mod interceptor;
 pub mod interface_implementation;
+pub mod invoke_options;
 pub mod invoker;
 pub mod package;
 pub mod redirects;
 pub mod resolution;
+pub mod resource_limits;
+pub mod telemetry;
 pub mod uri_resolver_handler;
+pub mod wasi_policy;
 pub mod wrap_invoker;
 pub mod wrap_loader;
+pub mod wrap_log;
 pub mod wrapper;
 pub use polywrap_core_macros as macros;
 pub use polywrap_uri as uri;
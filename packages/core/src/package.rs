@@ -22,4 +22,9 @@ pub trait WrapPackage: Send + Sync + Debug + Any {
     fn create_wrapper(&self) -> Result<Arc<dyn Wrapper>, Error>;
     /// Retrieves the wrap's manifest.
     fn get_manifest(&self, options: Option<&GetManifestOptions>) -> Result<WrapManifest, Error>;
+    /// Checks that the package is consistent with its manifest, e.g. that a plugin
+    /// implements the methods of its ABI.
+    fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
 }
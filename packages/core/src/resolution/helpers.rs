@@ -1,8 +1,9 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use crate::{
     client::CoreClient, error::Error, file_reader::FileReader,
-    interface_implementation::InterfaceImplementations, invoker::Invoker, uri::Uri,
+    interface_implementation::InterfaceImplementations, invoker::Invoker,
+    resolution::uri_resolution_context::UriResolutionStep, uri::Uri,
 };
 use polywrap_msgpack_serde::to_vec;
 use serde::Serialize;
@@ -104,3 +105,38 @@ pub fn get_env_from_resolution_path(
 
     None
 }
+
+fn collect_redirects(history: &[UriResolutionStep], redirects: &mut HashMap<Uri, Uri>) {
+    for step in history.iter() {
+        if let Ok(result) = &step.result {
+            let result_uri = result.uri();
+            if result_uri != step.source_uri {
+                redirects
+                    .entry(step.source_uri.clone())
+                    .or_insert(result_uri);
+            }
+        }
+
+        if let Some(sub_history) = &step.sub_history {
+            collect_redirects(sub_history, redirects);
+        }
+    }
+}
+
+/// Returns the URIs a URI was redirected through while being resolved,
+/// starting with the URI itself, by following the redirects recorded in its resolution history.
+/// Unlike the resolution path, it doesn't depend on the resolvers tracking the URIs they resolve.
+pub fn get_redirect_chain(uri: &Uri, history: &[UriResolutionStep]) -> Vec<Uri> {
+    let mut redirects = HashMap::new();
+    collect_redirects(history, &mut redirects);
+
+    let mut chain = vec![uri.clone()];
+    while let Some(next) = redirects.get(chain.last().unwrap()) {
+        if chain.contains(next) {
+            break;
+        }
+        chain.push(next.clone());
+    }
+
+    chain
+}
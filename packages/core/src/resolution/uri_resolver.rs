@@ -14,4 +14,9 @@ pub trait UriResolver: Send + Sync + Debug {
         client: Arc<dyn Invoker>,
         resolution_context: Arc<Mutex<UriResolutionContext>>,
     ) -> Result<UriPackageOrWrapper, Error>;
+
+    /// Returns the name of the resolver, used to identify it in tracing spans.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
 }
@@ -0,0 +1,49 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+/// Limits on the resources a wrap can use during a single invocation.
+/// Unset limits are unbounded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Instruction budget of the invocation. Execution fails with
+    /// `Error::ResourceLimitError` once it has been used up.
+    pub fuel: Option<u64>,
+    /// Maximum number of 64KiB pages the memory of the wrap can grow to.
+    pub max_memory_pages: Option<u32>,
+}
+
+impl ResourceLimits {
+    /// Returns these limits, taking the ones left unset from `fallback`.
+    pub fn or(&self, fallback: &ResourceLimits) -> Self {
+        Self {
+            fuel: self.fuel.or(fallback.fuel),
+            max_memory_pages: self.max_memory_pages.or(fallback.max_memory_pages),
+        }
+    }
+}
+
+/// A cloneable handle accumulating the resources used by an invocation and its
+/// subinvocations, to be read once it completes.
+#[derive(Clone, Debug, Default)]
+pub struct ResourceUsage {
+    fuel_consumed: Arc<AtomicU64>,
+}
+
+impl ResourceUsage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fuel consumed by the Wasm wraps invoked so far.
+    /// Only wraps invoked with a fuel limit are metered.
+    pub fn fuel_consumed(&self) -> u64 {
+        self.fuel_consumed.load(Ordering::SeqCst)
+    }
+
+    /// Adds the fuel consumed by an invocation.
+    pub fn add_fuel_consumed(&self, fuel: u64) {
+        self.fuel_consumed.fetch_add(fuel, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::{Arc, Mutex};
+
+use tracing::Span;
+
+use crate::{
+    error::Error,
+    invoker::Invoker,
+    resolution::{
+        uri_resolution_context::{UriPackageOrWrapper, UriResolutionContext},
+        uri_resolver::UriResolver,
+    },
+    uri::Uri,
+};
+
+/// Records the outcome of an operation on a span declared with
+/// the `otel.status_code` and `error` fields.
+pub fn record_result<T>(span: &Span, result: &Result<T, Error>) {
+    match result {
+        Ok(_) => {
+            span.record("otel.status_code", "OK");
+        }
+        Err(error) => {
+            span.record("otel.status_code", "ERROR");
+            span.record("error", tracing::field::display(error));
+        }
+    }
+}
+
+/// Records the outcome of a URI resolution on a span declared with
+/// the `outcome`, `otel.status_code` and `error` fields.
+pub fn record_resolution(span: &Span, result: &Result<UriPackageOrWrapper, Error>) {
+    if let Ok(uri_package_or_wrapper) = result {
+        let outcome = match uri_package_or_wrapper {
+            UriPackageOrWrapper::Uri(_) => "uri",
+            UriPackageOrWrapper::Package(_, _) => "package",
+            UriPackageOrWrapper::Wrapper(_, _) => "wrapper",
+        };
+        span.record("outcome", outcome);
+    }
+
+    record_result(span, result);
+}
+
+/// Resolves a URI with a resolver inside a `try_resolve_uri` span.
+///
+/// Used wherever a resolver is called, so that every resolver taking part
+/// in a resolution gets its own span, nested like the resolvers themselves.
+pub fn try_resolve_uri_in_span(
+    resolver: &dyn UriResolver,
+    uri: &Uri,
+    invoker: Arc<dyn Invoker>,
+    resolution_context: Arc<Mutex<UriResolutionContext>>,
+) -> Result<UriPackageOrWrapper, Error> {
+    let span = tracing::debug_span!(
+        "try_resolve_uri",
+        uri = %uri,
+        resolver = resolver.name(),
+        outcome = tracing::field::Empty,
+        otel.status_code = tracing::field::Empty,
+        error = tracing::field::Empty,
+    );
+
+    let result = span.in_scope(|| resolver.try_resolve_uri(uri, invoker, resolution_context));
+
+    record_resolution(&span, &result);
+    result
+}
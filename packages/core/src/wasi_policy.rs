@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+/// Directory of the host made available to a wrap through WASI.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreopenedDir {
+    /// Path of the directory on the host.
+    pub host_path: PathBuf,
+    /// Path the wrap accesses the directory with, e.g. `/data`.
+    pub guest_path: String,
+    /// Prevents the wrap from creating, writing or removing anything in the directory.
+    pub read_only: bool,
+}
+
+/// Capabilities a wrap is granted through the WASI preview1 imports.
+/// Everything is denied by default, calls relying on a denied capability fail.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WasiPolicy {
+    /// Allows reading the realtime and monotonic clocks.
+    pub clock: bool,
+    /// Allows generating random bytes.
+    pub random: bool,
+    /// Forwards writes to stdout to the stdout of the host.
+    pub stdout: bool,
+    /// Forwards writes to stderr to the stderr of the host.
+    pub stderr: bool,
+    /// Directories the wrap can access.
+    pub preopened_dirs: Vec<PreopenedDir>,
+}
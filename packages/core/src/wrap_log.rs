@@ -0,0 +1,61 @@
+use std::fmt::Debug;
+
+use crate::{error::WrapAbortInfo, uri::Uri};
+
+/// Target of the `tracing` events emitted by `TracingLogSink`.
+pub const WRAP_LOG_TARGET: &str = "polywrap::wrap";
+
+/// Event logged by a wrap through its host imports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WrapLogEvent {
+    /// Message passed to `__wrap_debug_log`.
+    Debug(String),
+    /// Information passed to `__wrap_abort`.
+    Abort(WrapAbortInfo),
+}
+
+/// Event logged by a wrap, along with the invocation it was logged during.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrapLogRecord {
+    /// URI of the invoked wrap, if known.
+    pub uri: Option<Uri>,
+    pub method: String,
+    pub event: WrapLogEvent,
+}
+
+/// Receives the events logged by wraps.
+/// Implement it to capture, redirect or silence the logs of wraps.
+pub trait WrapLogSink: Send + Sync + Debug {
+    fn log(&self, record: &WrapLogRecord);
+}
+
+/// Sink emitting wrap logs as `tracing` events with the `polywrap::wrap` target,
+/// debug messages at the `DEBUG` level and aborts at the `WARN` level.
+/// Used when no other sink is configured.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingLogSink;
+
+impl WrapLogSink for TracingLogSink {
+    fn log(&self, record: &WrapLogRecord) {
+        let uri = record.uri.as_ref().map(|uri| uri.to_string());
+        let method = record.method.as_str();
+
+        match &record.event {
+            WrapLogEvent::Debug(message) => {
+                tracing::debug!(target: WRAP_LOG_TARGET, uri, method, "{message}");
+            }
+            WrapLogEvent::Abort(info) => {
+                tracing::warn!(
+                    target: WRAP_LOG_TARGET,
+                    uri,
+                    method,
+                    file = info.file.as_str(),
+                    line = info.line,
+                    column = info.column,
+                    "{}",
+                    info.message
+                );
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::{any::Any, fmt::Debug, sync::Arc};
 
-use crate::{error::Error, invoker::Invoker};
+use crate::{async_wrapper::AsyncWrapper, error::Error, invoker::Invoker};
 
 /// The `Encoding` enum is used to specify the type of encoding for a file.
 /// It currently supports Base64 and UTF8 encoding.
@@ -40,4 +40,10 @@ pub trait Wrapper: Send + Sync + Debug + Any {
     /// The `get_file` method is used to get a file with the specified options.
     /// It returns a Result containing a byte vector on success, or an Error on failure.
     fn get_file(&self, options: &GetFileOptions) -> Result<Vec<u8>, Error>;
+
+    /// Returns the wrapper as an `AsyncWrapper` if its invocations can be awaited natively.
+    /// The async API of the client runs the others on the blocking thread pool.
+    fn as_async(&self) -> Option<&dyn AsyncWrapper> {
+        None
+    }
 }
@@ -3,9 +3,11 @@ use polywrap_fs_plugin::FileSystemPlugin;
 use polywrap_http_plugin::HttpPlugin;
 use polywrap_logger_plugin::LoggerPlugin;
 
-use polywrap_core::{client::CoreClientConfig, macros::uri, uri::Uri, package::WrapPackage};
+use polywrap_core::{
+    client::CoreClientConfig, error::Error, macros::uri, package::WrapPackage, uri::Uri,
+};
 use polywrap_msgpack_serde::to_vec;
-use polywrap_plugin::package::PluginPackage;
+use polywrap_plugin::package::{ConcurrentPluginPackage, PluginPackage};
 use serde::Serialize;
 use std::{collections::HashMap, sync::Arc};
 
@@ -88,9 +90,11 @@ impl Into<ClientConfig> for SystemClientConfig {
     }
 }
 
-impl Into<CoreClientConfig> for SystemClientConfig {
-    fn into(self) -> CoreClientConfig {
-        self.0.into()
+impl TryFrom<SystemClientConfig> for CoreClientConfig {
+    type Error = Error;
+
+    fn try_from(config: SystemClientConfig) -> Result<Self, Self::Error> {
+        config.0.try_into()
     }
 }
 
@@ -102,7 +106,7 @@ fn build_config(packages: Option<Vec<(Uri, Arc<dyn WrapPackage>)>>) -> SystemCli
         ),
         (
             uri!("plugin/http@1.1.0"),
-            Arc::new(PluginPackage::from(HttpPlugin {})),
+            Arc::new(ConcurrentPluginPackage::from(HttpPlugin {})),
         ),
         (
             uri!("wrapscan.io/polywrap/logger@1.0"),
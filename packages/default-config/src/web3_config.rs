@@ -1,9 +1,9 @@
 use polywrap_client_builder::ClientConfig;
-use polywrap_core::{client::CoreClientConfig, macros::uri, uri::Uri};
+use polywrap_core::{client::CoreClientConfig, error::Error, macros::uri, uri::Uri};
 use polywrap_ethereum_wallet_plugin::{
     connection::Connection, connections::Connections, EthereumWalletPlugin,
 };
-use polywrap_plugin::package::PluginPackage;
+use polywrap_plugin::package::ConcurrentPluginPackage;
 use std::{collections::HashMap, sync::Arc};
 
 /// The default Web3 config for the `Client`
@@ -11,7 +11,7 @@ use std::{collections::HashMap, sync::Arc};
 pub struct Web3ClientConfig(ClientConfig);
 
 impl Web3ClientConfig {
-    fn get_ethereum_plugin() -> PluginPackage<EthereumWalletPlugin> {
+    fn get_ethereum_plugin() -> ConcurrentPluginPackage<EthereumWalletPlugin> {
         let mainnet_connection = Connection::new(
             "https://mainnet.infura.io/v3/f1f688077be642c190ac9b28769daecf".to_string(),
             None,
@@ -71,8 +71,10 @@ impl Into<ClientConfig> for Web3ClientConfig {
     }
 }
 
-impl Into<CoreClientConfig> for Web3ClientConfig {
-    fn into(self) -> CoreClientConfig {
-        self.0.into()
+impl TryFrom<Web3ClientConfig> for CoreClientConfig {
+    type Error = Error;
+
+    fn try_from(config: Web3ClientConfig) -> Result<Self, Self::Error> {
+        config.0.try_into()
     }
 }
@@ -17,7 +17,7 @@ fn text_record_uri_resolver() {
         .add(SystemClientConfig::precompiled().into())
         .add(Web3ClientConfig::default().into());
 
-    let client = Client::new(config.into());
+    let client = Client::new(config.try_into().unwrap());
     let result = client.invoke::<String>(
         &wrap_uri.parse().unwrap(),
         "toWei",
@@ -41,7 +41,7 @@ fn content_hash_uri_resolver() {
         .add(SystemClientConfig::default().into())
         .add(Web3ClientConfig::default().into());
 
-    let client = Client::new(config.into());
+    let client = Client::new(config.try_into().unwrap());
     let result = client.invoke::<String>(
         &wrap_uri.parse().unwrap(),
         "toWei",
@@ -16,7 +16,7 @@ struct Args {
 
 #[test]
 fn sanity() {
-    let client = Client::new(SystemClientConfig::default().into());
+    let client = Client::new(SystemClientConfig::default().try_into().unwrap());
 
     let result = client
         .invoke::<u32>(
@@ -36,7 +36,7 @@ fn sanity() {
 
     assert_eq!(result, 42);
 
-    let client = Client::new(SystemClientConfig::precompiled().into());
+    let client = Client::new(SystemClientConfig::precompiled().try_into().unwrap());
 
     let result = client
         .invoke::<u32>(
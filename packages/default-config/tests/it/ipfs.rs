@@ -12,7 +12,7 @@ fn sanity() {
     let mut config = ClientConfig::new();
     config.add(SystemClientConfig::precompiled().into());
 
-    let client = Client::new(config.into());
+    let client = Client::new(config.try_into().unwrap());
     let result = client
         .invoke::<u32>(
             &SUBINVOKE_WRAP_URI.parse().unwrap(),
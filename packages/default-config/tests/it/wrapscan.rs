@@ -9,7 +9,7 @@ fn sanity() {
     config
         .add(SystemClientConfig::precompiled().into());
 
-    let client = Client::new(config.into());
+    let client = Client::new(config.try_into().unwrap());
     client
         .load_wrapper(
             &uri!("wrapscan.io/polywrap/wrapscan-uri-resolver@1.0.0"),
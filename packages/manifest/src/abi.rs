@@ -0,0 +1,340 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{
+    error::Error,
+    versions::{
+        AnyDefinition, ArrayDefinition, EnumDefinition, EnvDefinition, ImportedEnumDefinition,
+        ImportedEnvDefinition, ImportedModuleDefinition, ImportedObjectDefinition,
+        InterfaceDefinition, MapDefinition, MethodDefinition, ModuleDefinition, ObjectDefinition,
+        ObjectRef, PropertyDefinition, ScalarDefinition, UnresolvedObjectOrEnumRef,
+        WrapManifestAbi,
+    },
+};
+
+/// ABI of a wrap, with the definitions of its manifest parsed into typed structs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Abi {
+    pub module: Option<ModuleDefinition>,
+    pub env: Option<EnvDefinition>,
+    pub objects: Vec<ObjectDefinition>,
+    pub enums: Vec<EnumDefinition>,
+    pub interfaces: Vec<InterfaceDefinition>,
+    pub imported_modules: Vec<ImportedModuleDefinition>,
+    pub imported_objects: Vec<ImportedObjectDefinition>,
+    pub imported_enums: Vec<ImportedEnumDefinition>,
+    pub imported_envs: Vec<ImportedEnvDefinition>,
+}
+
+/// Module declared in an ABI, either the wrap's own module or an imported one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AbiModule<'a> {
+    /// Namespace of the module, `None` for the wrap's own module.
+    pub namespace: Option<&'a str>,
+    /// URI of the module, `None` for the wrap's own module.
+    pub uri: Option<&'a str>,
+    pub methods: &'a [Box<MethodDefinition>],
+}
+
+/// Definition of a named type declared in an ABI.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AbiTypeDefinition<'a> {
+    Object(&'a ObjectDefinition),
+    Enum(&'a EnumDefinition),
+    Env(&'a EnvDefinition),
+    ImportedObject(&'a ImportedObjectDefinition),
+    ImportedEnum(&'a ImportedEnumDefinition),
+    ImportedEnv(&'a ImportedEnvDefinition),
+}
+
+impl Abi {
+    /// Returns the methods of the wrap's own module.
+    pub fn methods(&self) -> &[Box<MethodDefinition>] {
+        self.module
+            .as_ref()
+            .and_then(|module| module.methods.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Returns the method of the wrap's own module with the given name.
+    pub fn method(&self, name: &str) -> Option<&MethodDefinition> {
+        self.methods()
+            .iter()
+            .map(|method| method.as_ref())
+            .find(|method| method.name.as_deref() == Some(name))
+    }
+
+    /// Returns the wrap's own module, if any, followed by the modules it imports.
+    pub fn modules(&self) -> Vec<AbiModule<'_>> {
+        let module = self.module.as_ref().map(|module| AbiModule {
+            namespace: None,
+            uri: None,
+            methods: module.methods.as_deref().unwrap_or_default(),
+        });
+
+        let imported_modules = self.imported_modules.iter().map(|module| AbiModule {
+            namespace: Some(&module.namespace),
+            uri: Some(&module.uri),
+            methods: module.methods.as_deref().unwrap_or_default(),
+        });
+
+        module.into_iter().chain(imported_modules).collect()
+    }
+
+    /// Returns the definition of the object, enum or env type with the given name.
+    /// Imported types are named after their namespace, e.g. `Namespace_Type`.
+    pub fn resolve_type(&self, type_name: &str) -> Option<AbiTypeDefinition<'_>> {
+        let object = self
+            .objects
+            .iter()
+            .find(|object| object.type_ == type_name)
+            .map(AbiTypeDefinition::Object);
+        let enum_type = || {
+            self.enums
+                .iter()
+                .find(|enum_type| enum_type.type_ == type_name)
+                .map(AbiTypeDefinition::Enum)
+        };
+        let env = || {
+            self.env
+                .as_ref()
+                .filter(|env| env.type_ == type_name)
+                .map(AbiTypeDefinition::Env)
+        };
+        let imported_object = || {
+            self.imported_objects
+                .iter()
+                .find(|object| object.type_ == type_name)
+                .map(AbiTypeDefinition::ImportedObject)
+        };
+        let imported_enum = || {
+            self.imported_enums
+                .iter()
+                .find(|enum_type| enum_type.type_ == type_name)
+                .map(AbiTypeDefinition::ImportedEnum)
+        };
+        let imported_env = || {
+            self.imported_envs
+                .iter()
+                .find(|env| env.type_ == type_name)
+                .map(AbiTypeDefinition::ImportedEnv)
+        };
+
+        object
+            .or_else(enum_type)
+            .or_else(env)
+            .or_else(imported_object)
+            .or_else(imported_enum)
+            .or_else(imported_env)
+    }
+}
+
+impl TryFrom<&WrapManifestAbi> for Abi {
+    type Error = Error;
+
+    fn try_from(abi: &WrapManifestAbi) -> Result<Self, Self::Error> {
+        Ok(Self {
+            module: abi.module_type.as_ref().map(parse_definition).transpose()?,
+            env: abi.env_type.as_ref().map(parse_definition).transpose()?,
+            objects: parse_definitions(&abi.object_types)?,
+            enums: parse_definitions(&abi.enum_types)?,
+            interfaces: parse_definitions(&abi.interface_types)?,
+            imported_modules: parse_definitions(&abi.imported_module_types)?,
+            imported_objects: parse_definitions(&abi.imported_object_types)?,
+            imported_enums: parse_definitions(&abi.imported_enum_types)?,
+            imported_envs: parse_definitions(&abi.imported_env_types)?,
+        })
+    }
+}
+
+fn parse_definition<T: DeserializeOwned>(definition: &BTreeMap<String, Value>) -> Result<T, Error> {
+    let definition = Value::Object(definition.clone().into_iter().collect());
+    Ok(serde_json::from_value(definition)?)
+}
+
+fn parse_definitions<T: DeserializeOwned>(
+    definitions: &Option<Vec<BTreeMap<String, Value>>>,
+) -> Result<Vec<T>, Error> {
+    definitions.iter().flatten().map(parse_definition).collect()
+}
+
+/// Type of a property, argument or return value declared in an ABI.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeRef {
+    pub kind: TypeKind,
+    /// Whether values of the type can't be null.
+    pub required: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeKind {
+    /// Built-in type, e.g. `String` or `UInt32`.
+    Scalar(String),
+    Object(String),
+    Enum(String),
+    /// Object or enum the ABI doesn't tell which of.
+    Unresolved(String),
+    Array(Box<TypeRef>),
+    Map {
+        key: String,
+        value: Box<TypeRef>,
+    },
+}
+
+impl Display for TypeRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if self.required {
+            write!(f, "!")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for TypeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeKind::Scalar(name)
+            | TypeKind::Object(name)
+            | TypeKind::Enum(name)
+            | TypeKind::Unresolved(name) => write!(f, "{name}"),
+            TypeKind::Array(item) => write!(f, "[{item}]"),
+            TypeKind::Map { key, value } => write!(f, "Map<{key}!, {value}>"),
+        }
+    }
+}
+
+/// Fields shared by the definitions of values, describing the type of the value
+/// or, for arrays and maps, the type of their items or values.
+struct TypeFields<'a> {
+    type_: &'a str,
+    required: Option<bool>,
+    array: Option<&'a ArrayDefinition>,
+    map: Option<&'a MapDefinition>,
+    scalar: Option<&'a ScalarDefinition>,
+    object: Option<&'a ObjectRef>,
+    enum_: Option<&'a Value>,
+    unresolved_object_or_enum: Option<&'a UnresolvedObjectOrEnumRef>,
+}
+
+impl TypeFields<'_> {
+    fn type_ref(&self) -> TypeRef {
+        if let Some(array) = self.array {
+            return TypeRef {
+                kind: TypeKind::Array(Box::new(array.type_fields().type_ref())),
+                required: array.required.unwrap_or_default(),
+            };
+        }
+
+        if let Some(map) = self.map {
+            let key = map
+                .key
+                .as_ref()
+                .and_then(|key| serde_json::to_value(&key.type_).ok())
+                .and_then(|key| key.as_str().map(String::from))
+                .unwrap_or_default();
+
+            return TypeRef {
+                kind: TypeKind::Map {
+                    key,
+                    value: Box::new(map.type_fields().type_ref()),
+                },
+                required: map.required.unwrap_or_default(),
+            };
+        }
+
+        let (kind, required) = if let Some(scalar) = self.scalar {
+            (TypeKind::Scalar(scalar.type_.clone()), scalar.required)
+        } else if let Some(object) = self.object {
+            (TypeKind::Object(object.type_.clone()), object.required)
+        } else if let Some(enum_) = self.enum_ {
+            let name = enum_["type"].as_str().unwrap_or(self.type_);
+            (
+                TypeKind::Enum(name.to_string()),
+                enum_["required"].as_bool(),
+            )
+        } else if let Some(unresolved) = self.unresolved_object_or_enum {
+            (
+                TypeKind::Unresolved(unresolved.type_.clone()),
+                unresolved.required,
+            )
+        } else {
+            (TypeKind::Unresolved(self.type_.to_string()), self.required)
+        };
+
+        TypeRef {
+            kind,
+            required: required.unwrap_or_default(),
+        }
+    }
+}
+
+macro_rules! impl_type_ref {
+    ($($definition:ty),*) => {$(
+        impl $definition {
+            fn type_fields(&self) -> TypeFields<'_> {
+                TypeFields {
+                    type_: &self.type_,
+                    required: self.required,
+                    array: self.array.as_deref(),
+                    map: self.map.as_deref(),
+                    scalar: self.scalar.as_deref(),
+                    object: self.object.as_deref(),
+                    enum_: self.enum_.as_ref(),
+                    unresolved_object_or_enum: self.unresolved_object_or_enum.as_deref(),
+                }
+            }
+        }
+    )*};
+}
+
+impl_type_ref!(
+    AnyDefinition,
+    ArrayDefinition,
+    MapDefinition,
+    PropertyDefinition
+);
+
+impl PropertyDefinition {
+    /// Returns the type of the property.
+    pub fn type_ref(&self) -> TypeRef {
+        self.type_fields().type_ref()
+    }
+}
+
+impl AnyDefinition {
+    /// Returns the type of the value.
+    pub fn type_ref(&self) -> TypeRef {
+        self.type_fields().type_ref()
+    }
+}
+
+impl MethodDefinition {
+    /// Returns the signature of the method, e.g. `get(url: String!, request: Request): Response`.
+    pub fn signature(&self) -> String {
+        let arguments = self
+            .arguments
+            .iter()
+            .flatten()
+            .map(|argument| {
+                format!(
+                    "{}: {}",
+                    argument.name.as_deref().unwrap_or_default(),
+                    argument.type_ref()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let name = self.name.as_deref().unwrap_or_default();
+        match &self.return_ {
+            Some(return_) => format!("{name}({arguments}): {}", return_.type_ref()),
+            None => format!("{name}({arguments})"),
+        }
+    }
+}
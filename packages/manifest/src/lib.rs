@@ -1,3 +1,4 @@
+pub mod abi;
 pub mod deserialize;
 pub mod error;
 pub mod get_schemas;
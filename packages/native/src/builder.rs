@@ -6,12 +6,13 @@ use std::{
 use polywrap_client::{
     builder::{ClientConfig, ClientConfigBuilder},
     client::Client,
-    core::uri::Uri,
+    core::{client::CoreClientConfigBuilder, uri::Uri},
 };
 use polywrap_client_default_config::{SystemClientConfig, Web3ClientConfig};
 
 use crate::{
     client::FFIClient,
+    error::FFIError,
     package::FFIWrapPackage,
     resolvers::{
         ffi_resolver::FFIUriResolver,
@@ -191,10 +192,10 @@ impl FFIBuilderConfig {
             .add(Web3ClientConfig::default().into());
     }
 
-    pub fn build(&self) -> Arc<FFIClient> {
+    pub fn build(&self) -> Result<Arc<FFIClient>, FFIError> {
         let config = self.0.lock().unwrap().clone();
-        let client = Arc::new(Client::new(config.into()));
-        Arc::new(FFIClient::new(client))
+        let client = Arc::new(Client::new(config.build()?));
+        Ok(Arc::new(FFIClient::new(client)))
     }
 }
 
@@ -440,7 +441,7 @@ mod test {
         assert!(builder.0.lock().unwrap().wrappers.is_none());
         assert!(builder.0.lock().unwrap().packages.is_some());
     
-        let _ = builder.build();
+        assert!(builder.build().is_ok());
     }
 
     #[test]
@@ -451,6 +452,6 @@ mod test {
         assert!(builder.0.lock().unwrap().interfaces.is_some());
         assert!(builder.0.lock().unwrap().packages.is_some());
 
-        let _ = builder.build();
+        assert!(builder.build().is_ok());
     }
 }
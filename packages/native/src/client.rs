@@ -266,7 +266,7 @@ mod test {
             .add(SystemClientConfig::precompiled().into())
             .add(Web3ClientConfig::default().into());
 
-        let client = Arc::from(Client::new(config.into()));
+        let client = Arc::from(Client::new(config.try_into().unwrap()));
         let ffi_client = FFIClient::new(client.clone());
 
         const SUBINVOKE_WRAP_URI: &str =
@@ -294,7 +294,7 @@ mod test {
         let mut config: ClientConfig = ClientConfig::new();
         config.add_redirect(from_uri.clone(), to_uri.clone());
 
-        let client = Arc::from(Client::new(config.into()));
+        let client = Arc::from(Client::new(config.try_into().unwrap()));
         let ffi_client = FFIClient::new(client.clone());
 
         let uri = ffi_uri_from_string(&from_uri.to_string()).unwrap();
@@ -324,7 +324,7 @@ mod test {
             .add(SystemClientConfig::precompiled().into())
             .add(Web3ClientConfig::default().into());
 
-        let client = Arc::from(Client::new(config.into()));
+        let client = Arc::from(Client::new(config.try_into().unwrap()));
         let ffi_client = FFIClient::new(client.clone());
 
         const SUBINVOKE_WRAP_URI: &str =
@@ -368,7 +368,7 @@ mod test {
         config
             .add_wrapper("wrap://mock/uri".parse().unwrap(), Arc::new(MockWrapper));
 
-        let client = Arc::from(Client::new(config.into()));
+        let client = Arc::from(Client::new(config.try_into().unwrap()));
         let ffi_client = FFIClient::new(client.clone());
 
         const SUBINVOKE_WRAP_URI: &str =
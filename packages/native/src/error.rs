@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use polywrap_client::core::error::Error;
+use polywrap_client::core::error::{ArgsMismatch, Error, ErrorKind, WrapAbortInfo};
 
 #[derive(thiserror::Error, Debug, Clone)]
 pub enum FFIError {
@@ -20,9 +20,23 @@ pub enum FFIError {
         uri: String,
         method: String,
         err: String,
+        sources: Vec<FFIErrorSource>,
     },
     #[error("Error loading wrapper: `{err}`")]
-    LoadWrapperError { uri: String, err: String },
+    LoadWrapperError {
+        uri: String,
+        err: String,
+        sources: Vec<FFIErrorSource>,
+    },
+    #[error("__wrap_abort: {message}\nFile: {file}\nLocation: [{line},{column}]")]
+    WrapAbort {
+        message: String,
+        file: String,
+        line: u32,
+        column: u32,
+        err: Option<String>,
+        sources: Vec<FFIErrorSource>,
+    },
     #[error("WasmWrapper error: `{err}`")]
     WasmWrapperError { err: String },
     #[error("Failed to resolve wrapper: `{err}`")]
@@ -41,10 +55,280 @@ pub enum FFIError {
     PluginError { err: String },
     #[error("`{err}`")]
     RuntimeError { err: String },
+    #[error("Invocation timed out: `{err}`")]
+    TimeoutError { err: String },
+    #[error("Invocation cancelled: `{err}`")]
+    CancelledError { err: String },
+    #[error("Resource limit exceeded: `{err}`")]
+    ResourceLimitError { err: String },
+    #[error("Invalid JSON at `{path}`: {err}")]
+    JsonError { path: String, err: String },
+    #[error("Invalid arguments, uri: `{uri}`, method: `{method}`")]
+    ArgsValidationError {
+        uri: String,
+        method: String,
+        mismatches: Vec<FFIArgsMismatch>,
+    },
     #[error("`{err}`")]
     OtherError { err: String },
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FFIErrorKind {
+    UriParse,
+    Redirects,
+    Wrapper,
+    WrapperCreate,
+    Invoke,
+    LoadWrapper,
+    WrapAbort,
+    WasmWrapper,
+    Resolution,
+    UriNotFound,
+    Msgpack,
+    Manifest,
+    FileRead,
+    Resolver,
+    Plugin,
+    Runtime,
+    Timeout,
+    Cancelled,
+    ResourceLimit,
+    Json,
+    ArgsValidation,
+    Other,
+}
+
+impl From<ErrorKind> for FFIErrorKind {
+    fn from(value: ErrorKind) -> Self {
+        match value {
+            ErrorKind::UriParse => FFIErrorKind::UriParse,
+            ErrorKind::Redirects => FFIErrorKind::Redirects,
+            ErrorKind::Wrapper => FFIErrorKind::Wrapper,
+            ErrorKind::WrapperCreate => FFIErrorKind::WrapperCreate,
+            ErrorKind::Invoke => FFIErrorKind::Invoke,
+            ErrorKind::LoadWrapper => FFIErrorKind::LoadWrapper,
+            ErrorKind::WrapAbort => FFIErrorKind::WrapAbort,
+            ErrorKind::WasmWrapper => FFIErrorKind::WasmWrapper,
+            ErrorKind::Resolution => FFIErrorKind::Resolution,
+            ErrorKind::UriNotFound => FFIErrorKind::UriNotFound,
+            ErrorKind::Msgpack => FFIErrorKind::Msgpack,
+            ErrorKind::Manifest => FFIErrorKind::Manifest,
+            ErrorKind::FileRead => FFIErrorKind::FileRead,
+            ErrorKind::Resolver => FFIErrorKind::Resolver,
+            ErrorKind::Plugin => FFIErrorKind::Plugin,
+            ErrorKind::Runtime => FFIErrorKind::Runtime,
+            ErrorKind::Timeout => FFIErrorKind::Timeout,
+            ErrorKind::Cancelled => FFIErrorKind::Cancelled,
+            ErrorKind::ResourceLimit => FFIErrorKind::ResourceLimit,
+            ErrorKind::Json => FFIErrorKind::Json,
+            ErrorKind::ArgsValidation => FFIErrorKind::ArgsValidation,
+            ErrorKind::Other => FFIErrorKind::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FFIWrapAbortInfo {
+    pub message: String,
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl From<WrapAbortInfo> for FFIWrapAbortInfo {
+    fn from(value: WrapAbortInfo) -> Self {
+        FFIWrapAbortInfo {
+            message: value.message,
+            file: value.file,
+            line: value.line,
+            column: value.column,
+        }
+    }
+}
+
+impl From<FFIWrapAbortInfo> for WrapAbortInfo {
+    fn from(value: FFIWrapAbortInfo) -> Self {
+        WrapAbortInfo {
+            message: value.message,
+            file: value.file,
+            line: value.line,
+            column: value.column,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FFIArgsMismatch {
+    pub path: String,
+    pub message: String,
+}
+
+impl From<ArgsMismatch> for FFIArgsMismatch {
+    fn from(value: ArgsMismatch) -> Self {
+        FFIArgsMismatch {
+            path: value.path,
+            message: value.message,
+        }
+    }
+}
+
+impl From<FFIArgsMismatch> for ArgsMismatch {
+    fn from(value: FFIArgsMismatch) -> Self {
+        ArgsMismatch {
+            path: value.path,
+            message: value.message,
+        }
+    }
+}
+
+/// Error along the cause chain of an `FFIError`, without its own cause.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FFIErrorSource {
+    pub kind: FFIErrorKind,
+    /// Message the error was created with, empty for invocation and loading errors.
+    pub message: String,
+    pub uri: Option<String>,
+    pub method: Option<String>,
+    pub abort: Option<FFIWrapAbortInfo>,
+}
+
+impl From<&Error> for FFIErrorSource {
+    fn from(value: &Error) -> Self {
+        let (message, uri, method, abort) = match value {
+            Error::UriParseError(err) => (err.to_string(), None, None, None),
+            Error::RedirectsError(err, _) => (err.clone(), None, None, None),
+            Error::WrapperError { message, .. } => (message.clone(), None, None, None),
+            Error::InvokeError { uri, method, .. } => {
+                (String::new(), Some(uri.clone()), Some(method.clone()), None)
+            }
+            Error::LoadWrapperError { uri, .. } => (String::new(), Some(uri.clone()), None, None),
+            Error::WrapAbort { info, .. } => {
+                (info.message.clone(), None, None, Some(info.clone().into()))
+            }
+            Error::ResolutionError { message, .. } => (message.clone(), None, None, None),
+            Error::MsgpackError(err) => (err.to_string(), None, None, None),
+            Error::JsonError { message, .. } => (message.clone(), None, None, None),
+            Error::ArgsValidationError { uri, method, .. } => {
+                (value.to_string(), Some(uri.clone()), Some(method.clone()), None)
+            }
+            Error::WrapperCreateError(err)
+            | Error::WasmWrapperError(err)
+            | Error::UriNotFoundError(err)
+            | Error::ManifestError(err)
+            | Error::FileReadError(err)
+            | Error::ResolverError(err)
+            | Error::PluginError(err)
+            | Error::RuntimeError(err)
+            | Error::TimeoutError(err)
+            | Error::CancelledError(err)
+            | Error::ResourceLimitError(err)
+            | Error::OtherError(err) => (err.clone(), None, None, None),
+        };
+
+        FFIErrorSource {
+            kind: value.kind().into(),
+            message,
+            uri,
+            method,
+            abort,
+        }
+    }
+}
+
+impl FFIErrorSource {
+    // Rebuilds the error with its cause, which only errors wrapping others keep
+    fn into_error(self, source: Option<Error>) -> Error {
+        let FFIErrorSource {
+            kind,
+            message,
+            uri,
+            method,
+            abort,
+        } = self;
+
+        match kind {
+            FFIErrorKind::UriParse => {
+                Error::UriParseError(polywrap_client::core::uri::ParseError(message))
+            }
+            FFIErrorKind::Redirects => Error::RedirectsError(message, HashMap::new()),
+            FFIErrorKind::Wrapper => Error::WrapperError {
+                message,
+                source: source.map(Box::new),
+            },
+            FFIErrorKind::WrapperCreate => Error::WrapperCreateError(message),
+            FFIErrorKind::Invoke => Error::InvokeError {
+                uri: uri.unwrap_or_default(),
+                method: method.unwrap_or_default(),
+                source: Box::new(source.unwrap_or(Error::OtherError(message))),
+            },
+            FFIErrorKind::LoadWrapper => Error::LoadWrapperError {
+                uri: uri.unwrap_or_default(),
+                source: Box::new(source.unwrap_or(Error::OtherError(message))),
+            },
+            FFIErrorKind::WrapAbort => Error::WrapAbort {
+                info: match abort {
+                    Some(abort) => abort.into(),
+                    None => WrapAbortInfo {
+                        message,
+                        file: String::new(),
+                        line: 0,
+                        column: 0,
+                    },
+                },
+                source: source.map(Box::new),
+            },
+            FFIErrorKind::WasmWrapper => Error::WasmWrapperError(message),
+            FFIErrorKind::Resolution => Error::ResolutionError {
+                message,
+                source: source.map(Box::new),
+            },
+            FFIErrorKind::UriNotFound => Error::UriNotFoundError(message),
+            FFIErrorKind::Msgpack => {
+                Error::MsgpackError(polywrap_msgpack_serde::Error::Message(message))
+            }
+            FFIErrorKind::Manifest => Error::ManifestError(message),
+            FFIErrorKind::FileRead => Error::FileReadError(message),
+            FFIErrorKind::Resolver => Error::ResolverError(message),
+            FFIErrorKind::Plugin => Error::PluginError(message),
+            FFIErrorKind::Runtime => Error::RuntimeError(message),
+            FFIErrorKind::Timeout => Error::TimeoutError(message),
+            FFIErrorKind::Cancelled => Error::CancelledError(message),
+            FFIErrorKind::ResourceLimit => Error::ResourceLimitError(message),
+            FFIErrorKind::Json => Error::JsonError {
+                path: String::new(),
+                message,
+            },
+            FFIErrorKind::ArgsValidation => Error::ArgsValidationError {
+                uri: uri.unwrap_or_default(),
+                method: method.unwrap_or_default(),
+                mismatches: vec![ArgsMismatch {
+                    path: String::new(),
+                    message,
+                }],
+            },
+            FFIErrorKind::Other => Error::OtherError(message),
+        }
+    }
+}
+
+// Lists the error and its causes, from the outermost one
+fn sources_to_ffi(source: &Error) -> Vec<FFIErrorSource> {
+    source.chain().map(FFIErrorSource::from).collect()
+}
+
+// Rebuilds the cause chain from the innermost cause, falling back to the
+// message of the error if its causes weren't provided
+fn source_from_ffi(err: String, sources: Vec<FFIErrorSource>) -> Error {
+    sources
+        .into_iter()
+        .rev()
+        .fold(None, |source, ffi_source| {
+            Some(ffi_source.into_error(source))
+        })
+        .unwrap_or(Error::OtherError(err))
+}
+
 impl From<polywrap_wasm::error::WrapperError> for FFIError {
     fn from(err: polywrap_wasm::error::WrapperError) -> Self {
       FFIError::WrapperError { err: err.to_string() }
@@ -63,23 +347,46 @@ impl From<Error> for FFIError {
                     resolution_stack,
                 }
             }
-            Error::WrapperError(err) => {
-                FFIError::WrapperError { err }
+            Error::WrapperError { message, .. } => {
+                FFIError::WrapperError { err: message }
             }
             Error::WrapperCreateError(err) => {
                 FFIError::WrapperCreateError { err }
             }
-            Error::InvokeError(uri, method, err) => {
-                FFIError::InvokeError { uri, method, err }
+            Error::InvokeError {
+                uri,
+                method,
+                source,
+            } => {
+                FFIError::InvokeError {
+                    uri,
+                    method,
+                    err: source.to_string(),
+                    sources: sources_to_ffi(&source),
+                }
+            }
+            Error::LoadWrapperError { uri, source } => {
+                FFIError::LoadWrapperError {
+                    uri,
+                    err: source.to_string(),
+                    sources: sources_to_ffi(&source),
+                }
             }
-            Error::LoadWrapperError(uri, err) => {
-                FFIError::LoadWrapperError { uri, err }
+            Error::WrapAbort { info, source } => {
+                FFIError::WrapAbort {
+                    message: info.message,
+                    file: info.file,
+                    line: info.line,
+                    column: info.column,
+                    err: source.as_ref().map(|e| e.to_string()),
+                    sources: source.map(|e| sources_to_ffi(&e)).unwrap_or_default(),
+                }
             }
             Error::WasmWrapperError(err) => {
                 FFIError::WasmWrapperError { err }
             }
-            Error::ResolutionError(err) => {
-                FFIError::ResolutionError { err }
+            Error::ResolutionError { message, .. } => {
+                FFIError::ResolutionError { err: message }
             }
             Error::UriNotFoundError(uri) => {
                 FFIError::UriNotFoundError { uri }
@@ -103,6 +410,29 @@ impl From<Error> for FFIError {
             Error::RuntimeError(err) => {
                 FFIError::RuntimeError { err }
             }
+            Error::TimeoutError(err) => {
+                FFIError::TimeoutError { err }
+            }
+            Error::CancelledError(err) => {
+                FFIError::CancelledError { err }
+            }
+            Error::ResourceLimitError(err) => {
+                FFIError::ResourceLimitError { err }
+            }
+            Error::JsonError { path, message } => {
+                FFIError::JsonError { path, err: message }
+            }
+            Error::ArgsValidationError {
+                uri,
+                method,
+                mismatches,
+            } => {
+                FFIError::ArgsValidationError {
+                    uri,
+                    method,
+                    mismatches: mismatches.into_iter().map(Into::into).collect(),
+                }
+            }
             Error::OtherError(err) => FFIError::OtherError { err },
         }
     }
@@ -119,22 +449,52 @@ impl From<FFIError> for Error {
                 resolution_stack,
             } => Error::RedirectsError(err, resolution_stack),
             FFIError::WrapperError { err } => {
-                Error::WrapperError(err)
+                Error::wrapper(err)
             }
             FFIError::WrapperCreateError { err } => {
                 Error::WrapperCreateError(err)
             }
-            FFIError::InvokeError { uri, method, err } => {
-                Error::InvokeError(uri, method, err)
+            FFIError::InvokeError {
+                uri,
+                method,
+                err,
+                sources,
+            } => {
+                Error::InvokeError {
+                    uri,
+                    method,
+                    source: Box::new(source_from_ffi(err, sources)),
+                }
+            }
+            FFIError::LoadWrapperError { uri, err, sources } => {
+                Error::LoadWrapperError {
+                    uri,
+                    source: Box::new(source_from_ffi(err, sources)),
+                }
             }
-            FFIError::LoadWrapperError { uri, err } => {
-                Error::LoadWrapperError(uri, err)
+            FFIError::WrapAbort {
+                message,
+                file,
+                line,
+                column,
+                err,
+                sources,
+            } => {
+                Error::WrapAbort {
+                    info: WrapAbortInfo {
+                        message,
+                        file,
+                        line,
+                        column,
+                    },
+                    source: err.map(|err| Box::new(source_from_ffi(err, sources))),
+                }
             }
             FFIError::WasmWrapperError { err } => {
                 Error::WasmWrapperError(err)
             }
             FFIError::ResolutionError { err } => {
-                Error::ResolutionError(err)
+                Error::resolution(err)
             }
             FFIError::UriNotFoundError { uri } => {
                 Error::UriNotFoundError(uri)
@@ -156,6 +516,29 @@ impl From<FFIError> for Error {
             FFIError::RuntimeError { err } => {
                 Error::RuntimeError(err)
             }
+            FFIError::TimeoutError { err } => {
+                Error::TimeoutError(err)
+            }
+            FFIError::CancelledError { err } => {
+                Error::CancelledError(err)
+            }
+            FFIError::ResourceLimitError { err } => {
+                Error::ResourceLimitError(err)
+            }
+            FFIError::JsonError { path, err } => {
+                Error::JsonError { path, message: err }
+            }
+            FFIError::ArgsValidationError {
+                uri,
+                method,
+                mismatches,
+            } => {
+                Error::ArgsValidationError {
+                    uri,
+                    method,
+                    mismatches: mismatches.into_iter().map(Into::into).collect(),
+                }
+            }
             FFIError::OtherError { err } => Error::OtherError(err),
         }
     }
@@ -166,3 +549,66 @@ impl From<uniffi::UnexpectedUniFFICallbackError> for FFIError {
         Self::OtherError { err: e.reason }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use polywrap_client::core::error::{Error, ErrorKind, WrapAbortInfo};
+
+    use super::{FFIError, FFIErrorKind};
+
+    fn abort_info() -> WrapAbortInfo {
+        WrapAbortInfo {
+            message: "subinvocation failed".to_string(),
+            file: "src/lib.rs".to_string(),
+            line: 4,
+            column: 2,
+        }
+    }
+
+    #[test]
+    fn ffi_error_keeps_source_chain() {
+        let error = Error::InvokeError {
+            uri: "wrap://mock/parent".to_string(),
+            method: "foo".to_string(),
+            source: Box::new(Error::WrapAbort {
+                info: abort_info(),
+                source: Some(Box::new(Error::InvokeError {
+                    uri: "wrap://mock/child".to_string(),
+                    method: "bar".to_string(),
+                    source: Box::new(Error::TimeoutError("bar".to_string())),
+                })),
+            }),
+        };
+
+        let ffi_error = FFIError::from(error.clone());
+        match &ffi_error {
+            FFIError::InvokeError { sources, .. } => {
+                let kinds: Vec<_> = sources.iter().map(|source| source.kind).collect();
+                assert_eq!(
+                    kinds,
+                    vec![
+                        FFIErrorKind::WrapAbort,
+                        FFIErrorKind::Invoke,
+                        FFIErrorKind::Timeout
+                    ]
+                );
+                assert_eq!(sources[0].abort, Some(abort_info().into()));
+                assert_eq!(sources[1].uri.as_deref(), Some("wrap://mock/child"));
+            }
+            _ => panic!("Expected InvokeError, got: {ffi_error:?}"),
+        }
+
+        let rebuilt_error = Error::from(ffi_error);
+        assert_eq!(rebuilt_error.to_string(), error.to_string());
+        assert_eq!(
+            rebuilt_error.chain().map(Error::kind).collect::<Vec<_>>(),
+            vec![
+                ErrorKind::Invoke,
+                ErrorKind::WrapAbort,
+                ErrorKind::Invoke,
+                ErrorKind::Timeout
+            ]
+        );
+        assert_eq!(rebuilt_error.abort_info(), Some(&abort_info()));
+    }
+}
@@ -1,9 +1,9 @@
 use std::{fmt::Debug, sync::Arc};
 
 use crate::{
-    error::FFIError,
+    error::{FFIError, FFIErrorKind, FFIErrorSource},
     invoker::FFIInvoker,
-    wrapper::{FFIWrapper, IFFIWrapper},
+    wrapper::{FFIEncoding, FFIWrapper, IFFIWrapper},
 };
 
 #[derive(Debug)]
@@ -26,10 +26,26 @@ impl IFFIWrapper for MockWrapper {
                 uri: "mock/ffi-wrap".to_string(),
                 method: "error_method".to_string(),
                 err: "error from mock ffi wrapper".to_string(),
+                sources: vec![FFIErrorSource {
+                    kind: FFIErrorKind::Wrapper,
+                    message: "error from mock ffi wrapper".to_string(),
+                    uri: None,
+                    method: None,
+                    abort: None,
+                }],
             }),
             _ => Ok(vec![194]),
         }
     }
+
+    fn get_file(&self, path: String, _: Option<FFIEncoding>) -> Result<Vec<u8>, FFIError> {
+        match path.as_str() {
+            "README.md" => Ok(b"# Mock".to_vec()),
+            _ => Err(FFIError::WrapperError {
+                err: format!("File was not found: {path}"),
+            }),
+        }
+    }
 }
 
 impl IFFIWrapper for DifferentMockWrapper {
@@ -47,6 +63,10 @@ impl IFFIWrapper for DifferentMockWrapper {
             Ok(vec![194])
         }
     }
+
+    fn get_file(&self, _: String, _: Option<FFIEncoding>) -> Result<Vec<u8>, FFIError> {
+        Ok(vec![1])
+    }
 }
 
 pub fn get_mock_ffi_wrapper() -> FFIWrapper {
@@ -4,10 +4,24 @@ use crate::{
     error::FFIError,
     wrapper::{IFFIWrapper, FFIWrapper},
 };
-use polywrap_client::core::{error::Error, package::WrapPackage, wrapper::Wrapper};
+use polywrap_client::{
+    core::{
+        error::Error,
+        package::{GetManifestOptions, WrapPackage},
+        wrapper::Wrapper,
+    },
+    wrap_manifest::{
+        deserialize::{deserialize_wrap_manifest, DeserializeManifestOptions},
+        versions::WrapManifest,
+    },
+};
+use polywrap_msgpack_serde::to_vec;
 
 pub trait IFFIWrapPackage: Debug + Send + Sync {
     fn create_wrapper(&self) -> Result<Box<dyn IFFIWrapper>, FFIError>;
+
+    /// Returns the msgpack encoded manifest, as in a `wrap.info` file.
+    fn get_manifest(&self) -> Result<Vec<u8>, FFIError>;
 }
 
 impl IFFIWrapPackage for Arc<dyn WrapPackage> {
@@ -16,6 +30,11 @@ impl IFFIWrapPackage for Arc<dyn WrapPackage> {
         let wrapper = WrapPackage::create_wrapper(arc_self.as_ref())?;
         Ok(Box::new(wrapper))
     }
+
+    fn get_manifest(&self) -> Result<Vec<u8>, FFIError> {
+        let manifest = WrapPackage::get_manifest(self.as_ref(), None)?;
+        to_vec(&manifest).map_err(|e| FFIError::MsgpackError { err: e.to_string() })
+    }
 }
 
 #[derive(Debug)]
@@ -29,6 +48,10 @@ impl FFIWrapPackage {
   pub fn create_wrapper(&self) -> Result<Arc<FFIWrapper>, FFIError> {
     Ok(Arc::new(FFIWrapper(self.0.create_wrapper()?)))
   }
+
+  pub fn get_manifest(&self) -> Result<Vec<u8>, FFIError> {
+    self.0.get_manifest()
+  }
 }
 
 impl WrapPackage for FFIWrapPackage {
@@ -37,11 +60,14 @@ impl WrapPackage for FFIWrapPackage {
         Ok(Arc::new(FFIWrapper(ffi_wrapper)))
     }
 
-    fn get_manifest(
-        &self,
-        _: Option<&polywrap_client::core::package::GetManifestOptions>,
-    ) -> Result<polywrap_client::wrap_manifest::versions::WrapManifest, Error> {
-        unimplemented!("get_manifest is not implemented for IFFIWrapPackage")
+    fn get_manifest(&self, options: Option<&GetManifestOptions>) -> Result<WrapManifest, Error> {
+        let manifest = self.0.get_manifest()?;
+        let options = options.map(|options| DeserializeManifestOptions {
+            no_validate: options.no_validate,
+            ext_schema: None,
+        });
+
+        deserialize_wrap_manifest(&manifest, options).map_err(|e| Error::ManifestError(e.to_string()))
     }
 }
 
@@ -49,12 +75,14 @@ impl WrapPackage for FFIWrapPackage {
 mod test {
     use std::sync::Arc;
 
+    use polywrap_client::core::package::{GetManifestOptions, WrapPackage};
     use polywrap_msgpack_serde::from_slice;
-    use polywrap_tests_utils::mocks::{get_mock_invoker, get_mock_package};
+    use polywrap_plugin::package::PluginPackage;
+    use polywrap_tests_utils::mocks::{get_mock_invoker, get_mock_package, MemoryStoragePlugin};
 
     use crate::{invoker::FFIInvoker, wrapper::FFIWrapper};
 
-    use super::IFFIWrapPackage;
+    use super::{FFIWrapPackage, IFFIWrapPackage};
 
     fn get_mocks() -> (Box<dyn IFFIWrapPackage>, FFIInvoker) {
         (Box::new(get_mock_package()), FFIInvoker(get_mock_invoker()))
@@ -68,4 +96,16 @@ mod test {
             ffi_wrapper.invoke("foo", None, None, Arc::new(ffi_invoker));
         assert!(from_slice::<bool>(&response.unwrap()).unwrap());
     }
+
+    #[test]
+    fn test_ffi_package_manifest() {
+        let package: Arc<dyn WrapPackage> =
+            Arc::new(PluginPackage::from(MemoryStoragePlugin { value: 1 }));
+        let expected_manifest = WrapPackage::get_manifest(package.as_ref(), None).unwrap();
+
+        let ffi_package = FFIWrapPackage(Box::new(package));
+        let options = GetManifestOptions { no_validate: true };
+        let manifest = WrapPackage::get_manifest(&ffi_package, Some(&options)).unwrap();
+        assert_eq!(manifest, expected_manifest);
+    }
 }
@@ -3,11 +3,35 @@ use std::{fmt::Debug, sync::Arc};
 use polywrap_client::core::{
     error::Error,
     invoker::Invoker,
-    wrapper::{GetFileOptions, Wrapper},
+    wrapper::{Encoding, GetFileOptions, Wrapper},
 };
 
 use crate::{error::FFIError, invoker::FFIInvoker};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FFIEncoding {
+    Base64,
+    UTF8,
+}
+
+impl From<&Encoding> for FFIEncoding {
+    fn from(value: &Encoding) -> Self {
+        match value {
+            Encoding::Base64 => FFIEncoding::Base64,
+            Encoding::UTF8 => FFIEncoding::UTF8,
+        }
+    }
+}
+
+impl From<FFIEncoding> for Encoding {
+    fn from(value: FFIEncoding) -> Self {
+        match value {
+            FFIEncoding::Base64 => Encoding::Base64,
+            FFIEncoding::UTF8 => Encoding::UTF8,
+        }
+    }
+}
+
 pub trait IFFIWrapper: Debug + Send + Sync {
     fn invoke(
         &self,
@@ -16,6 +40,8 @@ pub trait IFFIWrapper: Debug + Send + Sync {
         env: Option<Vec<u8>>,
         invoker: Arc<FFIInvoker>,
     ) -> Result<Vec<u8>, FFIError>;
+
+    fn get_file(&self, path: String, encoding: Option<FFIEncoding>) -> Result<Vec<u8>, FFIError>;
 }
 
 impl IFFIWrapper for Arc<dyn Wrapper> {
@@ -36,6 +62,16 @@ impl IFFIWrapper for Arc<dyn Wrapper> {
             invoker.0.clone(),
         )?)
     }
+
+    fn get_file(&self, path: String, encoding: Option<FFIEncoding>) -> Result<Vec<u8>, FFIError> {
+        Ok(Wrapper::get_file(
+            self.as_ref(),
+            &GetFileOptions {
+                path,
+                encoding: encoding.map(Into::into),
+            },
+        )?)
+    }
 }
 
 #[derive(Debug)]
@@ -55,6 +91,14 @@ impl FFIWrapper {
     ) -> Result<Vec<u8>, FFIError> {
         self.0.invoke(method.to_string(), args, env, invoker)
     }
+
+    pub fn get_file(
+        &self,
+        path: &str,
+        encoding: Option<FFIEncoding>,
+    ) -> Result<Vec<u8>, FFIError> {
+        self.0.get_file(path.to_string(), encoding)
+    }
 }
 
 impl Wrapper for FFIWrapper {
@@ -73,8 +117,13 @@ impl Wrapper for FFIWrapper {
             .map_err(|e| e.into())
     }
 
-    fn get_file(&self, _: &GetFileOptions) -> Result<Vec<u8>, Error> {
-        unimplemented!("FFI Wrapper does not implement get_file")
+    fn get_file(&self, options: &GetFileOptions) -> Result<Vec<u8>, Error> {
+        self.0
+            .get_file(
+                options.path.clone(),
+                options.encoding.as_ref().map(Into::into),
+            )
+            .map_err(|e| e.into())
     }
 }
 
@@ -82,12 +131,17 @@ impl Wrapper for FFIWrapper {
 mod test {
     use std::sync::Arc;
 
-    use polywrap_client::core::{error::Error, wrapper::Wrapper};
+    use polywrap_client::core::{
+        error::{Error, ErrorKind},
+        wrapper::{GetFileOptions, Wrapper},
+    };
     use polywrap_msgpack_serde::from_slice;
     use polywrap_tests_utils::mocks::get_mock_invoker;
 
     use crate::{
-        error::FFIError, invoker::FFIInvoker, mocks::wrapper::get_mock_ffi_wrapper,
+        error::{FFIError, FFIErrorKind},
+        invoker::FFIInvoker,
+        mocks::wrapper::get_mock_ffi_wrapper,
         wrapper::FFIWrapper,
     };
 
@@ -110,10 +164,16 @@ mod test {
         assert!(response.is_err());
         let error = response.unwrap_err();
         match error {
-            FFIError::InvokeError { uri, method, err } => {
+            FFIError::InvokeError {
+                uri,
+                method,
+                err,
+                sources,
+            } => {
                 assert_eq!(uri, "mock/ffi-wrap");
                 assert_eq!(method, "error_method");
                 assert_eq!(err, "error from mock ffi wrapper");
+                assert_eq!(sources[0].kind, FFIErrorKind::Wrapper);
             }
             _ => panic!("Unexpected error type received"),
         }
@@ -133,12 +193,32 @@ mod test {
         assert!(response.is_err());
         let error = response.unwrap_err();
         match error {
-            Error::InvokeError(uri, method, err) => {
+            Error::InvokeError {
+                uri,
+                method,
+                source,
+            } => {
                 assert_eq!(uri, "mock/ffi-wrap");
                 assert_eq!(method, "error_method");
-                assert_eq!(err, "error from mock ffi wrapper");
+                assert_eq!(source.to_string(), "`error from mock ffi wrapper`");
+                assert_eq!(source.kind(), ErrorKind::Wrapper);
             }
             _ => panic!("Unexpected error type received"),
         }
     }
+
+    #[test]
+    fn get_file_passing_ffi_wrapper() {
+        let (ffi_wrapper, _) = get_mocks();
+        assert_eq!(ffi_wrapper.get_file("README.md", None).unwrap(), b"# Mock");
+
+        let response = Wrapper::get_file(
+            &ffi_wrapper,
+            &GetFileOptions {
+                path: "icon.png".to_string(),
+                encoding: None,
+            },
+        );
+        assert_eq!(response.unwrap_err().kind(), ErrorKind::Wrapper);
+    }
 }
@@ -0,0 +1,25 @@
+use polywrap_plugin_codegen::{generate_with_options, read_manifest, GenerateOptions};
+
+fn main() {
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+    let options = GenerateOptions {
+        concurrent: args.iter().any(|arg| arg == "--concurrent"),
+        async_methods: args.iter().any(|arg| arg == "--async"),
+    };
+    args.retain(|arg| arg != "--concurrent" && arg != "--async");
+
+    let [manifest_path, out_dir] = args.as_slice() else {
+        eprintln!(
+            "Usage: polywrap-plugin-codegen [--concurrent] [--async] <wrap.info | manifest.json> <out dir>"
+        );
+        std::process::exit(1);
+    };
+
+    let result = read_manifest(manifest_path)
+        .and_then(|manifest| generate_with_options(&manifest, &options))
+        .and_then(|files| files.write(out_dir));
+    if let Err(e) = result {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+}
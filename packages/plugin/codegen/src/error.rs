@@ -0,0 +1,9 @@
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("IO error: `{0}`")]
+    IoError(#[from] std::io::Error),
+    #[error("Manifest error: `{0}`")]
+    ManifestError(#[from] wrap_manifest_schemas::error::Error),
+    #[error("Unsupported ABI: `{0}`")]
+    AbiError(String),
+}
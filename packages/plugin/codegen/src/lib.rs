@@ -0,0 +1,103 @@
+//! Generator of the `wrap` folder of plugins: the `Module` trait implemented with
+//! `#[plugin_impl]`, the `Args*` structs of its methods, the types of the ABI and the
+//! embedded `get_manifest()`.
+//!
+//! It can be run from the build script of a plugin:
+//!
+//! ```no_run
+//! polywrap_plugin_codegen::generate_plugin_wrap("wrap.info", "src/wrap").unwrap();
+//! ```
+//!
+//! or with the `polywrap-plugin-codegen [--concurrent] [--async] <manifest> <out dir>` binary.
+
+use std::path::Path;
+
+use wrap_manifest_schemas::{
+    abi::Abi, deserialize::deserialize_wrap_manifest, versions::WrapManifest,
+};
+
+pub mod error;
+mod naming;
+mod render;
+
+pub use error::Error;
+
+/// Contents of the files of the `wrap` folder of a plugin.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PluginWrapFiles {
+    pub mod_rs: String,
+    pub module_rs: String,
+    pub types_rs: String,
+    pub wrap_info_rs: String,
+}
+
+impl PluginWrapFiles {
+    /// Writes the files into the directory, creating it if it doesn't exist.
+    pub fn write(&self, dir: impl AsRef<Path>) -> Result<(), Error> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join("mod.rs"), &self.mod_rs)?;
+        std::fs::write(dir.join("module.rs"), &self.module_rs)?;
+        std::fs::write(dir.join("types.rs"), &self.types_rs)?;
+        std::fs::write(dir.join("wrap.info.rs"), &self.wrap_info_rs)?;
+        Ok(())
+    }
+}
+
+/// Options of the generation of the `wrap` folder.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GenerateOptions {
+    /// Generates a `Module` trait whose methods take `&self`, for plugins implemented with
+    /// `#[plugin_impl(concurrent)]`.
+    pub concurrent: bool,
+    /// Generates a `Module` trait of `async fn` methods, implemented in an impl also
+    /// annotated with `#[async_trait]`.
+    pub async_methods: bool,
+}
+
+/// Generates the `wrap` folder of a plugin implementing the manifest.
+pub fn generate(manifest: &WrapManifest) -> Result<PluginWrapFiles, Error> {
+    generate_with_options(manifest, &GenerateOptions::default())
+}
+
+/// Generates the `wrap` folder of a plugin implementing the manifest with the options.
+pub fn generate_with_options(
+    manifest: &WrapManifest,
+    options: &GenerateOptions,
+) -> Result<PluginWrapFiles, Error> {
+    let abi = Abi::try_from(&manifest.abi)?;
+
+    Ok(PluginWrapFiles {
+        mod_rs: render::mod_rs(),
+        module_rs: render::module_rs(&abi, options)?,
+        types_rs: render::types_rs(&abi)?,
+        wrap_info_rs: render::wrap_info_rs(manifest)?,
+    })
+}
+
+/// Reads a manifest, either a msgpack encoded `wrap.info` or, if its extension is `.json`,
+/// a JSON one.
+pub fn read_manifest(path: impl AsRef<Path>) -> Result<WrapManifest, Error> {
+    let path = path.as_ref();
+    let manifest = std::fs::read(path)?;
+
+    if path
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        serde_json::from_slice(&manifest)
+            .map_err(|e| Error::ManifestError(wrap_manifest_schemas::error::Error::from(e)))
+    } else {
+        Ok(deserialize_wrap_manifest(&manifest, None)?)
+    }
+}
+
+/// Generates the `wrap` folder of a plugin implementing the manifest at `manifest_path`
+/// into `out_dir`.
+pub fn generate_plugin_wrap(
+    manifest_path: impl AsRef<Path>,
+    out_dir: impl AsRef<Path>,
+) -> Result<(), Error> {
+    let manifest = read_manifest(manifest_path)?;
+    generate(&manifest)?.write(out_dir)
+}
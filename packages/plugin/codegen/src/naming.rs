@@ -0,0 +1,43 @@
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Converts a camelCase ABI name into the snake_case name `#[plugin_impl]` maps back to it,
+/// e.g. `readFileAsString` into `read_file_as_string`.
+pub fn to_lower(name: &str) -> String {
+    let mut lower = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            lower.push('_');
+        }
+        lower.extend(c.to_lowercase());
+    }
+    lower
+}
+
+/// Converts an ABI name into PascalCase, dropping the `_` separating namespaces from types,
+/// e.g. `Http_Request` into `HttpRequest` or `readFile` into `ReadFile`.
+pub fn to_upper(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Prefixes the name with `_` if it's a keyword.
+pub fn detect_keyword(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("_{name}")
+    } else {
+        name.to_string()
+    }
+}
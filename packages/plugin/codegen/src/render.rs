@@ -0,0 +1,388 @@
+use std::fmt::Write;
+
+use wrap_manifest_schemas::{
+    abi::{Abi, TypeKind, TypeRef},
+    versions::{ImportedModuleDefinition, MethodDefinition, PropertyDefinition, WrapManifest},
+};
+
+use crate::{
+    error::Error,
+    naming::{detect_keyword, to_lower, to_upper},
+    GenerateOptions,
+};
+
+const NOTE: &str = "/// NOTE: This is an auto-generated file.
+///       All modifications will be overwritten.
+";
+
+pub fn mod_rs() -> String {
+    format!(
+        "{NOTE}
+pub mod types;
+#[path = \"wrap.info.rs\"]
+pub mod wrap_info;
+pub mod module;"
+    )
+}
+
+pub fn wrap_info_rs(manifest: &WrapManifest) -> Result<String, Error> {
+    let abi = serde_json::to_string_pretty(&manifest.abi)
+        .map_err(wrap_manifest_schemas::error::Error::from)?;
+
+    Ok(format!(
+        "{NOTE}use polywrap_plugin::*;
+
+pub fn get_manifest() -> WrapManifest {{
+  WrapManifest {{
+    name: {:?}.to_string(),
+    type_: \"plugin\".to_string(),
+    version: {:?}.to_string(),
+    abi: from_value::<WrapManifestAbi>(json!({abi})).unwrap()
+  }}
+}}
+",
+        manifest.name, manifest.version
+    ))
+}
+
+pub fn types_rs(abi: &Abi) -> Result<String, Error> {
+    let mut env = String::new();
+    if let Some(env_type) = &abi.env {
+        env += &struct_item(None, &env_type.type_, env_type.properties.as_deref())?;
+    }
+
+    let mut objects = String::new();
+    for object in &abi.objects {
+        objects += &struct_item(None, &object.type_, object.properties.as_deref())?;
+    }
+
+    let mut enums = String::new();
+    for enum_type in &abi.enums {
+        enums += &enum_item(None, &enum_type.type_, &enum_type.constants);
+    }
+
+    let mut imported_objects = String::new();
+    for object in &abi.imported_objects {
+        imported_objects += &struct_item(
+            Some(&object.uri),
+            &object.type_,
+            object.properties.as_deref(),
+        )?;
+    }
+
+    let mut imported_envs = String::new();
+    for env_type in &abi.imported_envs {
+        imported_envs += &struct_item(
+            Some(&env_type.uri),
+            &env_type.type_,
+            env_type.properties.as_deref(),
+        )?;
+    }
+
+    let mut imported_enums = String::new();
+    for enum_type in &abi.imported_enums {
+        imported_enums += &enum_item(Some(&enum_type.uri), &enum_type.type_, &enum_type.constants);
+    }
+
+    let mut imported_modules = String::new();
+    for module in &abi.imported_modules {
+        imported_modules += &imported_module(module)?;
+    }
+
+    Ok(format!(
+        "#![allow(unused_imports)]
+#![allow(non_camel_case_types)]
+
+// NOTE: This is an auto-generated file.
+//       All modifications will be overwritten.
+use polywrap_plugin::*;
+use std::collections::BTreeMap;
+use serde::{{Serialize, Deserialize}};
+
+pub type BigInt = String;
+
+// Env START //
+
+{env}// Env END //
+
+// Objects START //
+
+{objects}// Objects END //
+
+// Enums START //
+
+{enums}// Enums END //
+
+// Imported objects START //
+
+{imported_objects}// Imported objects END //
+
+// Imported envs START //
+
+{imported_envs}// Imported envs END //
+
+// Imported enums START //
+
+{imported_enums}// Imported enums END //
+
+// Imported Modules START //
+
+{imported_modules}// Imported Modules END //
+"
+    ))
+}
+
+pub fn module_rs(abi: &Abi, options: &GenerateOptions) -> Result<String, Error> {
+    let (module_trait, receiver) = if options.concurrent {
+        ("ConcurrentPluginModule", "&self")
+    } else {
+        ("PluginModule", "&mut self")
+    };
+    let (async_trait, asyncness) = if options.async_methods {
+        ("#[async_trait]\n", "async ")
+    } else {
+        ("", "")
+    };
+
+    let mut args_structs = String::new();
+    let mut methods = vec![];
+
+    for method in abi.methods() {
+        let name = method.name.as_deref().unwrap_or_default();
+        let args_type = format!("Args{}", to_upper(name));
+        args_structs += &struct_item(None, &args_type, method.arguments.as_deref())?;
+        args_structs += "\n";
+
+        let env = match &method.env {
+            Some(env) if env.required.unwrap_or_default() => ", env: Env",
+            Some(_) => ", env: Option<Env>",
+            None => "",
+        };
+        methods.push(format!(
+            "  {asyncness}fn {}({receiver}, args: &{args_type}, invoker: Arc<dyn Invoker>{env}) -> Result<{}, PluginError>;",
+            detect_keyword(&to_lower(name)),
+            return_type(method)?
+        ));
+    }
+
+    // Otherwise BigInt resolves to the one exported by polywrap_plugin, not the alias of the types
+    let big_int_import = if uses_big_int(abi.methods()) {
+        "use super::types::BigInt;\n"
+    } else {
+        ""
+    };
+
+    Ok(format!(
+        "{NOTE}
+use std::sync::Arc;
+use polywrap_plugin::*;
+use std::collections::BTreeMap;
+use serde::{{Serialize, Deserialize}};
+use super::types::*;
+{big_int_import}
+{args_structs}{async_trait}pub trait Module: {module_trait} {{
+{}
+}}
+",
+        methods.join("\n\n")
+    ))
+}
+
+fn struct_item(
+    uri: Option<&str>,
+    type_name: &str,
+    properties: Option<&[Box<PropertyDefinition>]>,
+) -> Result<String, Error> {
+    let mut item = uri_comment(uri);
+    writeln!(item, "#[derive(Clone, Debug, Deserialize, Serialize)]").unwrap();
+    writeln!(
+        item,
+        "pub struct {} {{",
+        detect_keyword(&to_upper(type_name))
+    )
+    .unwrap();
+    for property in properties.into_iter().flatten() {
+        let name = property.name.as_deref().unwrap_or_default();
+        let field = detect_keyword(&to_lower(name));
+        if field != name {
+            writeln!(item, "    #[serde(rename = \"{name}\")]").unwrap();
+        }
+        writeln!(
+            item,
+            "    pub {field}: {},",
+            rust_type(&property.type_ref())?
+        )
+        .unwrap();
+    }
+    writeln!(item, "}}").unwrap();
+
+    Ok(item)
+}
+
+fn enum_item(uri: Option<&str>, type_name: &str, constants: &Option<Vec<String>>) -> String {
+    let mut item = uri_comment(uri);
+    writeln!(
+        item,
+        "#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]"
+    )
+    .unwrap();
+    writeln!(item, "pub enum {} {{", detect_keyword(&to_upper(type_name))).unwrap();
+    for constant in constants.iter().flatten() {
+        writeln!(item, "    {},", detect_keyword(constant)).unwrap();
+    }
+    writeln!(item, "    _MAX_").unwrap();
+    writeln!(item, "}}").unwrap();
+
+    item
+}
+
+/// Renders the arguments of the methods of an imported module, and the module invoking them.
+/// Interfaces are invoked on the URI of one of their implementations.
+fn imported_module(module: &ImportedModuleDefinition) -> Result<String, Error> {
+    let module_type = detect_keyword(&to_upper(&module.type_));
+    let is_interface = module.is_interface.unwrap_or_default();
+    let methods = module.methods.as_deref().unwrap_or_default();
+
+    let mut item = String::new();
+    let mut method_fns = vec![];
+    for method in methods {
+        let name = method.name.as_deref().unwrap_or_default();
+        let args_type = format!("{module_type}Args{}", to_upper(name));
+        item += &struct_item(Some(&module.uri), &args_type, method.arguments.as_deref())?;
+        item += "\n";
+
+        let (receiver, uri) = if is_interface {
+            ("&self, ", "self.uri.clone()".to_string())
+        } else {
+            ("", format!("Uri::try_from({module_type}::URI).unwrap()"))
+        };
+        method_fns.push(format!(
+            "    pub fn {}({receiver}args: &{args_type}, invoker: std::sync::Arc<dyn Invoker>) -> Result<{}, PluginError> {{
+        let uri = {uri};
+        let args = polywrap_msgpack_serde::to_vec(args)?;
+        let result = invoker
+            .invoke_raw(&uri, {name:?}, Some(&args), None, None)
+            .map_err(|e| PluginError::InvocationError {{
+                exception: e.to_string(),
+            }})?;
+
+        Ok(polywrap_msgpack_serde::from_slice(result.as_slice())?)
+    }}",
+            detect_keyword(&to_lower(name)),
+            return_type(method)?
+        ));
+    }
+
+    item += &uri_comment(Some(&module.uri));
+    if is_interface {
+        write!(
+            item,
+            "pub struct {module_type} {{
+    uri: Uri,
+}}
+
+impl {module_type} {{
+    pub const INTERFACE_URI: &'static str = {:?};
+
+    pub fn new(uri: Uri) -> {module_type} {{
+        {module_type} {{ uri }}
+    }}
+",
+            module.uri
+        )
+        .unwrap();
+    } else {
+        write!(
+            item,
+            "pub struct {module_type};
+
+impl {module_type} {{
+    pub const URI: &'static str = {:?};
+",
+            module.uri
+        )
+        .unwrap();
+    }
+    for method_fn in method_fns {
+        write!(item, "\n{method_fn}\n").unwrap();
+    }
+    writeln!(item, "}}").unwrap();
+
+    Ok(item)
+}
+
+fn uri_comment(uri: Option<&str>) -> String {
+    uri.map(|uri| format!("// URI: \"{uri}\" //\n"))
+        .unwrap_or_default()
+}
+
+fn return_type(method: &MethodDefinition) -> Result<String, Error> {
+    match &method.return_ {
+        Some(return_) => rust_type(&return_.type_ref()),
+        None => Ok("()".to_string()),
+    }
+}
+
+fn uses_big_int(methods: &[Box<MethodDefinition>]) -> bool {
+    fn is_big_int(type_ref: &TypeRef) -> bool {
+        match &type_ref.kind {
+            TypeKind::Scalar(scalar) => scalar == "BigInt",
+            TypeKind::Array(item) => is_big_int(item),
+            TypeKind::Map { key, value } => key == "BigInt" || is_big_int(value),
+            _ => false,
+        }
+    }
+
+    methods.iter().any(|method| {
+        method
+            .arguments
+            .iter()
+            .flatten()
+            .any(|argument| is_big_int(&argument.type_ref()))
+            || method
+                .return_
+                .as_ref()
+                .is_some_and(|return_| is_big_int(&return_.type_ref()))
+    })
+}
+
+fn rust_type(type_ref: &TypeRef) -> Result<String, Error> {
+    let type_ = match &type_ref.kind {
+        TypeKind::Scalar(scalar) => scalar_type(scalar)?.to_string(),
+        TypeKind::Object(name) | TypeKind::Enum(name) | TypeKind::Unresolved(name) => {
+            detect_keyword(&to_upper(name))
+        }
+        TypeKind::Array(item) => format!("Vec<{}>", rust_type(item)?),
+        TypeKind::Map { key, value } => {
+            format!("BTreeMap<{}, {}>", scalar_type(key)?, rust_type(value)?)
+        }
+    };
+
+    Ok(if type_ref.required {
+        type_
+    } else {
+        format!("Option<{type_}>")
+    })
+}
+
+fn scalar_type(scalar: &str) -> Result<&'static str, Error> {
+    Ok(match scalar {
+        "String" => "String",
+        "Boolean" => "bool",
+        "Int" | "Int32" => "i32",
+        "Int8" => "i8",
+        "Int16" => "i16",
+        "UInt" | "UInt32" => "u32",
+        "UInt8" => "u8",
+        "UInt16" => "u16",
+        "Bytes" => "ByteBuf",
+        "BigInt" => "BigInt",
+        "BigNumber" => "BigNumber",
+        "JSON" => "JSONString",
+        scalar => {
+            return Err(Error::AbiError(format!(
+                "unsupported scalar type `{scalar}`"
+            )))
+        }
+    })
+}
@@ -0,0 +1,25 @@
+/// NOTE: This is an auto-generated file.
+///       All modifications will be overwritten.
+
+use std::sync::Arc;
+use polywrap_plugin::*;
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize};
+use super::types::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ArgsEcho {
+    pub item: Item,
+    pub count: Option<i8>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ArgsFindByKind {
+    pub kind: Option<Kind>,
+}
+
+pub trait Module: PluginModule {
+  fn echo(&mut self, args: &ArgsEcho, invoker: Arc<dyn Invoker>) -> Result<Item, PluginError>;
+
+  fn find_by_kind(&mut self, args: &ArgsFindByKind, invoker: Arc<dyn Invoker>, env: Env) -> Result<Option<Vec<Item>>, PluginError>;
+}
@@ -0,0 +1,96 @@
+#![allow(unused_imports)]
+#![allow(non_camel_case_types)]
+
+// NOTE: This is an auto-generated file.
+//       All modifications will be overwritten.
+use polywrap_plugin::*;
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize};
+
+pub type BigInt = String;
+
+// Env START //
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Env {
+    pub prefix: String,
+}
+// Env END //
+
+// Objects START //
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Item {
+    pub id: BigInt,
+    pub amount: Option<BigNumber>,
+    pub data: ByteBuf,
+    pub meta: Option<JSONString>,
+    pub kind: Kind,
+    pub tags: Option<Vec<String>>,
+    pub counts: BTreeMap<String, u8>,
+    #[serde(rename = "type")]
+    pub _type: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<u32>,
+}
+// Objects END //
+
+// Enums START //
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+pub enum Kind {
+    SMALL,
+    LARGE,
+    _MAX_
+}
+// Enums END //
+
+// Imported objects START //
+
+// Imported objects END //
+
+// Imported envs START //
+
+// Imported envs END //
+
+// Imported enums START //
+
+// URI: "wrap://ens/wraps.eth:logger@1.0.0" //
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+pub enum LoggerLogLevel {
+    DEBUG,
+    INFO,
+    WARN,
+    ERROR,
+    _MAX_
+}
+// Imported enums END //
+
+// Imported Modules START //
+
+// URI: "wrap://ens/wraps.eth:logger@1.0.0" //
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LoggerModuleArgsLog {
+    pub level: LoggerLogLevel,
+    pub message: String,
+}
+
+// URI: "wrap://ens/wraps.eth:logger@1.0.0" //
+pub struct LoggerModule;
+
+impl LoggerModule {
+    pub const URI: &'static str = "wrap://ens/wraps.eth:logger@1.0.0";
+
+    pub fn log(args: &LoggerModuleArgsLog, invoker: std::sync::Arc<dyn Invoker>) -> Result<bool, PluginError> {
+        let uri = Uri::try_from(LoggerModule::URI).unwrap();
+        let args = polywrap_msgpack_serde::to_vec(args)?;
+        let result = invoker
+            .invoke_raw(&uri, "log", Some(&args), None, None)
+            .map_err(|e| PluginError::InvocationError {
+                exception: e.to_string(),
+            })?;
+
+        Ok(polywrap_msgpack_serde::from_slice(result.as_slice())?)
+    }
+}
+// Imported Modules END //
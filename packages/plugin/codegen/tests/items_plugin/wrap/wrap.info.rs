@@ -0,0 +1,350 @@
+/// NOTE: This is an auto-generated file.
+///       All modifications will be overwritten.
+use polywrap_plugin::*;
+
+pub fn get_manifest() -> WrapManifest {
+  WrapManifest {
+    name: "items".to_string(),
+    type_: "plugin".to_string(),
+    version: "0.1".to_string(),
+    abi: from_value::<WrapManifestAbi>(json!({
+  "enumTypes": [
+    {
+      "constants": [
+        "SMALL",
+        "LARGE"
+      ],
+      "kind": 8,
+      "type": "Kind"
+    }
+  ],
+  "envType": {
+    "kind": 65536,
+    "properties": [
+      {
+        "kind": 34,
+        "name": "prefix",
+        "required": true,
+        "scalar": {
+          "kind": 4,
+          "name": "prefix",
+          "required": true,
+          "type": "String"
+        },
+        "type": "String"
+      }
+    ],
+    "type": "Env"
+  },
+  "importedEnumTypes": [
+    {
+      "constants": [
+        "DEBUG",
+        "INFO",
+        "WARN",
+        "ERROR"
+      ],
+      "kind": 520,
+      "namespace": "Logger",
+      "nativeType": "LogLevel",
+      "type": "Logger_LogLevel",
+      "uri": "wrap://ens/wraps.eth:logger@1.0.0"
+    }
+  ],
+  "importedModuleTypes": [
+    {
+      "isInterface": false,
+      "kind": 256,
+      "methods": [
+        {
+          "arguments": [
+            {
+              "enum": {
+                "kind": 16384,
+                "name": "level",
+                "required": true,
+                "type": "Logger_LogLevel"
+              },
+              "kind": 34,
+              "name": "level",
+              "required": true,
+              "type": "Logger_LogLevel"
+            },
+            {
+              "kind": 34,
+              "name": "message",
+              "required": true,
+              "scalar": {
+                "kind": 4,
+                "name": "message",
+                "required": true,
+                "type": "String"
+              },
+              "type": "String"
+            }
+          ],
+          "kind": 64,
+          "name": "log",
+          "required": true,
+          "return": {
+            "kind": 34,
+            "name": "log",
+            "required": true,
+            "scalar": {
+              "kind": 4,
+              "name": "log",
+              "required": true,
+              "type": "Boolean"
+            },
+            "type": "Boolean"
+          },
+          "type": "Method"
+        }
+      ],
+      "namespace": "Logger",
+      "nativeType": "Module",
+      "type": "Logger_Module",
+      "uri": "wrap://ens/wraps.eth:logger@1.0.0"
+    }
+  ],
+  "moduleType": {
+    "imports": [
+      {
+        "type": "Logger_Module"
+      },
+      {
+        "type": "Logger_LogLevel"
+      }
+    ],
+    "kind": 128,
+    "methods": [
+      {
+        "arguments": [
+          {
+            "kind": 34,
+            "name": "item",
+            "object": {
+              "kind": 8192,
+              "name": "item",
+              "required": true,
+              "type": "Item"
+            },
+            "required": true,
+            "type": "Item"
+          },
+          {
+            "kind": 34,
+            "name": "count",
+            "scalar": {
+              "kind": 4,
+              "name": "count",
+              "type": "Int8"
+            },
+            "type": "Int8"
+          }
+        ],
+        "comment": "Returns the given item.",
+        "kind": 64,
+        "name": "echo",
+        "required": true,
+        "return": {
+          "kind": 34,
+          "name": "echo",
+          "object": {
+            "kind": 8192,
+            "name": "echo",
+            "required": true,
+            "type": "Item"
+          },
+          "required": true,
+          "type": "Item"
+        },
+        "type": "Method"
+      },
+      {
+        "arguments": [
+          {
+            "enum": {
+              "kind": 16384,
+              "name": "kind",
+              "type": "Kind"
+            },
+            "kind": 34,
+            "name": "kind",
+            "type": "Kind"
+          }
+        ],
+        "env": {
+          "required": true
+        },
+        "kind": 64,
+        "name": "findByKind",
+        "required": true,
+        "return": {
+          "array": {
+            "item": {
+              "kind": 8192,
+              "name": "findByKind",
+              "required": true,
+              "type": "Item"
+            },
+            "kind": 18,
+            "name": "findByKind",
+            "object": {
+              "kind": 8192,
+              "name": "findByKind",
+              "required": true,
+              "type": "Item"
+            },
+            "type": "[Item]"
+          },
+          "kind": 34,
+          "name": "findByKind",
+          "type": "[Item]"
+        },
+        "type": "Method"
+      }
+    ],
+    "type": "Module"
+  },
+  "objectTypes": [
+    {
+      "comment": "An item of the inventory.",
+      "kind": 1,
+      "properties": [
+        {
+          "kind": 34,
+          "name": "id",
+          "required": true,
+          "scalar": {
+            "kind": 4,
+            "name": "id",
+            "required": true,
+            "type": "BigInt"
+          },
+          "type": "BigInt"
+        },
+        {
+          "kind": 34,
+          "name": "amount",
+          "scalar": {
+            "kind": 4,
+            "name": "amount",
+            "type": "BigNumber"
+          },
+          "type": "BigNumber"
+        },
+        {
+          "kind": 34,
+          "name": "data",
+          "required": true,
+          "scalar": {
+            "kind": 4,
+            "name": "data",
+            "required": true,
+            "type": "Bytes"
+          },
+          "type": "Bytes"
+        },
+        {
+          "kind": 34,
+          "name": "meta",
+          "scalar": {
+            "kind": 4,
+            "name": "meta",
+            "type": "JSON"
+          },
+          "type": "JSON"
+        },
+        {
+          "enum": {
+            "kind": 16384,
+            "name": "kind",
+            "required": true,
+            "type": "Kind"
+          },
+          "kind": 34,
+          "name": "kind",
+          "required": true,
+          "type": "Kind"
+        },
+        {
+          "array": {
+            "item": {
+              "kind": 4,
+              "name": "tags",
+              "required": true,
+              "type": "String"
+            },
+            "kind": 18,
+            "name": "tags",
+            "scalar": {
+              "kind": 4,
+              "name": "tags",
+              "required": true,
+              "type": "String"
+            },
+            "type": "[String]"
+          },
+          "kind": 34,
+          "name": "tags",
+          "type": "[String]"
+        },
+        {
+          "kind": 34,
+          "map": {
+            "key": {
+              "kind": 4,
+              "name": "counts",
+              "required": true,
+              "type": "String"
+            },
+            "kind": 262146,
+            "name": "counts",
+            "required": true,
+            "scalar": {
+              "kind": 4,
+              "name": "counts",
+              "required": true,
+              "type": "UInt8"
+            },
+            "type": "Map<String, UInt8>",
+            "value": {
+              "kind": 4,
+              "name": "counts",
+              "required": true,
+              "type": "UInt8"
+            }
+          },
+          "name": "counts",
+          "required": true,
+          "type": "Map<String, UInt8>"
+        },
+        {
+          "kind": 34,
+          "name": "type",
+          "scalar": {
+            "kind": 4,
+            "name": "type",
+            "type": "String"
+          },
+          "type": "String"
+        },
+        {
+          "kind": 34,
+          "name": "createdAt",
+          "scalar": {
+            "kind": 4,
+            "name": "createdAt",
+            "type": "UInt32"
+          },
+          "type": "UInt32"
+        }
+      ],
+      "type": "Item"
+    }
+  ],
+  "version": "0.1"
+})).unwrap()
+  }
+}
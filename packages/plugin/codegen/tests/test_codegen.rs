@@ -0,0 +1,246 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use polywrap_core::{
+    error::Error, interface_implementation::InterfaceImplementations, invoker::Invoker,
+    resolution::uri_resolution_context::UriResolutionContext, uri::Uri,
+};
+use polywrap_msgpack_serde::{from_slice, to_vec};
+use polywrap_plugin::{
+    error::PluginError, implementor::plugin_impl, module::PluginModule, ByteBuf,
+};
+use polywrap_plugin_codegen::{
+    generate, generate_with_options, read_manifest, GenerateOptions, PluginWrapFiles,
+};
+
+#[allow(unused_imports)]
+#[path = "items_plugin/wrap/mod.rs"]
+mod wrap;
+
+use wrap::{
+    module::{ArgsEcho, ArgsFindByKind, Module},
+    types::{Env, Item, Kind, LoggerLogLevel, LoggerModule, LoggerModuleArgsLog},
+    wrap_info::get_manifest,
+};
+
+const ITEMS_MANIFEST_PATH: &str = "../../bindings/tests/fixtures/items/wrap.info";
+
+#[derive(Debug)]
+struct ItemsPlugin;
+
+#[plugin_impl]
+impl Module for ItemsPlugin {
+    fn echo(&mut self, args: &ArgsEcho, _: Arc<dyn Invoker>) -> Result<Item, PluginError> {
+        Ok(args.item.clone())
+    }
+
+    fn find_by_kind(
+        &mut self,
+        args: &ArgsFindByKind,
+        _: Arc<dyn Invoker>,
+        env: Env,
+    ) -> Result<Option<Vec<Item>>, PluginError> {
+        Ok(args.kind.map(|kind| vec![get_item(kind, &env.prefix)]))
+    }
+}
+
+/// Answers every invocation with `true`, and records their methods.
+#[derive(Default)]
+struct MockInvoker {
+    methods: Mutex<Vec<String>>,
+}
+
+impl Invoker for MockInvoker {
+    fn invoke_raw(
+        &self,
+        _: &Uri,
+        method: &str,
+        _: Option<&[u8]>,
+        _: Option<&[u8]>,
+        _: Option<Arc<Mutex<UriResolutionContext>>>,
+    ) -> Result<Vec<u8>, Error> {
+        self.methods.lock().unwrap().push(method.to_string());
+        Ok(to_vec(&true)?)
+    }
+
+    fn get_implementations(&self, _: &Uri) -> Result<Vec<Uri>, Error> {
+        Ok(vec![])
+    }
+
+    fn get_interfaces(&self) -> Option<InterfaceImplementations> {
+        None
+    }
+
+    fn get_env_by_uri(&self, _: &Uri) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+fn get_item(kind: Kind, name: &str) -> Item {
+    Item {
+        id: "1".to_string(),
+        amount: None,
+        data: ByteBuf::from(vec![1, 2]),
+        meta: None,
+        kind,
+        tags: Some(vec![name.to_string()]),
+        counts: BTreeMap::from([("x".to_string(), 1)]),
+        _type: None,
+        created_at: Some(1),
+    }
+}
+
+fn assert_files_eq(files: &PluginWrapFiles, expected: [&str; 4]) {
+    assert_eq!(files.mod_rs, expected[0]);
+    assert_eq!(files.module_rs, expected[1]);
+    assert_eq!(files.types_rs, expected[2]);
+    assert_eq!(files.wrap_info_rs, expected[3]);
+}
+
+#[test]
+fn logger_plugin_wrap_is_reproduced() {
+    let files = generate(&polywrap_logger_plugin::wrap::wrap_info::get_manifest()).unwrap();
+
+    assert_files_eq(
+        &files,
+        [
+            include_str!("../../../plugins/logger/src/wrap/mod.rs"),
+            include_str!("../../../plugins/logger/src/wrap/module.rs"),
+            include_str!("../../../plugins/logger/src/wrap/types.rs"),
+            include_str!("../../../plugins/logger/src/wrap/wrap.info.rs"),
+        ],
+    );
+}
+
+#[test]
+fn fs_plugin_module_is_reproduced() {
+    let files = generate(&polywrap_fs_plugin::wrap::wrap_info::get_manifest()).unwrap();
+
+    assert_eq!(
+        files.module_rs,
+        include_str!("../../../plugins/fs/src/wrap/module.rs")
+    );
+    assert_eq!(
+        files.wrap_info_rs,
+        include_str!("../../../plugins/fs/src/wrap/wrap.info.rs")
+    );
+}
+
+#[test]
+fn items_plugin_wrap_is_up_to_date() {
+    let files = generate(&read_manifest(ITEMS_MANIFEST_PATH).unwrap()).unwrap();
+
+    assert_files_eq(
+        &files,
+        [
+            include_str!("items_plugin/wrap/mod.rs"),
+            include_str!("items_plugin/wrap/module.rs"),
+            include_str!("items_plugin/wrap/types.rs"),
+            include_str!("items_plugin/wrap/wrap.info.rs"),
+        ],
+    );
+}
+
+#[test]
+fn concurrent_module_takes_shared_references() {
+    let manifest = read_manifest(ITEMS_MANIFEST_PATH).unwrap();
+
+    let options = GenerateOptions {
+        concurrent: true,
+        ..Default::default()
+    };
+    let files = generate_with_options(&manifest, &options).unwrap();
+
+    assert!(files
+        .module_rs
+        .contains("pub trait Module: ConcurrentPluginModule {"));
+    assert!(files.module_rs.contains(
+        "  fn echo(&self, args: &ArgsEcho, invoker: Arc<dyn Invoker>) -> Result<Item, PluginError>;"
+    ));
+    assert_eq!(files.types_rs, generate(&manifest).unwrap().types_rs);
+}
+
+#[test]
+fn async_module_has_async_methods() {
+    let manifest = read_manifest(ITEMS_MANIFEST_PATH).unwrap();
+
+    let options = GenerateOptions {
+        concurrent: true,
+        async_methods: true,
+    };
+    let files = generate_with_options(&manifest, &options).unwrap();
+
+    assert!(files
+        .module_rs
+        .contains("#[async_trait]\npub trait Module: ConcurrentPluginModule {"));
+    assert!(files.module_rs.contains(
+        "  async fn echo(&self, args: &ArgsEcho, invoker: Arc<dyn Invoker>) -> Result<Item, PluginError>;"
+    ));
+}
+
+#[test]
+fn json_manifests_are_read() {
+    let manifest = read_manifest(ITEMS_MANIFEST_PATH).unwrap();
+    let path = std::env::temp_dir().join("polywrap_plugin_codegen_items.json");
+    std::fs::write(&path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+
+    let files = generate(&read_manifest(&path).unwrap()).unwrap();
+
+    assert_eq!(files, generate(&manifest).unwrap());
+}
+
+#[test]
+fn generated_module_is_implemented_with_plugin_impl() {
+    let mut plugin = ItemsPlugin;
+    let invoker: Arc<dyn Invoker> = Arc::new(MockInvoker::default());
+    let item = get_item(Kind::LARGE, "apple");
+
+    let args = to_vec(&ArgsEcho {
+        item: item.clone(),
+        count: None,
+    })
+    .unwrap();
+    let result = plugin
+        ._wrap_invoke("echo", &args, None, invoker.clone())
+        .unwrap();
+    assert_eq!(
+        from_slice::<Item>(&result).unwrap().tags,
+        Some(vec!["apple".to_string()])
+    );
+
+    let args = to_vec(&ArgsFindByKind {
+        kind: Some(Kind::SMALL),
+    })
+    .unwrap();
+    let env = to_vec(&Env {
+        prefix: "inventory".to_string(),
+    })
+    .unwrap();
+    let result = plugin
+        ._wrap_invoke("findByKind", &args, Some(&env), invoker)
+        .unwrap();
+    let items = from_slice::<Option<Vec<Item>>>(&result).unwrap().unwrap();
+    assert_eq!(items[0].kind, Kind::SMALL);
+    assert_eq!(items[0].tags, Some(vec!["inventory".to_string()]));
+
+    assert_eq!(get_manifest().name, "items");
+}
+
+#[test]
+fn imported_modules_are_invoked() {
+    let invoker = Arc::new(MockInvoker::default());
+
+    let result = LoggerModule::log(
+        &LoggerModuleArgsLog {
+            level: LoggerLogLevel::INFO,
+            message: "restocked".to_string(),
+        },
+        invoker.clone(),
+    )
+    .unwrap();
+
+    assert!(result);
+    assert_eq!(*invoker.methods.lock().unwrap(), vec!["log".to_string()]);
+}
@@ -2,126 +2,289 @@ use proc_macro::TokenStream;
 use proc_macro2::Ident;
 use quote::quote;
 
-use syn::{parse, parse_macro_input, ItemImpl};
+use syn::{
+    parse_macro_input, punctuated::Punctuated, DeriveInput, FnArg, ImplItem, ItemImpl, Token,
+};
+use wrap_type::{type_name, MethodTypes};
+
+mod wrap_type;
 
 fn snake_case_to_camel_case(s: &str) -> String {
     s.split('_')
         .enumerate()
         .map(|(i, s)| {
-            if i == 0 {
-                s.to_string()
-            } else {
-                s.chars().next().unwrap().to_uppercase().collect::<String>() + &s[1..]
+            let mut chars = s.chars();
+            match chars.next() {
+                Some(first) if i > 0 => first.to_uppercase().chain(chars).collect(),
+                _ => s.to_string(),
             }
         })
         .collect()
 }
 
+/// Method of a plugin, invoked by its name in the ABI.
+struct PluginMethod {
+    ident: Ident,
+    name: String,
+    types: MethodTypes,
+    is_async: bool,
+    /// Whether the method takes `&mut self`, so the plugin can't be invoked concurrently.
+    is_mut: bool,
+}
+
+/// Parses a method of the plugin, which must have the signature
+/// `fn(&self, args: &Args, invoker: Arc<dyn Invoker>, env: Env) -> Result<T, PluginError>`,
+/// where `env` is optional and `Env` may be an `Option`.
+fn plugin_method(item: &ImplItem) -> syn::Result<PluginMethod> {
+    let ImplItem::Method(method) = item else {
+        return Err(syn::Error::new_spanned(
+            item,
+            "`#[plugin_impl]` only supports methods",
+        ));
+    };
+    let sig = &method.sig;
+
+    let is_mut = match sig.receiver() {
+        Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() => {
+            receiver.mutability.is_some()
+        }
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &sig.ident,
+                "expected the method to take `self` by reference",
+            ))
+        }
+    };
+    if !(3..=4).contains(&sig.inputs.len()) {
+        return Err(syn::Error::new(
+            sig.paren_token.span,
+            "expected the method to take `args`, `invoker` and optionally `env`",
+        ));
+    }
+
+    Ok(PluginMethod {
+        ident: sig.ident.clone(),
+        name: snake_case_to_camel_case(&sig.ident.to_string()),
+        types: wrap_type::method_types(sig)?,
+        is_async: sig.asyncness.is_some(),
+        is_mut,
+    })
+}
+
+/// Implements `PluginModule` for the plugin, and its conversions into a `PluginPackage` and
+/// a `PluginWrapper`.
+///
+/// If all the methods of the module take `&self`, it also implements `ConcurrentPluginModule`,
+/// converting into a `ConcurrentPluginPackage` and a `ConcurrentPluginWrapper` whose
+/// invocations run in parallel. `#[plugin_impl(concurrent)]` reports methods taking `&mut self`.
+///
+/// Methods may be `async fn`, in an impl also annotated with `#[async_trait]`. Synchronous
+/// invocations drive them on the executor of the client, see `polywrap_plugin::executor`,
+/// while plugins whose methods take `&self` also implement `AsyncPluginModule` to be awaited
+/// natively by a `ConcurrentPluginWrapper`.
+///
+/// With `#[plugin_impl(abi)]`, the plugin also gets a `get_abi` function returning the ABI of
+/// its module, derived from the types of its methods. Their arguments, results and env must
+/// implement `WrapType`, e.g. with `#[derive(WrapObject)]` and `#[derive(WrapEnum)]`.
 #[proc_macro_attribute]
 pub fn plugin_impl(args: TokenStream, input: TokenStream) -> TokenStream {
     let item_impl = parse_macro_input!(input as ItemImpl);
-    let _ = parse_macro_input!(args as parse::Nothing);
-
-    let struct_ident = item_impl.clone().self_ty;
-
-    let mut method_idents: Vec<(Ident, String, bool, Option<bool>)> = vec![];
+    let options = parse_macro_input!(args with Punctuated::<Ident, Token![,]>::parse_terminated);
 
-    for item in item_impl.clone().items {
-        match item {
-            syn::ImplItem::Method(method) => {
-                let function_ident = &method.sig.ident;
-                let env_is_option = if &method.sig.inputs.len() > &3 {
-                    let env = &method.sig.inputs[3];
-                    let env_str = quote! { #env }.to_string();
+    let mut concurrent = false;
+    let mut abi = false;
+    for option in options {
+        if option == "concurrent" {
+            concurrent = true;
+        } else if option == "abi" {
+            abi = true;
+        } else {
+            return syn::Error::new(option.span(), "expected `concurrent` or `abi`")
+                .to_compile_error()
+                .into();
+        }
+    }
 
-                    Some(env_str.contains("Option <"))
-                } else {
-                    None
-                };
+    let struct_ident = item_impl.clone().self_ty;
 
-                let output_type = match &method.sig.output {
-                    syn::ReturnType::Default => quote! { () },
-                    syn::ReturnType::Type(_, ty) => quote! { #ty },
-                };
-                let output_type = quote! { #output_type }.to_string();
-                let function_ident_str = snake_case_to_camel_case(&function_ident.to_string());
-                let output_is_option = output_type.contains("Option <");
-
-                method_idents.push((
-                    function_ident.clone(),
-                    function_ident_str.clone(),
-                    output_is_option,
-                    env_is_option,
-                ));
-            }
-            _ => panic!("Wrong function signature"),
+    let mut plugin_methods = vec![];
+    let mut errors: Option<syn::Error> = None;
+    for item in &item_impl.items {
+        match plugin_method(item) {
+            Ok(method) => plugin_methods.push(method),
+            Err(e) => match &mut errors {
+                Some(errors) => errors.combine(e),
+                None => errors = Some(e),
+            },
         }
     }
+    if concurrent {
+        for method in plugin_methods.iter().filter(|method| method.is_mut) {
+            let e = syn::Error::new(
+                method.ident.span(),
+                "`#[plugin_impl(concurrent)]` expects the method to take `&self`",
+            );
+            match &mut errors {
+                Some(errors) => errors.combine(e),
+                None => errors = Some(e),
+            }
+        }
+    }
+    if let Some(errors) = errors {
+        return errors.to_compile_error().into();
+    }
 
-    let supported_methods =
-        method_idents
-            .clone()
-            .into_iter()
-            .enumerate()
-            .map(|(_, (_, ident_str, _, _))| {
-                quote! {
-                  #ident_str
-                }
-            });
+    let supported_methods = plugin_methods
+        .iter()
+        .map(|method| {
+            let name = &method.name;
+            quote! {
+              #name
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let has_async_methods = plugin_methods.iter().any(|method| method.is_async);
+
+    let method_calls = plugin_methods
+        .iter()
+        .map(|method| {
+            let name = &method.name;
+            let mut args = quote! {
+              &polywrap_plugin::decode::decode(#name, "args", params)?,
+              invoker
+            };
 
-    let methods = method_idents.into_iter().enumerate().map(
-        |(_, (ident, ident_str, output_is_option, env_is_option))| {
-            let args = if let Some(env_is_option) = env_is_option {
-                let env = if env_is_option {
+            if let Some((_, required)) = &method.types.env {
+                let env = if *required {
                     quote! {
-                      if let Some(e) = env {
-                          Some(polywrap_msgpack_serde::from_slice(&e).unwrap())
-                      } else {
-                          None
-                      }
+                        match env {
+                            Some(e) => polywrap_plugin::decode::decode(#name, "env", e)?,
+                            None => {
+                                return Err(polywrap_plugin::error::PluginError::MissingEnvError(
+                                    #name.to_string(),
+                                ))
+                            }
+                        }
                     }
                 } else {
                     quote! {
-                        if let Some(e) = env {
-                          polywrap_msgpack_serde::from_slice(&e).unwrap()
-                        } else {
-                          panic!("Env must be defined for method '{}'", #ident_str)
+                        match env {
+                            Some(e) => Some(polywrap_plugin::decode::decode(#name, "env", e)?),
+                            None => None,
                         }
                     }
                 };
 
-                quote! {
-                  &polywrap_msgpack_serde::from_slice(&params).unwrap(),
-                  invoker,
+                args = quote! {
+                  #args,
                   #env
+                };
+            }
+
+            (&method.ident, name, args, method.is_async)
+        })
+        .collect::<Vec<_>>();
+
+    let output = quote! {
+        Ok(polywrap_msgpack_serde::to_vec(&result)?)
+    };
+
+    let methods = method_calls
+        .iter()
+        .map(|(ident, ident_str, args, is_async)| {
+            let call = if *is_async {
+                quote! {
+                    polywrap_plugin::executor::block_on(
+                        polywrap_core::invoker::Invoker::get_executor(&*invoker),
+                        self.#ident(
+                          #args
+                        ),
+                    )?
                 }
             } else {
                 quote! {
-                  &polywrap_msgpack_serde::from_slice(&params).unwrap(),
-                  invoker
+                    self.#ident(
+                      #args
+                    )?
                 }
             };
 
-            let output = quote! {
-                Ok(polywrap_msgpack_serde::to_vec(&result)?)
+            quote! {
+                #ident_str => {
+                    let result = #call;
+
+                    #output
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let async_methods = method_calls
+        .iter()
+        .map(|(ident, ident_str, args, is_async)| {
+            let call = if *is_async {
+                quote! {
+                    self.#ident(
+                      #args
+                    ).await?
+                }
+            } else {
+                quote! {
+                    self.#ident(
+                      #args
+                    )?
+                }
             };
 
             quote! {
                 #ident_str => {
-                    let result = self.#ident(
-                      #args
-                    )?;
+                    let result = #call;
 
                     #output
                 }
             }
-        },
-    );
+        });
 
-    let module_impl = quote! {
-        impl polywrap_plugin::module::PluginModule for #struct_ident {
+    // Plugins whose methods all take `&self` are also invoked concurrently, and awaited natively
+    // if they have async methods, while the invocations of the others hold a lock
+    let is_concurrent = plugin_methods.iter().all(|method| !method.is_mut);
+    let is_async_module = is_concurrent && has_async_methods;
+
+    let module_methods = plugin_methods
+        .iter()
+        .map(|method| {
+            let name = &method.name;
+            let env = match &method.types.env {
+                Some((_, required)) => quote! { Some(#required) },
+                None => quote! { None },
+            };
+            let env_type = match &method.types.env {
+                // Types of plugins deriving their ABI may be renamed with serde
+                Some((env, _)) if abi => quote! {
+                    Some(<#env as polywrap_plugin::wrap_type::WrapType>::wrap_type())
+                },
+                Some((env, _)) => {
+                    let env = type_name(env);
+                    quote! { Some(#env.to_string()) }
+                }
+                None => quote! { None },
+            };
+
+            quote! {
+                polywrap_plugin::module::ModuleMethod {
+                    name: #name.to_string(),
+                    env: #env,
+                    env_type: #env_type,
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let module_fns = |receiver| {
+        quote! {
             fn _wrap_invoke(
-                &mut self,
+                #receiver,
                 method_name: &str,
                 params: &[u8],
                 env: Option<&[u8]>,
@@ -133,23 +296,134 @@ pub fn plugin_impl(args: TokenStream, input: TokenStream) -> TokenStream {
                     _ => Err(PluginError::MethodNotFoundError(method_name.to_string())),
                 }
             }
+
+            fn _wrap_methods(&self) -> Option<Vec<polywrap_plugin::module::ModuleMethod>> {
+                Some(vec![#(#module_methods),*])
+            }
         }
     };
 
-    let from_impls = quote! {
-        impl From<#struct_ident> for polywrap_plugin::package::PluginPackage<#struct_ident> {
-            fn from(plugin: #struct_ident) -> polywrap_plugin::package::PluginPackage<#struct_ident> {
-                let plugin_module = Arc::new(std::sync::Mutex::new(plugin));
-                polywrap_plugin::package::PluginPackage::new(plugin_module, get_manifest())
+    let plugin_module_fns = module_fns(quote! { &mut self });
+    let module_impl = quote! {
+        impl polywrap_plugin::module::PluginModule for #struct_ident {
+            #plugin_module_fns
+        }
+    };
+
+    let from_impls = |package: proc_macro2::TokenStream,
+                      wrapper: proc_macro2::TokenStream,
+                      instance: proc_macro2::TokenStream| {
+        quote! {
+            impl From<#struct_ident> for polywrap_plugin::package::#package<#struct_ident> {
+                fn from(plugin: #struct_ident) -> polywrap_plugin::package::#package<#struct_ident> {
+                    let plugin_module = #instance;
+                    polywrap_plugin::package::#package::new(plugin_module, get_manifest())
+                }
+            }
+
+            impl From<#struct_ident> for polywrap_plugin::wrapper::#wrapper<#struct_ident> {
+                fn from(plugin: #struct_ident) -> polywrap_plugin::wrapper::#wrapper<#struct_ident> {
+                    let plugin_module = #instance;
+                    polywrap_plugin::wrapper::#wrapper::new(plugin_module)
+                }
             }
         }
+    };
+
+    let module_from_impls = from_impls(
+        quote! { PluginPackage },
+        quote! { PluginWrapper },
+        quote! { Arc::new(std::sync::Mutex::new(plugin)) },
+    );
 
-        impl From<#struct_ident> for polywrap_plugin::wrapper::PluginWrapper<#struct_ident> {
-            fn from(plugin: #struct_ident) -> polywrap_plugin::wrapper::PluginWrapper<#struct_ident> {
-                let plugin_module = Arc::new(std::sync::Mutex::new(plugin));
-                polywrap_plugin::wrapper::PluginWrapper::new(plugin_module)
+    let concurrent_module_impl = if is_concurrent {
+        let concurrent_module_fns = module_fns(quote! { &self });
+        let as_async = if is_async_module {
+            quote! {
+                fn _as_async(&self) -> Option<&dyn polywrap_plugin::module::AsyncPluginModule> {
+                    Some(self)
+                }
+            }
+        } else {
+            quote! {}
+        };
+        let concurrent_from_impls = from_impls(
+            quote! { ConcurrentPluginPackage },
+            quote! { ConcurrentPluginWrapper },
+            quote! { Arc::new(plugin) },
+        );
+
+        quote! {
+            impl polywrap_plugin::module::ConcurrentPluginModule for #struct_ident {
+                #concurrent_module_fns
+
+                #as_async
+            }
+
+            #concurrent_from_impls
+        }
+    } else {
+        quote! {}
+    };
+
+    let async_module_impl = if is_async_module {
+        quote! {
+            #[polywrap_plugin::async_trait]
+            impl polywrap_plugin::module::AsyncPluginModule for #struct_ident {
+                async fn _wrap_invoke_async(
+                    &self,
+                    method_name: &str,
+                    params: &[u8],
+                    env: Option<&[u8]>,
+                    invoker: Arc<dyn polywrap_core::invoker::Invoker>,
+                ) -> Result<Vec<u8>, polywrap_plugin::error::PluginError> {
+                    match method_name {
+                        #(#async_methods)*
+                        _ => Err(PluginError::MethodNotFoundError(method_name.to_string())),
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let abi_impl = if abi {
+        let env = plugin_methods
+            .iter()
+            .find_map(|method| method.types.env.as_ref())
+            .map(|(env, _)| {
+                quote! {
+                    abi.set_env::<#env>();
+                }
+            });
+        let methods = plugin_methods.iter().map(|method| {
+            let name = &method.name;
+            let args = &method.types.args;
+            let output = &method.types.output;
+            let env = match &method.types.env {
+                Some((_, required)) => quote! { Some(#required) },
+                None => quote! { None },
+            };
+
+            quote! {
+                abi.add_method::<#args, #output>(#name, #env);
+            }
+        });
+
+        quote! {
+            impl #struct_ident {
+                /// Returns the ABI of the plugin, derived from the types of its methods.
+                pub fn get_abi() -> polywrap_plugin::WrapManifestAbi {
+                    let mut abi = polywrap_plugin::wrap_type::AbiBuilder::new();
+                    #(#methods)*
+                    #env
+                    abi.build()
+                }
             }
         }
+    } else {
+        quote! {}
     };
 
     quote! {
@@ -157,7 +431,34 @@ pub fn plugin_impl(args: TokenStream, input: TokenStream) -> TokenStream {
 
         #module_impl
 
-        #from_impls
+        #module_from_impls
+
+        #concurrent_module_impl
+
+        #async_module_impl
+
+        #abi_impl
     }
     .into()
 }
+
+/// Implements `WrapObject` for a struct, describing it as an object of the ABI of a plugin.
+/// Its name and the names of its properties follow `#[serde(rename = "...")]`,
+/// and doc comments become the comments of their definitions.
+#[proc_macro_derive(WrapObject, attributes(serde))]
+pub fn derive_wrap_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    wrap_type::derive_wrap_object(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `WrapEnum` for an enum of unit variants, describing it as an enum of the ABI
+/// of a plugin. The `_MAX_` variant of generated bindings isn't a constant of the enum.
+#[proc_macro_derive(WrapEnum, attributes(serde))]
+pub fn derive_wrap_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    wrap_type::derive_wrap_enum(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,274 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    Attribute, Data, DeriveInput, Fields, FnArg, GenericArgument, Lit, Meta, NestedMeta,
+    PathArguments, ReturnType, Signature, Type,
+};
+
+/// Types of a plugin method, used to decode its arguments and env, and to describe it in the ABI.
+pub struct MethodTypes {
+    pub args: Type,
+    pub output: Type,
+    /// Type of the env and whether it is required, if the method takes one.
+    pub env: Option<(Type, bool)>,
+}
+
+pub fn derive_wrap_object(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "`WrapObject` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`WrapObject` can only be derived for structs",
+            ))
+        }
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let wrap_type = serde_name(&input.attrs).unwrap_or_else(|| ident.to_string());
+    let comment = option_tokens(doc_comment(&input.attrs));
+
+    let mut properties = vec![];
+    let mut field_types = vec![];
+    for field in fields {
+        let name = match serde_name(&field.attrs) {
+            Some(name) => name,
+            None => field.ident.as_ref().unwrap().to_string(),
+        };
+        let field_comment = option_tokens(doc_comment(&field.attrs));
+        let ty = &field.ty;
+
+        properties.push(quote! {
+            polywrap_plugin::wrap_type::property_definition::<#ty>(#name, #field_comment)
+        });
+        field_types.push(ty);
+    }
+
+    Ok(quote! {
+        impl #impl_generics polywrap_plugin::wrap_type::WrapType for #ident #ty_generics #where_clause {
+            fn wrap_type() -> String {
+                #wrap_type.to_string()
+            }
+
+            fn ref_field() -> &'static str {
+                "object"
+            }
+
+            fn type_ref(name: &str, required: bool) -> polywrap_plugin::JSON::Value {
+                polywrap_plugin::wrap_type::object_ref(#wrap_type, name, required)
+            }
+
+            fn add_definitions(abi: &mut polywrap_plugin::wrap_type::AbiBuilder) {
+                abi.add_object::<Self>();
+            }
+        }
+
+        impl #impl_generics polywrap_plugin::wrap_type::WrapObject for #ident #ty_generics #where_clause {
+            fn properties() -> Vec<polywrap_plugin::JSON::Value> {
+                vec![#(#properties),*]
+            }
+
+            fn comment() -> Option<&'static str> {
+                #comment
+            }
+
+            fn add_property_definitions(abi: &mut polywrap_plugin::wrap_type::AbiBuilder) {
+                #(<#field_types as polywrap_plugin::wrap_type::WrapType>::add_definitions(abi);)*
+            }
+        }
+    })
+}
+
+pub fn derive_wrap_enum(input: DeriveInput) -> syn::Result<TokenStream> {
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`WrapEnum` can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut constants = vec![];
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "`WrapEnum` can only be derived for enums with unit variants",
+            ));
+        }
+        // Upper bound of the enums of generated bindings
+        if variant.ident == "_MAX_" {
+            continue;
+        }
+        constants.push(match serde_name(&variant.attrs) {
+            Some(name) => name,
+            None => variant.ident.to_string(),
+        });
+    }
+
+    let ident = &input.ident;
+    let wrap_type = serde_name(&input.attrs).unwrap_or_else(|| ident.to_string());
+    let comment = option_tokens(doc_comment(&input.attrs));
+
+    Ok(quote! {
+        impl polywrap_plugin::wrap_type::WrapType for #ident {
+            fn wrap_type() -> String {
+                #wrap_type.to_string()
+            }
+
+            fn ref_field() -> &'static str {
+                "enum"
+            }
+
+            fn type_ref(name: &str, required: bool) -> polywrap_plugin::JSON::Value {
+                polywrap_plugin::wrap_type::enum_ref(#wrap_type, name, required)
+            }
+
+            fn add_definitions(abi: &mut polywrap_plugin::wrap_type::AbiBuilder) {
+                abi.add_enum::<Self>();
+            }
+        }
+
+        impl polywrap_plugin::wrap_type::WrapEnum for #ident {
+            fn constants() -> Vec<&'static str> {
+                vec![#(#constants),*]
+            }
+
+            fn comment() -> Option<&'static str> {
+                #comment
+            }
+        }
+    })
+}
+
+/// Returns the types of the arguments, result and env of a plugin method,
+/// e.g. `fn get(&self, args: &ArgsGet, invoker: Arc<dyn Invoker>) -> Result<Response, PluginError>`.
+pub fn method_types(sig: &Signature) -> syn::Result<MethodTypes> {
+    let input_type = |index: usize| match sig.inputs.iter().nth(index) {
+        Some(FnArg::Typed(input)) => Some(&*input.ty),
+        _ => None,
+    };
+
+    let args = match input_type(1) {
+        Some(Type::Reference(args)) => (*args.elem).clone(),
+        Some(args) => {
+            return Err(syn::Error::new_spanned(
+                args,
+                "expected the arguments of the method to be taken by reference",
+            ))
+        }
+        None => {
+            return Err(syn::Error::new_spanned(
+                sig,
+                "expected the arguments of the method to be taken by reference",
+            ))
+        }
+    };
+
+    let output = match &sig.output {
+        ReturnType::Type(_, ty) => generic_argument(ty, "Result"),
+        ReturnType::Default => None,
+    };
+    let Some(output) = output else {
+        return Err(syn::Error::new_spanned(
+            &sig.output,
+            "expected the method to return a `Result`",
+        ));
+    };
+
+    let env = input_type(3).map(|env| match generic_argument(env, "Option") {
+        Some(env) => (env, false),
+        None => (env.clone(), true),
+    });
+
+    Ok(MethodTypes { args, output, env })
+}
+
+/// Returns the first generic argument of `ty` if its name is `name`, e.g. `T` for `Option<T>`.
+fn generic_argument(ty: &Type, name: &str) -> Option<Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != name {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    arguments.args.iter().find_map(|argument| match argument {
+        GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    })
+}
+
+/// Returns the name of the last segment of the path of `ty`, e.g. `Env` for `types::Env`.
+pub fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Path(path) => match path.path.segments.last() {
+            Some(segment) => segment.ident.to_string(),
+            None => String::new(),
+        },
+        _ => quote!(#ty).to_string(),
+    }
+}
+
+/// Returns the name set with `#[serde(rename = "...")]`, which is the name in the ABI.
+fn serde_name(attrs: &[Attribute]) -> Option<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .find_map(|nested| match nested {
+            NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("rename") => {
+                match name_value.lit {
+                    Lit::Str(name) => Some(name.value()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+}
+
+/// Returns the doc comment of an item, which is the comment of its definition in the ABI.
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(name_value)) => match name_value.lit {
+                Lit::Str(line) => Some(line.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(String::from).unwrap_or(line))
+        .collect::<Vec<_>>();
+
+    let comment = lines.join("\n");
+    let comment = comment.trim_end();
+    (!comment.is_empty()).then(|| comment.to_string())
+}
+
+fn option_tokens(value: Option<String>) -> TokenStream {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
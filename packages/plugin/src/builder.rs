@@ -0,0 +1,225 @@
+use std::sync::{Arc, Mutex};
+
+use polywrap_core::invoker::Invoker;
+use polywrap_msgpack_serde::to_vec;
+use serde::{de::DeserializeOwned, Serialize};
+use wrap_manifest_schemas::versions::WrapManifest;
+
+use crate::{
+    decode::decode,
+    error::PluginError,
+    method::PluginMethod,
+    module::ModuleMethod,
+    package::{ConcurrentPluginPackage, PluginPackage},
+    with_methods::PluginModuleWithMethods,
+    wrap_type::{AbiBuilder, WrapObject, WrapType},
+};
+
+/// Env taken by the methods of a `PluginBuilder`: `()` for methods without env,
+/// `Option<E>` for methods with an optional env of type `E`, or `E` for methods requiring it.
+pub trait MethodEnv: Sized {
+    /// Whether the method requires an env, `None` if it doesn't take one.
+    fn required() -> Option<bool>;
+
+    /// Name of the type of the env in the ABI, `None` if the method doesn't take one.
+    fn env_type() -> Option<String>;
+
+    /// Sets the env of the ABI, if the method takes one.
+    fn set_env(abi: &mut AbiBuilder);
+
+    /// Decodes the env of an invocation of `method`.
+    fn decode(method: &str, env: Option<&[u8]>) -> Result<Self, PluginError>;
+}
+
+impl MethodEnv for () {
+    fn required() -> Option<bool> {
+        None
+    }
+
+    fn env_type() -> Option<String> {
+        None
+    }
+
+    fn set_env(_: &mut AbiBuilder) {}
+
+    fn decode(_: &str, _: Option<&[u8]>) -> Result<Self, PluginError> {
+        Ok(())
+    }
+}
+
+impl<E: DeserializeOwned + WrapObject> MethodEnv for Option<E> {
+    fn required() -> Option<bool> {
+        Some(false)
+    }
+
+    fn env_type() -> Option<String> {
+        Some(E::wrap_type())
+    }
+
+    fn set_env(abi: &mut AbiBuilder) {
+        abi.set_env::<E>();
+    }
+
+    fn decode(method: &str, env: Option<&[u8]>) -> Result<Self, PluginError> {
+        env.map(|env| decode(method, "env", env)).transpose()
+    }
+}
+
+impl<E: DeserializeOwned + WrapObject> MethodEnv for E {
+    fn required() -> Option<bool> {
+        Some(true)
+    }
+
+    fn env_type() -> Option<String> {
+        Some(E::wrap_type())
+    }
+
+    fn set_env(abi: &mut AbiBuilder) {
+        abi.set_env::<E>();
+    }
+
+    fn decode(method: &str, env: Option<&[u8]>) -> Result<Self, PluginError> {
+        match env {
+            Some(env) => decode(method, "env", env),
+            None => Err(PluginError::MissingEnvError(method.to_string())),
+        }
+    }
+}
+
+/// Method added to a `PluginBuilder`, along with how to describe it in the ABI.
+struct BuilderMethod {
+    name: String,
+    env: Option<bool>,
+    env_type: Option<String>,
+    invoke: Arc<PluginMethod>,
+    add_to_abi: fn(&mut AbiBuilder, &str),
+}
+
+fn add_to_abi<Args: WrapObject, Env: MethodEnv, R: WrapType>(abi: &mut AbiBuilder, name: &str) {
+    abi.add_method::<Args, R>(name, Env::required());
+    Env::set_env(abi);
+}
+
+/// Builds a plugin from closures taking and returning typed values, decoded from and
+/// encoded to msgpack by the builder, e.g.
+///
+/// ```ignore
+/// let package = PluginBuilder::new()
+///     .method("add", |args: ArgsAdd, env: Option<Env>, invoker| Ok(args.a + args.b))
+///     .build();
+/// ```
+///
+/// The methods are described in the ABI of the manifest of the plugin,
+/// unless it is set with `with_manifest`.
+pub struct PluginBuilder {
+    name: String,
+    methods: Vec<BuilderMethod>,
+    manifest: Option<WrapManifest>,
+}
+
+impl Default for PluginBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PluginBuilder {
+    pub fn new() -> Self {
+        Self {
+            name: "plugin".to_string(),
+            methods: vec![],
+            manifest: None,
+        }
+    }
+
+    /// Sets the name of the generated manifest, `plugin` by default.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the manifest of the plugin, instead of generating it.
+    pub fn with_manifest(mut self, manifest: WrapManifest) -> Self {
+        self.manifest = Some(manifest);
+        self
+    }
+
+    /// Adds a method, replacing any method of the same name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the method in the ABI.
+    /// * `method` - Closure taking the arguments, the env and the invoker of an invocation.
+    pub fn method<Args, Env, R, F>(mut self, name: &str, method: F) -> Self
+    where
+        Args: DeserializeOwned + WrapObject,
+        Env: MethodEnv,
+        R: Serialize + WrapType,
+        F: Fn(Args, Env, &dyn Invoker) -> Result<R, PluginError> + Send + Sync + 'static,
+    {
+        let method_name = name.to_string();
+        let invoke = move |args: &[u8], env: Option<&[u8]>, invoker: &dyn Invoker| {
+            let args = decode(&method_name, "args", args)?;
+            let env = Env::decode(&method_name, env)?;
+            let result = method(args, env, invoker)?;
+
+            Ok(to_vec(&result)?)
+        };
+
+        self.methods.retain(|method| method.name != name);
+        self.methods.push(BuilderMethod {
+            name: name.to_string(),
+            env: Env::required(),
+            env_type: Env::env_type(),
+            invoke: Arc::new(invoke),
+            add_to_abi: add_to_abi::<Args, Env, R>,
+        });
+        self
+    }
+
+    fn into_parts(self) -> (PluginModuleWithMethods, WrapManifest) {
+        let manifest = self.manifest.unwrap_or_else(|| {
+            let mut abi = AbiBuilder::new();
+            for method in &self.methods {
+                (method.add_to_abi)(&mut abi, &method.name);
+            }
+
+            WrapManifest {
+                name: self.name,
+                type_: "plugin".to_string(),
+                version: "0.1".to_string(),
+                abi: abi.build(),
+            }
+        });
+
+        let module_methods = self
+            .methods
+            .iter()
+            .map(|method| ModuleMethod {
+                name: method.name.clone(),
+                env: method.env,
+                env_type: method.env_type.clone(),
+            })
+            .collect();
+        let methods = self
+            .methods
+            .into_iter()
+            .map(|method| (method.name, method.invoke))
+            .collect();
+
+        let mut module = PluginModuleWithMethods::new();
+        module.methods(methods).module_methods(module_methods);
+        (module, manifest)
+    }
+
+    pub fn build(self) -> PluginPackage<PluginModuleWithMethods> {
+        let (module, manifest) = self.into_parts();
+        PluginPackage::new(Arc::new(Mutex::new(module)), manifest)
+    }
+
+    /// Builds a package whose invocations run in parallel, as the methods are `Fn`s.
+    pub fn build_concurrent(self) -> ConcurrentPluginPackage<PluginModuleWithMethods> {
+        let (module, manifest) = self.into_parts();
+        ConcurrentPluginPackage::new(Arc::new(module), manifest)
+    }
+}
@@ -0,0 +1,316 @@
+use std::{cell::RefCell, fmt};
+
+use polywrap_msgpack_serde::Deserializer as MsgpackDeserializer;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess,
+    Visitor,
+};
+
+use crate::error::PluginError;
+
+/// Decodes the msgpack `field` of an invocation of `method`, e.g. its `args` or `env`.
+/// Failures are reported with the path of the value that failed to decode,
+/// e.g. `args.request.headers[0]`.
+pub fn decode<T: DeserializeOwned>(
+    method: &str,
+    field: &str,
+    bytes: &[u8],
+) -> Result<T, PluginError> {
+    let path = Path::default();
+    let mut deserializer = MsgpackDeserializer::from_slice(bytes);
+
+    T::deserialize(TrackedDeserializer {
+        deserializer: &mut deserializer,
+        path: &path,
+    })
+    .map_err(|e| {
+        let exception = e.to_string();
+        let mut field = format!("{field}{}", path.segments.borrow().concat());
+
+        // Missing fields are only noticed once the object holding them is decoded
+        if let Some(missing) = missing_field(&exception) {
+            field = format!("{field}.{missing}");
+        }
+
+        PluginError::DecodeError {
+            method: method.to_string(),
+            field,
+            exception,
+        }
+    })
+}
+
+fn missing_field(exception: &str) -> Option<&str> {
+    let start = exception.find("missing field `")? + "missing field `".len();
+    let end = exception[start..].find('`')?;
+
+    Some(&exception[start..start + end])
+}
+
+/// Path to the value being decoded. Segments are popped once their value is decoded,
+/// so on failure the path points at the value that failed.
+#[derive(Default)]
+struct Path {
+    segments: RefCell<Vec<String>>,
+    /// Last string visited, which is the key of a map entry once its key is decoded
+    last_str: RefCell<Option<String>>,
+}
+
+impl Path {
+    fn within<T, E>(&self, segment: String, decode: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        self.segments.borrow_mut().push(segment);
+        let result = decode();
+        if result.is_ok() {
+            self.segments.borrow_mut().pop();
+        }
+
+        result
+    }
+}
+
+struct TrackedDeserializer<'a, D> {
+    deserializer: D,
+    path: &'a Path,
+}
+
+struct TrackedVisitor<'a, V> {
+    visitor: V,
+    path: &'a Path,
+}
+
+struct TrackedSeed<'a, S> {
+    seed: S,
+    path: &'a Path,
+}
+
+struct TrackedSeqAccess<'a, A> {
+    seq: A,
+    path: &'a Path,
+    index: usize,
+}
+
+struct TrackedMapAccess<'a, A> {
+    map: A,
+    path: &'a Path,
+    key: Option<String>,
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, D::Error> {
+                self.deserializer.$method($($arg,)* TrackedVisitor {
+                    visitor,
+                    path: self.path,
+                })
+            }
+        )*
+    };
+}
+
+impl<'de, 'a, D: Deserializer<'de>> Deserializer<'de> for TrackedDeserializer<'a, D> {
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.deserializer.is_human_readable()
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<E: de::Error>(self, v: $ty) -> Result<V::Value, E> {
+                self.visitor.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a, V: Visitor<'de>> Visitor<'de> for TrackedVisitor<'a, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.visitor.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_bytes(&[u8]),
+        visit_borrowed_bytes(&'de [u8]),
+        visit_byte_buf(Vec<u8>),
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<V::Value, E> {
+        *self.path.last_str.borrow_mut() = Some(v.to_string());
+        self.visitor.visit_str(v)
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<V::Value, E> {
+        *self.path.last_str.borrow_mut() = Some(v.to_string());
+        self.visitor.visit_borrowed_str(v)
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<V::Value, E> {
+        *self.path.last_str.borrow_mut() = Some(v.clone());
+        self.visitor.visit_string(v)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<V::Value, E> {
+        self.visitor.visit_none()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<V::Value, E> {
+        self.visitor.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<V::Value, D::Error> {
+        self.visitor.visit_some(TrackedDeserializer {
+            deserializer,
+            path: self.path,
+        })
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<V::Value, D::Error> {
+        self.visitor.visit_newtype_struct(TrackedDeserializer {
+            deserializer,
+            path: self.path,
+        })
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<V::Value, A::Error> {
+        self.visitor.visit_seq(TrackedSeqAccess {
+            seq,
+            path: self.path,
+            index: 0,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<V::Value, A::Error> {
+        self.visitor.visit_map(TrackedMapAccess {
+            map,
+            path: self.path,
+            key: None,
+        })
+    }
+
+    // Values inside enums aren't tracked, and are reported at the path of the enum
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<V::Value, A::Error> {
+        self.visitor.visit_enum(data)
+    }
+}
+
+impl<'de, 'a, S: DeserializeSeed<'de>> DeserializeSeed<'de> for TrackedSeed<'a, S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<S::Value, D::Error> {
+        self.seed.deserialize(TrackedDeserializer {
+            deserializer,
+            path: self.path,
+        })
+    }
+}
+
+impl<'de, 'a, A: SeqAccess<'de>> SeqAccess<'de> for TrackedSeqAccess<'a, A> {
+    type Error = A::Error;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, A::Error> {
+        let path = self.path;
+        let seq = &mut self.seq;
+        let element = path.within(format!("[{}]", self.index), || {
+            seq.next_element_seed(TrackedSeed { seed, path })
+        });
+        self.index += 1;
+
+        element
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.seq.size_hint()
+    }
+}
+
+impl<'de, 'a, A: MapAccess<'de>> MapAccess<'de> for TrackedMapAccess<'a, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, A::Error> {
+        self.path.last_str.borrow_mut().take();
+        let key = self.map.next_key_seed(TrackedSeed {
+            seed,
+            path: self.path,
+        })?;
+        self.key = self.path.last_str.borrow_mut().take();
+
+        Ok(key)
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, A::Error> {
+        let path = self.path;
+        let map = &mut self.map;
+        let segment = match self.key.take() {
+            Some(key) => format!(".{key}"),
+            None => "[?]".to_string(),
+        };
+
+        path.within(segment, || map.next_value_seed(TrackedSeed { seed, path }))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.map.size_hint()
+    }
+}
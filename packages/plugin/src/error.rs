@@ -9,6 +9,19 @@ pub enum PluginError {
     #[error("Method '`{0}`' not found")]
     MethodNotFoundError(String),
 
+    #[error("Failed to decode `{field}` of method '{method}': {exception}")]
+    DecodeError {
+        method: String,
+        field: String,
+        exception: String,
+    },
+
+    #[error("Env must be defined for method '{0}'")]
+    MissingEnvError(String),
+
+    #[error("Plugin doesn't implement its ABI: {}", .0.join("; "))]
+    AbiMismatchError(Vec<String>),
+
     #[error(transparent)]
     JSONError(#[from] serde_json::error::Error),
 
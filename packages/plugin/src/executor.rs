@@ -0,0 +1,64 @@
+//! Drives the async methods of plugins from synchronous invocations.
+//!
+//! Futures are polled on the invoking thread, which parks until they are woken,
+//! while the I/O and timers they depend on are driven by the worker threads of a
+//! multi-threaded Tokio runtime. Unlike `Runtime::block_on`, this doesn't panic
+//! when the invocation itself runs inside a Tokio runtime.
+
+use std::{
+    future::Future,
+    pin::pin,
+    sync::{Arc, OnceLock},
+    task::{Context, Poll, Wake},
+    thread::{self, Thread},
+};
+
+use tokio::runtime::{Handle, Runtime, RuntimeFlavor};
+
+static DEFAULT_EXECUTOR: OnceLock<Runtime> = OnceLock::new();
+
+/// Returns the runtime shared by the clients without a configured executor.
+pub fn default_executor() -> Handle {
+    DEFAULT_EXECUTOR
+        .get_or_init(|| Runtime::new().expect("Failed to start the default plugin executor"))
+        .handle()
+        .clone()
+}
+
+/// Runs a future to completion on the current thread, in the context of `executor`.
+/// Falls back to the default executor if `executor` is not set, or is not multi-threaded
+/// since a current-thread runtime only makes progress while it is blocked on.
+///
+/// # Arguments
+///
+/// * `executor` - Runtime configured on the client, as returned by `Invoker::get_executor`.
+/// * `future` - The future to run.
+pub fn block_on<F: Future>(executor: Option<Handle>, future: F) -> F::Output {
+    let executor = executor
+        .filter(|executor| executor.runtime_flavor() == RuntimeFlavor::MultiThread)
+        .unwrap_or_else(default_executor);
+    let _guard = executor.enter();
+
+    let waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+        thread::park();
+    }
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
@@ -1,20 +1,30 @@
+pub mod builder;
+pub mod decode;
 pub mod error;
+pub mod executor;
 pub mod method;
 pub mod module;
 pub mod package;
 pub mod with_methods;
+pub mod wrap_type;
 pub mod wrapper;
 
+pub use builder::*;
 pub use error::*;
 pub use method::*;
 pub use module::*;
 pub use package::*;
 pub use with_methods::*;
+pub use wrap_type::*;
 pub use wrapper::*;
 
+pub use async_trait::async_trait;
 pub use implementor::*;
 pub use polywrap_core::{client::*, invoker::*, macros::*, *};
-pub use polywrap_msgpack_serde::{serde_bytes::ByteBuf, to_vec, Map, *};
+pub use polywrap_msgpack_serde::{
+    from_slice, serde_bytes, serde_bytes::ByteBuf, to_vec, BigInt, BigIntWrapper, BigNumber,
+    JSONString, Map, JSON,
+};
 pub use polywrap_plugin_implementor as implementor;
 pub use polywrap_uri::*;
 pub use wrap_manifest_schemas::{versions::*, *};
@@ -22,4 +32,6 @@ pub use wrap_manifest_schemas::{versions::*, *};
 // These are needed to expose because plugin_impl macro uses it
 pub use polywrap_core;
 pub use polywrap_msgpack_serde;
-pub use serde_json::*;
+// Only the JSON items used by plugins, as globbing `serde_json` clashes with
+// the `Result`, `Error` and (de)serializers of `polywrap_msgpack_serde`
+pub use serde_json::{from_str, from_value, json, to_string, to_value, Value};
@@ -1,9 +1,21 @@
 use std::{fmt::Debug, sync::Arc};
 
+use async_trait::async_trait;
 use polywrap_core::invoker::Invoker;
 
 use crate::error::PluginError;
 
+/// Method implemented by a plugin module, checked against the ABI of its package.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleMethod {
+    /// Name of the method in the ABI.
+    pub name: String,
+    /// Whether the method requires an env, `None` if it doesn't take one.
+    pub env: Option<bool>,
+    /// Name of the type of the env in the ABI, `None` if the method doesn't take one.
+    pub env_type: Option<String>,
+}
+
 pub trait PluginModule: Send + Sync + Debug {
     fn _wrap_invoke(
         &mut self,
@@ -12,4 +24,44 @@ pub trait PluginModule: Send + Sync + Debug {
         env: Option<&[u8]>,
         invoker: Arc<dyn Invoker>,
     ) -> Result<Vec<u8>, PluginError>;
+
+    /// Returns the methods the module implements, `None` if it can't tell.
+    fn _wrap_methods(&self) -> Option<Vec<ModuleMethod>> {
+        None
+    }
+}
+
+/// Module of a plugin invoked through a shared reference, so that independent invocations
+/// run in parallel. Any state it keeps has to be synchronized internally.
+pub trait ConcurrentPluginModule: Send + Sync + Debug {
+    fn _wrap_invoke(
+        &self,
+        method_name: &str,
+        params: &[u8],
+        env: Option<&[u8]>,
+        invoker: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, PluginError>;
+
+    /// Returns the methods the module implements, `None` if it can't tell.
+    fn _wrap_methods(&self) -> Option<Vec<ModuleMethod>> {
+        None
+    }
+
+    /// Returns the module as an `AsyncPluginModule` if it has async methods.
+    fn _as_async(&self) -> Option<&dyn AsyncPluginModule> {
+        None
+    }
+}
+
+/// Module of a plugin with async methods taking `&self`, awaited natively by the async API of the
+/// client when the plugin is wrapped in a `ConcurrentPluginWrapper`.
+#[async_trait]
+pub trait AsyncPluginModule: Send + Sync {
+    async fn _wrap_invoke_async(
+        &self,
+        method_name: &str,
+        params: &[u8],
+        env: Option<&[u8]>,
+        invoker: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, PluginError>;
 }
@@ -3,26 +3,124 @@ use polywrap_core::{
     package::{GetManifestOptions, WrapPackage},
     wrapper::Wrapper,
 };
+use polywrap_msgpack_serde::to_vec;
 use std::{
+    collections::HashMap,
     fmt::{Debug, Formatter},
     sync::{Arc, Mutex},
 };
-use wrap_manifest_schemas::versions::WrapManifest;
+use wrap_manifest_schemas::{abi::Abi, versions::WrapManifest};
 
-use crate::{module::PluginModule, wrapper::PluginWrapper};
+use crate::{
+    error::PluginError,
+    module::{ConcurrentPluginModule, ModuleMethod, PluginModule},
+    wrapper::{ConcurrentPluginWrapper, PluginWrapper},
+};
+
+type PluginFiles = Arc<HashMap<String, Vec<u8>>>;
+
+fn get_plugin_files(manifest: &WrapManifest) -> PluginFiles {
+    let wrap_info = to_vec(manifest).unwrap();
+    Arc::new(HashMap::from([("wrap.info".to_string(), wrap_info)]))
+}
+
+fn describe_env(env: Option<bool>) -> &'static str {
+    match env {
+        Some(true) => "required",
+        Some(false) => "optional",
+        None => "not taken",
+    }
+}
+
+/// Checks that `methods` are the methods of the ABI of `manifest`, taking an env of the type
+/// it declares.
+/// Modules which can't tell their methods aren't checked.
+fn validate_methods(
+    methods: Option<Vec<ModuleMethod>>,
+    manifest: &WrapManifest,
+) -> Result<(), PluginError> {
+    let Some(methods) = methods else {
+        return Ok(());
+    };
+    let abi = Abi::try_from(&manifest.abi)
+        .map_err(|e| PluginError::AbiMismatchError(vec![format!("invalid ABI: {e}")]))?;
+
+    let mut mismatches = vec![];
+    for abi_method in abi.methods() {
+        let name = abi_method.name.as_deref().unwrap_or_default();
+        let abi_env = abi_method
+            .env
+            .as_ref()
+            .map(|env| env.required.unwrap_or_default());
+
+        match methods.iter().find(|method| method.name == name) {
+            Some(method) if method.env != abi_env => mismatches.push(format!(
+                "env of method '{name}' is {} in the ABI but {} in the implementation",
+                describe_env(abi_env),
+                describe_env(method.env)
+            )),
+            Some(_) => {}
+            None => mismatches.push(format!("method '{name}' of the ABI isn't implemented")),
+        }
+    }
+
+    for method in &methods {
+        if abi.method(&method.name).is_none() {
+            mismatches.push(format!(
+                "method '{}' isn't declared in the ABI",
+                method.name
+            ));
+        } else if method.env.is_some() && abi.env.is_none() {
+            mismatches.push(format!(
+                "method '{}' takes an env but the ABI has no env type",
+                method.name
+            ));
+        } else if let (Some(env_type), Some(abi_env)) = (&method.env_type, &abi.env) {
+            if *env_type != abi_env.type_ {
+                mismatches.push(format!(
+                    "env of method '{}' is of type '{env_type}' but the env type of the ABI is '{}'",
+                    method.name, abi_env.type_
+                ));
+            }
+        }
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(PluginError::AbiMismatchError(mismatches))
+    }
+}
 
 pub struct PluginPackage<T: PluginModule> {
     manifest: WrapManifest,
     plugin_module: Arc<Mutex<T>>,
+    files: PluginFiles,
 }
 
 impl<T: PluginModule> PluginPackage<T> {
+    /// Creates a package serving its manifest as the `wrap.info` file of its wrappers.
     pub fn new(plugin_module: Arc<Mutex<T>>, manifest: WrapManifest) -> Self {
         Self {
             plugin_module,
+            files: get_plugin_files(&manifest),
             manifest,
         }
     }
+
+    /// Registers a static file, e.g. a README, an icon or a schema, served by the
+    /// `get_file` of the wrappers of the package. It replaces any file at the same path.
+    pub fn with_file(mut self, path: impl Into<String>, content: Vec<u8>) -> Self {
+        Arc::make_mut(&mut self.files).insert(path.into(), content);
+        self
+    }
+
+    /// Checks that the module implements the methods of the ABI of the manifest, and only
+    /// them, each taking an env of the type the ABI declares.
+    pub fn validate(&self) -> Result<(), PluginError> {
+        let methods = self.plugin_module.lock().unwrap()._wrap_methods();
+        validate_methods(methods, &self.manifest)
+    }
 }
 
 impl<T: PluginModule> PartialEq for PluginPackage<T> {
@@ -51,7 +149,70 @@ impl<T: PluginModule + 'static> WrapPackage for PluginPackage<T> {
         Ok(self.manifest.clone())
     }
 
+    fn validate(&self) -> Result<(), Error> {
+        Ok(PluginPackage::validate(self)?)
+    }
+
+    fn create_wrapper(&self) -> Result<Arc<dyn Wrapper>, Error> {
+        Ok(Arc::new(
+            PluginWrapper::new(self.plugin_module.clone()).with_files(self.files.clone()),
+        ))
+    }
+}
+
+/// Package of a plugin whose invocations aren't serialized behind a lock.
+pub struct ConcurrentPluginPackage<T: ConcurrentPluginModule> {
+    manifest: WrapManifest,
+    plugin_module: Arc<T>,
+    files: PluginFiles,
+}
+
+impl<T: ConcurrentPluginModule> ConcurrentPluginPackage<T> {
+    /// Creates a package serving its manifest as the `wrap.info` file of its wrappers.
+    pub fn new(plugin_module: Arc<T>, manifest: WrapManifest) -> Self {
+        Self {
+            plugin_module,
+            files: get_plugin_files(&manifest),
+            manifest,
+        }
+    }
+
+    /// Registers a static file, e.g. a README, an icon or a schema, served by the
+    /// `get_file` of the wrappers of the package. It replaces any file at the same path.
+    pub fn with_file(mut self, path: impl Into<String>, content: Vec<u8>) -> Self {
+        Arc::make_mut(&mut self.files).insert(path.into(), content);
+        self
+    }
+
+    /// Checks that the module implements the methods of the ABI of the manifest, and only
+    /// them, each taking an env of the type the ABI declares.
+    pub fn validate(&self) -> Result<(), PluginError> {
+        validate_methods(self.plugin_module._wrap_methods(), &self.manifest)
+    }
+}
+
+impl<T: ConcurrentPluginModule> Debug for ConcurrentPluginPackage<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        f.debug_struct("ConcurrentPluginPackage")
+            .field("plugin_module", &self.plugin_module)
+            .field("manifest", &self.manifest)
+            .finish()
+    }
+}
+
+impl<T: ConcurrentPluginModule + 'static> WrapPackage for ConcurrentPluginPackage<T> {
+    fn get_manifest(&self, _: Option<&GetManifestOptions>) -> Result<WrapManifest, Error> {
+        Ok(self.manifest.clone())
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        Ok(ConcurrentPluginPackage::validate(self)?)
+    }
+
     fn create_wrapper(&self) -> Result<Arc<dyn Wrapper>, Error> {
-        Ok(Arc::new(PluginWrapper::new(self.plugin_module.clone())))
+        Ok(Arc::new(
+            ConcurrentPluginWrapper::new(self.plugin_module.clone())
+                .with_files(self.files.clone()),
+        ))
     }
 }
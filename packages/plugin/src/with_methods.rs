@@ -5,11 +5,16 @@ use std::{
     sync::Arc,
 };
 
-use crate::{error::PluginError, method::PluginMethod, module::PluginModule};
+use crate::{
+    error::PluginError,
+    method::PluginMethod,
+    module::{ConcurrentPluginModule, ModuleMethod, PluginModule},
+};
 
 #[derive(Clone)]
 pub struct PluginModuleWithMethods {
     methods_map: HashMap<String, Arc<PluginMethod>>,
+    module_methods: Option<Vec<ModuleMethod>>,
 }
 
 impl Default for PluginModuleWithMethods {
@@ -22,6 +27,7 @@ impl PluginModuleWithMethods {
     pub fn new() -> Self {
         Self {
             methods_map: HashMap::new(),
+            module_methods: None,
         }
     }
 
@@ -29,6 +35,12 @@ impl PluginModuleWithMethods {
         self.methods_map = methods;
         self
     }
+
+    /// Sets the methods returned by `_wrap_methods`, checked against the ABI of the package.
+    pub fn module_methods(&mut self, module_methods: Vec<ModuleMethod>) -> &mut Self {
+        self.module_methods = Some(module_methods);
+        self
+    }
 }
 
 impl PluginModule for PluginModuleWithMethods {
@@ -38,6 +50,22 @@ impl PluginModule for PluginModuleWithMethods {
         params: &[u8],
         env: Option<&[u8]>,
         invoker: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, PluginError> {
+        ConcurrentPluginModule::_wrap_invoke(self, method_name, params, env, invoker)
+    }
+
+    fn _wrap_methods(&self) -> Option<Vec<ModuleMethod>> {
+        self.module_methods.clone()
+    }
+}
+
+impl ConcurrentPluginModule for PluginModuleWithMethods {
+    fn _wrap_invoke(
+        &self,
+        method_name: &str,
+        params: &[u8],
+        env: Option<&[u8]>,
+        invoker: Arc<dyn Invoker>,
     ) -> Result<Vec<u8>, PluginError> {
         if let Some(method) = self.methods_map.get(method_name) {
             (method)(params, env, invoker.as_ref())
@@ -45,6 +73,10 @@ impl PluginModule for PluginModuleWithMethods {
             Err(PluginError::MethodNotFoundError(method_name.to_string()))
         }
     }
+
+    fn _wrap_methods(&self) -> Option<Vec<ModuleMethod>> {
+        self.module_methods.clone()
+    }
 }
 
 impl Debug for PluginModuleWithMethods {
@@ -0,0 +1,358 @@
+//! ABI of plugins derived from the Rust types of their methods, so that it can't drift from
+//! their implementation. Objects and enums implement `WrapObject` and `WrapEnum` with the
+//! derives of the same name, and `#[plugin_impl(abi)]` builds the ABI of the module.
+
+use std::collections::{BTreeMap, HashMap};
+
+use polywrap_msgpack_serde::{serde_bytes::ByteBuf, BigInt, BigIntWrapper, BigNumber, JSONString};
+use serde_json::{json, Value};
+use wrap_manifest_schemas::versions::WrapManifestAbi;
+
+const OBJECT_KIND: u32 = 1;
+const SCALAR_KIND: u32 = 1 << 2;
+const ENUM_KIND: u32 = 1 << 3;
+const ARRAY_KIND: u32 = 1 << 4 | 2;
+const PROPERTY_KIND: u32 = 1 << 5 | 2;
+const METHOD_KIND: u32 = 1 << 6;
+const MODULE_KIND: u32 = 1 << 7;
+const OBJECT_REF_KIND: u32 = 1 << 13;
+const ENUM_REF_KIND: u32 = 1 << 14;
+const ENV_KIND: u32 = 1 << 16;
+const MAP_KIND: u32 = 1 << 18 | 2;
+
+/// Rust type of the values described by the ABI of a plugin.
+pub trait WrapType {
+    /// Name of the type in the ABI, e.g. `String`, `[Int]` or `Map<String, Int>`.
+    fn wrap_type() -> String;
+
+    /// Field of definitions holding the reference to the type, e.g. `scalar` or `object`.
+    fn ref_field() -> &'static str;
+
+    /// Reference to the type from the value named `name`.
+    fn type_ref(name: &str, required: bool) -> Value;
+
+    /// Whether values of the type can't be null, which holds for every type but `Option`s.
+    fn required() -> bool {
+        true
+    }
+
+    /// Adds the definitions of the objects and enums the type refers to.
+    fn add_definitions(_abi: &mut AbiBuilder) {}
+}
+
+/// Object of the ABI of a plugin, usually implemented with `#[derive(WrapObject)]`.
+pub trait WrapObject: WrapType {
+    /// Definitions of the properties of the object.
+    fn properties() -> Vec<Value>;
+
+    /// Documentation of the object.
+    fn comment() -> Option<&'static str> {
+        None
+    }
+
+    /// Adds the definitions of the objects and enums the properties refer to.
+    fn add_property_definitions(abi: &mut AbiBuilder);
+}
+
+/// Enum of the ABI of a plugin, usually implemented with `#[derive(WrapEnum)]`.
+pub trait WrapEnum: WrapType {
+    /// Names of the constants of the enum.
+    fn constants() -> Vec<&'static str>;
+
+    /// Documentation of the enum.
+    fn comment() -> Option<&'static str> {
+        None
+    }
+}
+
+/// Builds the ABI of a plugin from the types of its methods.
+#[derive(Debug, Default)]
+pub struct AbiBuilder {
+    methods: Vec<Value>,
+    object_types: Vec<Value>,
+    enum_types: Vec<Value>,
+    env_type: Option<Value>,
+}
+
+impl AbiBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a method taking the properties of `Args` as arguments, and returning `R`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the method in the ABI.
+    /// * `env` - Whether the method requires an env, `None` if it doesn't take one.
+    pub fn add_method<Args: WrapObject, R: WrapType>(
+        &mut self,
+        name: &str,
+        env: Option<bool>,
+    ) -> &mut Self {
+        let mut method = json!({
+            "arguments": Args::properties(),
+            "kind": METHOD_KIND,
+            "name": name,
+            "required": true,
+            "return": property_definition::<R>(name, None),
+            "type": "Method",
+        });
+        if let Some(required) = env {
+            method["env"] = json!({ "required": required });
+        }
+
+        self.methods.push(method);
+        Args::add_property_definitions(self);
+        R::add_definitions(self);
+        self
+    }
+
+    /// Sets the env of the methods.
+    pub fn set_env<E: WrapObject>(&mut self) -> &mut Self {
+        if self.env_type.is_none() {
+            self.env_type = Some(object_definition::<E>(ENV_KIND));
+            E::add_property_definitions(self);
+        }
+        self
+    }
+
+    /// Adds the definition of an object, and of the types it refers to.
+    pub fn add_object<T: WrapObject>(&mut self) -> &mut Self {
+        if !contains_type(&self.object_types, &T::wrap_type()) {
+            // Added before its properties, which may refer to it
+            self.object_types.push(object_definition::<T>(OBJECT_KIND));
+            T::add_property_definitions(self);
+        }
+        self
+    }
+
+    /// Adds the definition of an enum.
+    pub fn add_enum<T: WrapEnum>(&mut self) -> &mut Self {
+        if !contains_type(&self.enum_types, &T::wrap_type()) {
+            let mut definition = json!({
+                "constants": T::constants(),
+                "kind": ENUM_KIND,
+                "type": T::wrap_type(),
+            });
+            if let Some(comment) = T::comment() {
+                definition["comment"] = comment.into();
+            }
+            self.enum_types.push(definition);
+        }
+        self
+    }
+
+    pub fn build(&self) -> WrapManifestAbi {
+        let mut abi = json!({
+            "moduleType": {
+                "kind": MODULE_KIND,
+                "methods": self.methods,
+                "type": "Module",
+            },
+            "version": "0.1",
+        });
+        if !self.object_types.is_empty() {
+            abi["objectTypes"] = self.object_types.clone().into();
+        }
+        if !self.enum_types.is_empty() {
+            abi["enumTypes"] = self.enum_types.clone().into();
+        }
+        if let Some(env_type) = &self.env_type {
+            abi["envType"] = env_type.clone();
+        }
+
+        serde_json::from_value(abi).expect("Derived ABI doesn't match the manifest schema")
+    }
+}
+
+/// Definition of a property or argument.
+///
+/// # Arguments
+///
+/// * `name` - Name of the property.
+/// * `comment` - Documentation of the property.
+pub fn property_definition<T: WrapType>(name: &str, comment: Option<&str>) -> Value {
+    let mut definition = json!({
+        "kind": PROPERTY_KIND,
+        "name": name,
+        "type": T::wrap_type(),
+    });
+    if T::required() {
+        definition["required"] = true.into();
+    }
+    definition[T::ref_field()] = T::type_ref(name, T::required());
+    if let Some(comment) = comment {
+        definition["comment"] = comment.into();
+    }
+    definition
+}
+
+/// Reference to the object `wrap_type` from the value named `name`.
+pub fn object_ref(wrap_type: &str, name: &str, required: bool) -> Value {
+    named_type_ref(OBJECT_REF_KIND, wrap_type, name, required)
+}
+
+/// Reference to the enum `wrap_type` from the value named `name`.
+pub fn enum_ref(wrap_type: &str, name: &str, required: bool) -> Value {
+    named_type_ref(ENUM_REF_KIND, wrap_type, name, required)
+}
+
+fn named_type_ref(kind: u32, wrap_type: &str, name: &str, required: bool) -> Value {
+    let mut type_ref = json!({
+        "kind": kind,
+        "name": name,
+        "type": wrap_type,
+    });
+    if required {
+        type_ref["required"] = true.into();
+    }
+    type_ref
+}
+
+fn object_definition<T: WrapObject>(kind: u32) -> Value {
+    let mut definition = json!({
+        "kind": kind,
+        "properties": T::properties(),
+        "type": T::wrap_type(),
+    });
+    if let Some(comment) = T::comment() {
+        definition["comment"] = comment.into();
+    }
+    definition
+}
+
+fn contains_type(definitions: &[Value], wrap_type: &str) -> bool {
+    definitions
+        .iter()
+        .any(|definition| definition["type"] == wrap_type)
+}
+
+macro_rules! impl_scalar {
+    ($($type:ty => $wrap_type:literal),* $(,)?) => {$(
+        impl WrapType for $type {
+            fn wrap_type() -> String {
+                $wrap_type.to_string()
+            }
+
+            fn ref_field() -> &'static str {
+                "scalar"
+            }
+
+            fn type_ref(name: &str, required: bool) -> Value {
+                named_type_ref(SCALAR_KIND, $wrap_type, name, required)
+            }
+        }
+    )*};
+}
+
+impl_scalar!(
+    String => "String",
+    bool => "Boolean",
+    i8 => "Int8",
+    i16 => "Int16",
+    i32 => "Int",
+    u8 => "UInt8",
+    u16 => "UInt16",
+    u32 => "UInt32",
+    ByteBuf => "Bytes",
+    BigInt => "BigInt",
+    BigIntWrapper => "BigInt",
+    BigNumber => "BigNumber",
+    JSONString => "JSON",
+);
+
+impl<T: WrapType> WrapType for Option<T> {
+    fn wrap_type() -> String {
+        T::wrap_type()
+    }
+
+    fn ref_field() -> &'static str {
+        T::ref_field()
+    }
+
+    fn type_ref(name: &str, required: bool) -> Value {
+        T::type_ref(name, required)
+    }
+
+    fn required() -> bool {
+        false
+    }
+
+    fn add_definitions(abi: &mut AbiBuilder) {
+        T::add_definitions(abi)
+    }
+}
+
+impl<T: WrapType> WrapType for Box<T> {
+    fn wrap_type() -> String {
+        T::wrap_type()
+    }
+
+    fn ref_field() -> &'static str {
+        T::ref_field()
+    }
+
+    fn type_ref(name: &str, required: bool) -> Value {
+        T::type_ref(name, required)
+    }
+
+    fn required() -> bool {
+        T::required()
+    }
+
+    fn add_definitions(abi: &mut AbiBuilder) {
+        T::add_definitions(abi)
+    }
+}
+
+impl<T: WrapType> WrapType for Vec<T> {
+    fn wrap_type() -> String {
+        format!("[{}]", T::wrap_type())
+    }
+
+    fn ref_field() -> &'static str {
+        "array"
+    }
+
+    fn type_ref(name: &str, required: bool) -> Value {
+        let item = T::type_ref(name, T::required());
+        let mut type_ref = named_type_ref(ARRAY_KIND, &Self::wrap_type(), name, required);
+        type_ref[T::ref_field()] = item.clone();
+        type_ref["item"] = item;
+        type_ref
+    }
+
+    fn add_definitions(abi: &mut AbiBuilder) {
+        T::add_definitions(abi)
+    }
+}
+
+macro_rules! impl_map {
+    ($($map:ident),*) => {$(
+        impl<K: WrapType, V: WrapType> WrapType for $map<K, V> {
+            fn wrap_type() -> String {
+                format!("Map<{}, {}>", K::wrap_type(), V::wrap_type())
+            }
+
+            fn ref_field() -> &'static str {
+                "map"
+            }
+
+            fn type_ref(name: &str, required: bool) -> Value {
+                let value = V::type_ref(name, V::required());
+                let mut type_ref = named_type_ref(MAP_KIND, &Self::wrap_type(), name, required);
+                type_ref["key"] = K::type_ref(name, true);
+                type_ref[V::ref_field()] = value.clone();
+                type_ref["value"] = value;
+                type_ref
+            }
+
+            fn add_definitions(abi: &mut AbiBuilder) {
+                V::add_definitions(abi)
+            }
+        }
+    )*};
+}
+
+impl_map!(BTreeMap, HashMap);
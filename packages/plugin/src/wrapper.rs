@@ -1,25 +1,41 @@
 use std::{
+    collections::HashMap,
     fmt::Debug,
     sync::{Arc, Mutex},
 };
 
+use async_trait::async_trait;
 use polywrap_core::{
+    async_wrapper::AsyncWrapper,
     error::Error,
     invoker::Invoker,
     wrapper::{GetFileOptions, Wrapper},
 };
 use polywrap_msgpack_serde::to_vec;
 
-use crate::module::PluginModule;
+use crate::{
+    error::PluginError,
+    module::{ConcurrentPluginModule, PluginModule},
+};
 
 #[derive(Debug)]
 pub struct PluginWrapper<T: PluginModule> {
     instance: Arc<Mutex<T>>,
+    files: Arc<HashMap<String, Vec<u8>>>,
 }
 
 impl<T: PluginModule> PluginWrapper<T> {
     pub fn new(instance: Arc<Mutex<T>>) -> Self {
-        Self { instance }
+        Self {
+            instance,
+            files: Arc::default(),
+        }
+    }
+
+    /// Sets the static files served by `get_file`, by path.
+    pub fn with_files(mut self, files: Arc<HashMap<String, Vec<u8>>>) -> Self {
+        self.files = files;
+        self
     }
 }
 
@@ -31,10 +47,7 @@ impl<T: PluginModule + 'static> Wrapper for PluginWrapper<T> {
         env: Option<&[u8]>,
         invoker: Arc<dyn Invoker>,
     ) -> Result<Vec<u8>, Error> {
-        let args = match args {
-            Some(args) => args.to_vec(),
-            None => to_vec(&{}).unwrap(),
-        };
+        let args = get_args(args);
 
         let result = self
             .instance
@@ -42,16 +55,11 @@ impl<T: PluginModule + 'static> Wrapper for PluginWrapper<T> {
             .unwrap()
             ._wrap_invoke(method, &args, env, invoker);
 
-        match result {
-            Ok(result) => Ok(result),
-            Err(e) => Err(crate::error::PluginError::InvocationError {
-                exception: e.to_string(),
-            }
-            .into()),
-        }
+        map_invocation_result(result)
     }
-    fn get_file(&self, _: &GetFileOptions) -> Result<Vec<u8>, polywrap_core::error::Error> {
-        unimplemented!("client.get_file(...) is not implemented for Plugins.")
+
+    fn get_file(&self, options: &GetFileOptions) -> Result<Vec<u8>, Error> {
+        get_plugin_file(&self.files, options)
     }
 }
 
@@ -60,3 +68,98 @@ impl<T: PluginModule> PartialEq for PluginWrapper<T> {
         self == other
     }
 }
+
+/// Wrapper of a plugin whose invocations run in parallel, see `ConcurrentPluginModule`.
+#[derive(Debug)]
+pub struct ConcurrentPluginWrapper<T: ConcurrentPluginModule> {
+    instance: Arc<T>,
+    files: Arc<HashMap<String, Vec<u8>>>,
+}
+
+impl<T: ConcurrentPluginModule> ConcurrentPluginWrapper<T> {
+    pub fn new(instance: Arc<T>) -> Self {
+        Self {
+            instance,
+            files: Arc::default(),
+        }
+    }
+
+    /// Sets the static files served by `get_file`, by path.
+    pub fn with_files(mut self, files: Arc<HashMap<String, Vec<u8>>>) -> Self {
+        self.files = files;
+        self
+    }
+}
+
+impl<T: ConcurrentPluginModule + 'static> Wrapper for ConcurrentPluginWrapper<T> {
+    fn invoke(
+        &self,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        invoker: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, Error> {
+        let args = get_args(args);
+        let result = self.instance._wrap_invoke(method, &args, env, invoker);
+
+        map_invocation_result(result)
+    }
+
+    fn get_file(&self, options: &GetFileOptions) -> Result<Vec<u8>, Error> {
+        get_plugin_file(&self.files, options)
+    }
+
+    fn as_async(&self) -> Option<&dyn AsyncWrapper> {
+        self.instance._as_async().map(|_| self as &dyn AsyncWrapper)
+    }
+}
+
+#[async_trait]
+impl<T: ConcurrentPluginModule + 'static> AsyncWrapper for ConcurrentPluginWrapper<T> {
+    async fn invoke_async(
+        &self,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        invoker: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, Error> {
+        let Some(module) = self.instance._as_async() else {
+            return self.invoke(method, args, env, invoker);
+        };
+
+        let args = get_args(args);
+        let result = module._wrap_invoke_async(method, &args, env, invoker).await;
+
+        map_invocation_result(result)
+    }
+}
+
+fn get_args(args: Option<&[u8]>) -> Vec<u8> {
+    match args {
+        Some(args) => args.to_vec(),
+        None => to_vec(&{}).unwrap(),
+    }
+}
+
+fn map_invocation_result(result: Result<Vec<u8>, PluginError>) -> Result<Vec<u8>, Error> {
+    match result {
+        Ok(result) => Ok(result),
+        Err(e) => Err(PluginError::InvocationError {
+            exception: e.to_string(),
+        }
+        .into()),
+    }
+}
+
+/// Files are returned as they were registered, whatever the requested encoding.
+fn get_plugin_file(
+    files: &HashMap<String, Vec<u8>>,
+    options: &GetFileOptions,
+) -> Result<Vec<u8>, Error> {
+    files.get(&options.path).cloned().ok_or_else(|| {
+        Error::wrapper(format!(
+            "PluginWrapper: File was not found.\nSubpath: {}",
+            options.path
+        ))
+    })
+}
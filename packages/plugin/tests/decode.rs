@@ -0,0 +1,118 @@
+use polywrap_msgpack_serde::to_vec;
+use polywrap_plugin::{decode::decode, error::PluginError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Args {
+    request: Request,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Request {
+    url: String,
+    headers: Vec<Header>,
+    timeout: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Header {
+    key: String,
+    value: String,
+}
+
+#[derive(Serialize)]
+struct InvalidArgs {
+    request: InvalidRequest,
+}
+
+#[derive(Serialize)]
+struct InvalidRequest {
+    url: String,
+    headers: Vec<InvalidHeader>,
+    timeout: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct InvalidHeader {
+    key: String,
+    value: u32,
+}
+
+#[derive(Serialize)]
+struct IncompleteArgs {
+    request: IncompleteRequest,
+}
+
+#[derive(Serialize)]
+struct IncompleteRequest {
+    headers: Vec<()>,
+}
+
+fn failing_field<T: std::fmt::Debug>(result: Result<T, PluginError>) -> String {
+    match result {
+        Err(PluginError::DecodeError { method, field, .. }) => {
+            assert_eq!(method, "get");
+            field
+        }
+        result => panic!("Expected a decode error, got: {result:?}"),
+    }
+}
+
+#[test]
+fn decodes_valid_values() {
+    let bytes = to_vec(&InvalidArgs {
+        request: InvalidRequest {
+            url: "https://polywrap.io".to_string(),
+            headers: vec![],
+            timeout: Some(10),
+        },
+    })
+    .unwrap();
+
+    let args: Args = decode("get", "args", &bytes).unwrap();
+    assert_eq!(
+        args,
+        Args {
+            request: Request {
+                url: "https://polywrap.io".to_string(),
+                headers: vec![],
+                timeout: Some(10),
+            }
+        }
+    );
+}
+
+#[test]
+fn reports_path_of_invalid_value() {
+    let bytes = to_vec(&InvalidArgs {
+        request: InvalidRequest {
+            url: "https://polywrap.io".to_string(),
+            headers: vec![
+                InvalidHeader {
+                    key: "a".to_string(),
+                    value: 1,
+                },
+                InvalidHeader {
+                    key: "b".to_string(),
+                    value: 2,
+                },
+            ],
+            timeout: None,
+        },
+    })
+    .unwrap();
+
+    let field = failing_field(decode::<Args>("get", "args", &bytes));
+    assert_eq!(field, "args.request.headers[0].value");
+}
+
+#[test]
+fn reports_path_of_missing_field() {
+    let bytes = to_vec(&IncompleteArgs {
+        request: IncompleteRequest { headers: vec![] },
+    })
+    .unwrap();
+
+    let field = failing_field(decode::<Args>("get", "args", &bytes));
+    assert_eq!(field, "args.request.url");
+}
@@ -0,0 +1,138 @@
+use std::sync::{
+    atomic::{AtomicI32, Ordering},
+    Arc,
+};
+
+use polywrap_core::invoker::Invoker;
+use polywrap_plugin::{
+    error::PluginError,
+    implementor::{plugin_impl, WrapObject},
+    module::ConcurrentPluginModule,
+    package::ConcurrentPluginPackage,
+    JSON,
+};
+use serde::{Deserialize, Serialize};
+use wrap_manifest_schemas::versions::{WrapManifest, WrapManifestAbi};
+
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
+pub struct Env {
+    pub step: i32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
+pub struct ArgsIncrement {}
+
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
+pub struct ArgsReset {}
+
+pub trait Module: ConcurrentPluginModule {
+    fn increment(
+        &self,
+        args: &ArgsIncrement,
+        invoker: Arc<dyn Invoker>,
+        env: Env,
+    ) -> Result<i32, PluginError>;
+
+    fn reset(&self, args: &ArgsReset, invoker: Arc<dyn Invoker>) -> Result<bool, PluginError>;
+}
+
+#[derive(Debug, Default)]
+struct CounterPlugin {
+    count: AtomicI32,
+}
+
+#[plugin_impl(concurrent, abi)]
+impl Module for CounterPlugin {
+    fn increment(
+        &self,
+        _: &ArgsIncrement,
+        _: Arc<dyn Invoker>,
+        env: Env,
+    ) -> Result<i32, PluginError> {
+        Ok(self.count.fetch_add(env.step, Ordering::SeqCst) + env.step)
+    }
+
+    fn reset(&self, _: &ArgsReset, _: Arc<dyn Invoker>) -> Result<bool, PluginError> {
+        self.count.store(0, Ordering::SeqCst);
+        Ok(true)
+    }
+}
+
+fn get_manifest() -> WrapManifest {
+    manifest(CounterPlugin::get_abi())
+}
+
+fn manifest(abi: WrapManifestAbi) -> WrapManifest {
+    WrapManifest {
+        name: "counter".to_string(),
+        type_: "plugin".to_string(),
+        version: "0.1".to_string(),
+        abi,
+    }
+}
+
+fn package(manifest: WrapManifest) -> ConcurrentPluginPackage<CounterPlugin> {
+    ConcurrentPluginPackage::new(Arc::new(CounterPlugin::default()), manifest)
+}
+
+#[test]
+fn validates_package_implementing_its_abi() {
+    assert!(package(get_manifest()).validate().is_ok());
+}
+
+#[test]
+fn reports_mismatches_with_the_abi() {
+    let abi = JSON::from_value::<WrapManifestAbi>(JSON::json!({
+        "moduleType": {
+            "kind": 128,
+            "methods": [
+                {
+                    "env": { "required": false },
+                    "kind": 64,
+                    "name": "increment",
+                    "required": true,
+                    "type": "Method",
+                },
+                {
+                    "kind": 64,
+                    "name": "get",
+                    "required": true,
+                    "type": "Method",
+                },
+            ],
+            "type": "Module",
+        },
+        "version": "0.1",
+    }))
+    .unwrap();
+
+    let mismatches = match package(manifest(abi)).validate() {
+        Err(PluginError::AbiMismatchError(mismatches)) => mismatches,
+        result => panic!("Expected ABI mismatches, got: {result:?}"),
+    };
+    assert_eq!(
+        mismatches,
+        vec![
+            "env of method 'increment' is optional in the ABI but required in the implementation",
+            "method 'get' of the ABI isn't implemented",
+            "method 'increment' takes an env but the ABI has no env type",
+            "method 'reset' isn't declared in the ABI",
+        ]
+    );
+}
+
+#[test]
+fn reports_env_type_mismatches() {
+    let mut abi = JSON::to_value(CounterPlugin::get_abi()).unwrap();
+    abi["envType"]["type"] = JSON::json!("Config");
+    let abi = JSON::from_value::<WrapManifestAbi>(abi).unwrap();
+
+    let mismatches = match package(manifest(abi)).validate() {
+        Err(PluginError::AbiMismatchError(mismatches)) => mismatches,
+        result => panic!("Expected ABI mismatches, got: {result:?}"),
+    };
+    assert_eq!(
+        mismatches,
+        vec!["env of method 'increment' is of type 'Env' but the env type of the ABI is 'Config'"]
+    );
+}
@@ -0,0 +1,86 @@
+use polywrap_core::package::WrapPackage;
+use polywrap_plugin::{implementor::WrapObject, PluginBuilder};
+use serde::{Deserialize, Serialize};
+use wrap_manifest_schemas::{abi::Abi, validate::validate_wrap_manifest, versions::AnyManifest};
+
+#[derive(Debug, Deserialize, Serialize, WrapObject)]
+pub struct ArgsAdd {
+    pub a: i32,
+    pub b: i32,
+}
+
+#[derive(Debug, Deserialize, Serialize, WrapObject)]
+pub struct ArgsGreet {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, WrapObject)]
+pub struct Env {
+    pub greeting: String,
+}
+
+#[test]
+fn generates_abi_of_methods() {
+    let package = PluginBuilder::new()
+        .with_name("math")
+        .method("add", |args: ArgsAdd, _: (), _| Ok(args.a + args.b))
+        .method("greet", |args: ArgsGreet, env: Option<Env>, _| {
+            let greeting = env.map(|env| env.greeting);
+            Ok(format!(
+                "{} {}",
+                greeting.as_deref().unwrap_or("Hello"),
+                args.name
+            ))
+        })
+        .method("welcome", |args: ArgsGreet, env: Env, _| {
+            Ok(format!("{} {}", env.greeting, args.name))
+        })
+        .build();
+
+    let manifest = package.get_manifest(None).unwrap();
+    assert_eq!(manifest.name, "math");
+
+    let abi = Abi::try_from(&manifest.abi).unwrap();
+    let signatures = abi
+        .methods()
+        .iter()
+        .map(|method| {
+            let env = method.env.as_ref().map(|env| env.required);
+            (method.signature(), env)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        signatures,
+        vec![
+            ("add(a: Int!, b: Int!): Int!".to_string(), None),
+            (
+                "greet(name: String!): String!".to_string(),
+                Some(Some(false))
+            ),
+            (
+                "welcome(name: String!): String!".to_string(),
+                Some(Some(true))
+            ),
+        ]
+    );
+    assert_eq!(abi.env.unwrap().type_, "Env");
+
+    let manifest = AnyManifest::WrapManifest01(manifest);
+    assert!(validate_wrap_manifest(&manifest, None).is_ok());
+}
+
+#[test]
+fn built_package_implements_its_abi() {
+    let package = PluginBuilder::new()
+        .method("add", |args: ArgsAdd, _: (), _| Ok(args.a + args.b))
+        .method("greet", |args: ArgsGreet, env: Env, _| {
+            Ok(format!("{} {}", env.greeting, args.name))
+        })
+        .build();
+    assert!(package.validate().is_ok());
+
+    let package = PluginBuilder::new()
+        .method("add", |args: ArgsAdd, _: (), _| Ok(args.a + args.b))
+        .build_concurrent();
+    assert!(package.validate().is_ok());
+}
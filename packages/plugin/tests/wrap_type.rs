@@ -0,0 +1,190 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use polywrap_core::invoker::Invoker;
+use polywrap_plugin::{
+    error::PluginError,
+    implementor::{plugin_impl, WrapEnum, WrapObject},
+    module::PluginModule,
+};
+use serde::{Deserialize, Serialize};
+use wrap_manifest_schemas::{
+    abi::{Abi, TypeKind, TypeRef},
+    validate::validate_wrap_manifest,
+    versions::{AnyManifest, WrapManifest},
+};
+
+/// An item of the inventory.
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
+pub struct Item {
+    /// Name of the item
+    pub name: String,
+    pub kind: Kind,
+    pub tags: Option<Vec<String>>,
+    pub counts: BTreeMap<String, i32>,
+    #[serde(rename = "parentItem")]
+    pub parent_item: Option<Box<Item>>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, WrapEnum)]
+pub enum Kind {
+    SMALL,
+    LARGE,
+    _MAX_,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
+pub struct Env {
+    pub prefix: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
+pub struct ArgsEcho {
+    pub item: Item,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
+pub struct ArgsFindByKind {
+    pub kind: Kind,
+}
+
+pub trait Module: PluginModule {
+    fn echo(
+        &mut self,
+        args: &ArgsEcho,
+        invoker: Arc<dyn Invoker>,
+        env: Env,
+    ) -> Result<Item, PluginError>;
+
+    fn find_by_kind(
+        &mut self,
+        args: &ArgsFindByKind,
+        invoker: Arc<dyn Invoker>,
+        env: Option<Env>,
+    ) -> Result<Option<Vec<Item>>, PluginError>;
+}
+
+#[derive(Debug)]
+struct ItemsPlugin;
+
+#[plugin_impl(abi)]
+impl Module for ItemsPlugin {
+    fn echo(&mut self, args: &ArgsEcho, _: Arc<dyn Invoker>, _: Env) -> Result<Item, PluginError> {
+        Ok(args.item.clone())
+    }
+
+    fn find_by_kind(
+        &mut self,
+        _: &ArgsFindByKind,
+        _: Arc<dyn Invoker>,
+        _: Option<Env>,
+    ) -> Result<Option<Vec<Item>>, PluginError> {
+        Ok(None)
+    }
+}
+
+fn get_manifest() -> WrapManifest {
+    WrapManifest {
+        name: "items".to_string(),
+        type_: "plugin".to_string(),
+        version: "0.1".to_string(),
+        abi: ItemsPlugin::get_abi(),
+    }
+}
+
+fn scalar(name: &str, required: bool) -> TypeRef {
+    TypeRef {
+        kind: TypeKind::Scalar(name.to_string()),
+        required,
+    }
+}
+
+#[test]
+fn derives_methods_from_signatures() {
+    let abi = Abi::try_from(&ItemsPlugin::get_abi()).unwrap();
+
+    let signatures = abi
+        .methods()
+        .iter()
+        .map(|method| (method.signature(), method.env.as_ref().unwrap().required))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        signatures,
+        vec![
+            ("echo(item: Item!): Item!".to_string(), Some(true)),
+            ("findByKind(kind: Kind!): [Item!]".to_string(), Some(false)),
+        ]
+    );
+}
+
+#[test]
+fn derives_objects_enums_and_env() {
+    let abi = Abi::try_from(&ItemsPlugin::get_abi()).unwrap();
+
+    assert_eq!(abi.objects.len(), 1);
+    let item = &abi.objects[0];
+    assert_eq!(item.type_, "Item");
+    assert_eq!(item.comment.as_deref(), Some("An item of the inventory."));
+
+    let properties = item.properties.as_deref().unwrap();
+    let property_types = properties
+        .iter()
+        .map(|property| (property.name.clone().unwrap(), property.type_ref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        property_types,
+        vec![
+            ("name".to_string(), scalar("String", true)),
+            (
+                "kind".to_string(),
+                TypeRef {
+                    kind: TypeKind::Enum("Kind".to_string()),
+                    required: true
+                }
+            ),
+            (
+                "tags".to_string(),
+                TypeRef {
+                    kind: TypeKind::Array(Box::new(scalar("String", true))),
+                    required: false
+                }
+            ),
+            (
+                "counts".to_string(),
+                TypeRef {
+                    kind: TypeKind::Map {
+                        key: "String".to_string(),
+                        value: Box::new(scalar("Int", true))
+                    },
+                    required: true
+                }
+            ),
+            (
+                "parentItem".to_string(),
+                TypeRef {
+                    kind: TypeKind::Object("Item".to_string()),
+                    required: false
+                }
+            ),
+        ]
+    );
+    assert_eq!(properties[0].comment.as_deref(), Some("Name of the item"));
+
+    assert_eq!(abi.enums.len(), 1);
+    assert_eq!(abi.enums[0].type_, "Kind");
+    assert_eq!(
+        abi.enums[0].constants,
+        Some(vec!["SMALL".to_string(), "LARGE".to_string()])
+    );
+
+    let env = abi.env.unwrap();
+    assert_eq!(env.type_, "Env");
+    assert_eq!(env.properties.unwrap().len(), 1);
+}
+
+#[test]
+fn derived_manifest_matches_schema() {
+    let manifest = AnyManifest::WrapManifest01(get_manifest());
+
+    assert!(validate_wrap_manifest(&manifest, None).is_ok());
+}
@@ -5,7 +5,6 @@ use ethers::{
 };
 use std::fmt::Debug;
 use thiserror::Error;
-use tokio::runtime::Runtime;
 
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum WalletError {
@@ -74,13 +73,11 @@ impl Connection {
         }
     }
 
-    pub fn get_signer(&self) -> Result<LocalWallet, WalletError> {
+    pub async fn get_signer(&self) -> Result<LocalWallet, WalletError> {
         if let Some(s) = &self.signer {
             let wallet = s.parse::<LocalWallet>();
             if let Ok(w) = wallet {
-                let chain_id = self.provider.get_chainid();
-                let runtime = tokio::runtime::Runtime::new().unwrap();
-                let chain_id = Runtime::block_on(&runtime, chain_id).unwrap();
+                let chain_id = self.provider.get_chainid().await.unwrap();
                 Ok(w.with_chain_id(chain_id.as_u64()))
             } else {
                 Err(WalletError::WrongSignerGiven)
@@ -103,10 +100,10 @@ mod test {
         Connection::new(provider, signer).unwrap()
     }
 
-    #[test]
-    fn get_signer() {
+    #[tokio::test]
+    async fn get_signer() {
         let connection = create_connection();
-        let s = connection.get_signer();
+        let s = connection.get_signer().await;
         assert!(s.is_ok());
     }
 }
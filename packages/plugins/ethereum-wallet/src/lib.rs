@@ -13,7 +13,6 @@ use ethers::{
 use polywrap_plugin::*;
 use serde::{Deserialize, Serialize};
 use std::{str::FromStr, sync::Arc};
-use tokio::runtime::Runtime;
 use wrap::module::{
     ArgsRequest, ArgsSignMessage, ArgsSignTransaction, ArgsSignerAddress, ArgsWaitForTransaction,
     Module,
@@ -23,6 +22,7 @@ pub mod connection;
 pub mod connections;
 mod networks;
 mod types;
+#[allow(unused_imports)]
 mod wrap;
 
 pub use types::*;
@@ -92,9 +92,10 @@ impl EthereumWalletPlugin {
 }
 
 #[plugin_impl]
+#[async_trait]
 impl Module for EthereumWalletPlugin {
-    fn request(
-        &mut self,
+    async fn request(
+        &self,
         args: &ArgsRequest,
         _: Arc<dyn Invoker>,
     ) -> Result<JSONString, PluginError> {
@@ -102,30 +103,28 @@ impl Module for EthereumWalletPlugin {
         let provider: &Provider<Http> = &connection.provider;
         let method = args.method.as_str();
         let parameters = Params::sanatize(method, &args.params);
-        let runtime = tokio::runtime::Runtime::new().unwrap();
         match method {
             "eth_signTypedData_v4" => {
-                let signer = connection.get_signer().unwrap();
+                let signer = connection.get_signer().await.unwrap();
                 let typed_data: TypedData = from_value(parameters[1].clone()).unwrap();
-                let hash = Runtime::block_on(&runtime, signer.sign_typed_data(&typed_data));
+                let hash = signer.sign_typed_data(&typed_data).await;
                 let hash = format!("0x{}", hash.unwrap().to_string());
                 Ok(JSONString::new(Value::String(hash)))
             }
             "eth_sendTransaction" => {
-                let signer = connection.get_signer().unwrap();
+                let signer = connection.get_signer().await.unwrap();
                 let tx: TransactionRequest = from_value(parameters[0].clone()).unwrap();
                 let client = SignerMiddleware::new(provider, signer);
-                let hash = Runtime::block_on(&runtime, client.send_transaction(tx, None));
+                let hash = client.send_transaction(tx, None).await;
                 Ok(JSONString::new(Value::String(format!(
                     "{:#?}",
                     hash.unwrap().tx_hash()
                 ))))
             }
             _ => {
-                let response = Runtime::block_on(
-                    &runtime,
-                    provider.request::<Vec<Value>, Value>(method, parameters),
-                );
+                let response = provider
+                    .request::<Vec<Value>, Value>(method, parameters)
+                    .await;
 
                 let result = response.map_err(|e| e.to_string()).unwrap();
 
@@ -138,8 +137,8 @@ impl Module for EthereumWalletPlugin {
         }
     }
 
-    fn wait_for_transaction(
-        &mut self,
+    async fn wait_for_transaction(
+        &self,
         args: &ArgsWaitForTransaction,
         _: Arc<dyn Invoker>,
     ) -> Result<bool, PluginError> {
@@ -155,19 +154,18 @@ impl Module for EthereumWalletPlugin {
         //     pending_transaction.interval(duration);
         // };
 
-        let runtime: Runtime = tokio::runtime::Runtime::new().unwrap();
-        let tx = Runtime::block_on(&runtime, pending_transaction);
+        let tx = pending_transaction.await;
 
         Ok(matches!(tx, Ok(Some(_))))
     }
 
-    fn signer_address(
-        &mut self,
+    async fn signer_address(
+        &self,
         args: &ArgsSignerAddress,
         _: Arc<dyn Invoker>,
     ) -> Result<Option<String>, PluginError> {
         let connection = self.connections.get_connection(args.connection.clone());
-        let signer = connection.get_signer();
+        let signer = connection.get_signer().await;
         match signer {
             Ok(s) => Ok(Some(format!("0x{:x}", s.address()))),
             Err(e) => {
@@ -182,17 +180,16 @@ impl Module for EthereumWalletPlugin {
         }
     }
 
-    fn sign_message(
-        &mut self,
+    async fn sign_message(
+        &self,
         args: &ArgsSignMessage,
         _: Arc<dyn Invoker>,
     ) -> Result<String, PluginError> {
         let connection = self.connections.get_connection(args.connection.clone());
-        let signer = connection.get_signer();
+        let signer = connection.get_signer().await;
         match signer {
             Ok(s) => {
-                let runtime: Runtime = tokio::runtime::Runtime::new().unwrap();
-                let response = Runtime::block_on(&runtime, s.sign_message(args.message.to_vec()));
+                let response = s.sign_message(args.message.to_vec()).await;
                 if let Ok(signature) = response {
                     Ok(format!("{:#}", signature))
                 } else {
@@ -207,20 +204,19 @@ impl Module for EthereumWalletPlugin {
         }
     }
 
-    fn sign_transaction(
-        &mut self,
+    async fn sign_transaction(
+        &self,
         args: &ArgsSignTransaction,
         _: Arc<dyn Invoker>,
     ) -> Result<String, PluginError> {
         let connection = self.connections.get_connection(args.connection.clone());
-        let signer = connection.get_signer();
+        let signer = connection.get_signer().await;
         match signer {
             Ok(s) => {
-                let runtime: Runtime = tokio::runtime::Runtime::new().unwrap();
                 let tx: TypedTransaction =
                     from_value(to_value(args.rlp.to_vec()).unwrap()).unwrap();
 
-                let response = Runtime::block_on(&runtime, s.sign_transaction(&tx));
+                let response = s.sign_transaction(&tx).await;
                 if let Ok(signature) = response {
                     Ok(format!("{:#}", signature))
                 } else {
@@ -3,6 +3,7 @@
 
 use std::sync::Arc;
 use polywrap_plugin::*;
+use std::collections::BTreeMap;
 use serde::{Serialize, Deserialize};
 use super::types::*;
 
@@ -39,14 +40,15 @@ pub struct ArgsSignTransaction {
     pub connection: Option<Connection>,
 }
 
-pub trait Module: PluginModule {
-  fn request(&mut self, args: &ArgsRequest, invoker: Arc<dyn Invoker>) -> Result<JSONString, PluginError>;
+#[async_trait]
+pub trait Module: ConcurrentPluginModule {
+  async fn request(&self, args: &ArgsRequest, invoker: Arc<dyn Invoker>) -> Result<JSONString, PluginError>;
 
-  fn wait_for_transaction(&mut self, args: &ArgsWaitForTransaction, invoker: Arc<dyn Invoker>) -> Result<bool, PluginError>;
+  async fn wait_for_transaction(&self, args: &ArgsWaitForTransaction, invoker: Arc<dyn Invoker>) -> Result<bool, PluginError>;
 
-  fn signer_address(&mut self, args: &ArgsSignerAddress, invoker: Arc<dyn Invoker>) -> Result<Option<String>, PluginError>;
+  async fn signer_address(&self, args: &ArgsSignerAddress, invoker: Arc<dyn Invoker>) -> Result<Option<String>, PluginError>;
 
-  fn sign_message(&mut self, args: &ArgsSignMessage, invoker: Arc<dyn Invoker>) -> Result<String, PluginError>;
+  async fn sign_message(&self, args: &ArgsSignMessage, invoker: Arc<dyn Invoker>) -> Result<String, PluginError>;
 
-  fn sign_transaction(&mut self, args: &ArgsSignTransaction, invoker: Arc<dyn Invoker>) -> Result<String, PluginError>;
+  async fn sign_transaction(&self, args: &ArgsSignTransaction, invoker: Arc<dyn Invoker>) -> Result<String, PluginError>;
 }
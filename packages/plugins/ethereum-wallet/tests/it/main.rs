@@ -57,6 +57,7 @@ fn get_client() -> Client {
         resolver: Arc::new(resolver),
         interfaces: None,
         envs: None,
+        ..Default::default()
     })
 }
 
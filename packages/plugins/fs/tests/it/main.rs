@@ -53,6 +53,7 @@ fn get_client() -> Client {
         resolver: Arc::new(resolver),
         interfaces: None,
         envs: None,
+        ..Default::default()
     })
 }
 
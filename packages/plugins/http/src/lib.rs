@@ -1,30 +1,48 @@
-use crate::{
-    parse_request::parse_request, parse_response::parse_response, wrap::wrap_info::get_manifest,
-};
+use crate::{parse_request::parse_request, parse_response::parse_response};
 use multipart::client::lazy::Multipart;
 use polywrap_plugin::*;
+use serde::{Deserialize, Serialize};
 use std::{io::Cursor, sync::Arc};
+use types::{FormDataEntry, Request, Response, ResponseType};
 use ureq::{Request as UreqRequest, Response as UreqResponse};
-use wrap::{
-    module::{ArgsGet, ArgsPost, Module},
-    types::{FormDataEntry, Response, ResponseType},
-};
 pub mod parse_request;
 pub mod parse_response;
-pub mod wrap;
+pub mod types;
 
 pub enum RequestMethod {
     GET,
     POST,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
+pub struct ArgsGet {
+    pub url: String,
+    pub request: Option<Request>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
+pub struct ArgsPost {
+    pub url: String,
+    pub request: Option<Request>,
+}
+
+/// Manifest of the plugin, whose ABI is derived from the types of its methods.
+pub fn get_manifest() -> WrapManifest {
+    WrapManifest {
+        name: "Http".to_string(),
+        type_: "plugin".to_string(),
+        version: "0.1".to_string(),
+        abi: HttpPlugin::get_abi(),
+    }
+}
+
 #[derive(Debug)]
 pub struct HttpPlugin;
 
-#[plugin_impl]
-impl Module for HttpPlugin {
+#[plugin_impl(concurrent, abi)]
+impl HttpPlugin {
     fn get(
-        &mut self,
+        &self,
         args: &ArgsGet,
         _: Arc<dyn Invoker>,
     ) -> Result<Option<Response>, PluginError> {
@@ -44,7 +62,7 @@ impl Module for HttpPlugin {
     }
 
     fn post(
-        &mut self,
+        &self,
         args: &ArgsPost,
         _: Arc<dyn Invoker>,
     ) -> Result<Option<Response>, PluginError> {
@@ -1,4 +1,4 @@
-use crate::{wrap::types::Request, RequestMethod};
+use crate::{types::Request, RequestMethod};
 
 pub fn parse_request(url: &str, request: Option<Request>, method: RequestMethod) -> ureq::Request {
     let mut request_builder = match method {
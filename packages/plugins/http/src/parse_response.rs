@@ -1,7 +1,7 @@
 use std::collections::BTreeMap;
 use std::io::Read;
 
-use crate::wrap::types::{Response, ResponseType};
+use crate::types::{Response, ResponseType};
 use polywrap_plugin::error::PluginError;
 
 pub fn parse_response(
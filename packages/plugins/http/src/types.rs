@@ -0,0 +1,49 @@
+use polywrap_plugin::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
+pub struct Response {
+    pub status: i32,
+    #[serde(rename = "statusText")]
+    pub status_text: String,
+    pub headers: Option<BTreeMap<String, String>>,
+    pub body: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
+pub struct Request {
+    pub headers: Option<BTreeMap<String, String>>,
+    #[serde(rename = "urlParams")]
+    pub url_params: Option<BTreeMap<String, String>>,
+    #[serde(rename = "responseType")]
+    pub response_type: ResponseType,
+    /// The body of the request. If present, the `formData` property will be ignored.
+    pub body: Option<String>,
+    /// An alternative to the standard request body, 'formData' is expected to be in the 'multipart/form-data' format.
+    /// If present, the `body` property is not null, `formData` will be ignored.
+    /// Otherwise, if formData is not null, the following header will be added to the request: 'Content-Type: multipart/form-data'.
+    #[serde(rename = "formData")]
+    pub form_data: Option<Vec<FormDataEntry>>,
+    pub timeout: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
+pub struct FormDataEntry {
+    /// FormData entry key
+    pub name: String,
+    /// If 'type' is defined, value is treated as a base64 byte string
+    pub value: Option<String>,
+    /// File name to report to the server
+    #[serde(rename = "fileName")]
+    pub file_name: Option<String>,
+    /// MIME type (https://developer.mozilla.org/en-US/docs/Web/HTTP/Basics_of_HTTP/MIME_types). Defaults to empty string.
+    #[serde(rename = "type")]
+    pub _type: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, WrapEnum)]
+pub enum ResponseType {
+    TEXT,
+    BINARY,
+}
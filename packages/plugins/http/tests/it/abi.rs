@@ -0,0 +1,34 @@
+use polywrap_http_plugin::get_manifest;
+use polywrap_plugin::*;
+
+/// ABI generated from the GraphQL schema of the plugin, before it was derived from its types.
+const SCHEMA_ABI: &str = include_str!("abi.json");
+
+/// Sorts the object types, which are derived in the order the methods use them,
+/// and trims the indentation of comments, the schema having one indented line.
+fn normalize(value: &mut JSON::Value) {
+    match value {
+        JSON::Value::Object(object) => {
+            if let Some(JSON::Value::Array(object_types)) = object.get_mut("objectTypes") {
+                object_types.sort_by_key(|object_type| object_type["type"].to_string());
+            }
+            if let Some(JSON::Value::String(comment)) = object.get_mut("comment") {
+                *comment = comment.trim_start().to_string();
+            }
+            object.values_mut().for_each(normalize);
+        }
+        JSON::Value::Array(values) => values.iter_mut().for_each(normalize),
+        _ => {}
+    }
+}
+
+#[test]
+fn derived_abi_matches_schema_abi() {
+    let mut abi = JSON::to_value(get_manifest().abi).unwrap();
+    let schema_abi = JSON::from_str::<WrapManifestAbi>(SCHEMA_ABI).unwrap();
+    let mut schema_abi = JSON::to_value(schema_abi).unwrap();
+
+    normalize(&mut abi);
+    normalize(&mut schema_abi);
+    assert_eq!(abi, schema_abi);
+}
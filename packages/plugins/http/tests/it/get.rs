@@ -1,4 +1,4 @@
-use polywrap_http_plugin::wrap::types::Response;
+use polywrap_http_plugin::types::Response;
 use polywrap_plugin::*;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -6,6 +6,7 @@ use polywrap_http_plugin::HttpPlugin;
 use polywrap_plugin::*;
 use std::sync::Arc;
 
+mod abi;
 mod get;
 mod post;
 
@@ -22,5 +23,6 @@ pub fn get_client() -> Client {
         resolver: Arc::new(resolver),
         interfaces: None,
         envs: None,
+        ..Default::default()
     })
 }
@@ -1,5 +1,5 @@
+use polywrap_http_plugin::types::{Request, Response};
 use polywrap_plugin::*;
-use polywrap_http_plugin::wrap::types::{Request, Response};
 use serde::{Deserialize, Serialize};
 
 use crate::get_client;
@@ -29,7 +29,7 @@ fn post_method() {
                 &to_vec(&ArgsPost {
                     url: "https://jsonplaceholder.typicode.com/todos".to_string(),
                     request: Request {
-                        response_type: polywrap_http_plugin::wrap::types::ResponseType::TEXT,
+                        response_type: polywrap_http_plugin::types::ResponseType::TEXT,
                         body: Some(body.to_string()),
                         headers: None,
                         url_params: None,
@@ -78,7 +78,7 @@ mod tests {
         let logger_plugin = LoggerPlugin::new(None);
         let logger_package: PluginPackage<LoggerPlugin> = PluginPackage::from(logger_plugin);
         builder.add_package(uri!("plugin/logger"), Arc::new(logger_package));
-        let client = Client::new(builder.build());
+        let client = Client::new(builder.build().unwrap());
         let result = client.invoke::<bool>(
             &uri!("plugin/logger"),
             "log",
@@ -4,6 +4,7 @@ use polywrap_core::{
     invoker::Invoker,
     resolution::uri_resolution_context::{UriPackageOrWrapper, UriResolutionContext},
     resolution::uri_resolver::UriResolver,
+    telemetry::try_resolve_uri_in_span,
     uri::Uri,
 };
 use std::sync::{Arc, Mutex};
@@ -29,15 +30,20 @@ impl UriResolver for BaseResolver {
         invoker: Arc<dyn Invoker>,
         resolution_context: Arc<Mutex<UriResolutionContext>>,
     ) -> Result<UriPackageOrWrapper, Error> {
-        let redirected_uri = self.static_resolver.try_resolve_uri(
+        let redirected_uri = try_resolve_uri_in_span(
+            self.static_resolver.as_ref(),
             uri,
             invoker.clone(),
             resolution_context.clone(),
         )?;
 
         if let UriPackageOrWrapper::Uri(redirected_uri) = redirected_uri {
-            self.fs_resolver
-                .try_resolve_uri(&redirected_uri, invoker, resolution_context)
+            try_resolve_uri_in_span(
+                self.fs_resolver.as_ref(),
+                &redirected_uri,
+                invoker,
+                resolution_context,
+            )
         } else {
             Ok(redirected_uri)
         }
@@ -45,7 +45,10 @@ impl UriResolver for PackageResolver {
                 description: Some(format!("Package ({})", self.uri)),
                 result: match &result {
                     Ok(r) => Ok(r.clone()),
-                    Err(e) => Err(Error::ResolutionError(e.to_string())),
+                    Err(e) => Err(Error::ResolutionError {
+                        message: e.to_string(),
+                        source: Some(Box::new(e.clone())),
+                    }),
                 },
                 sub_history: None,
             });
@@ -6,6 +6,7 @@ use polywrap_core::{
         uri_resolution_context::{UriPackageOrWrapper, UriResolutionContext, UriResolutionStep},
         uri_resolver::UriResolver,
     },
+    telemetry::try_resolve_uri_in_span,
     uri::Uri,
 };
 use std::fmt;
@@ -66,9 +67,12 @@ impl UriResolver for PackageToWrapperResolver {
         invoker: Arc<dyn Invoker>,
         resolution_context: Arc<Mutex<UriResolutionContext>>,
     ) -> Result<UriPackageOrWrapper, Error> {
-        let result =
-            self.resolver
-                .try_resolve_uri(uri, invoker.clone(), resolution_context.clone());
+        let result = try_resolve_uri_in_span(
+            self.resolver.as_ref(),
+            uri,
+            invoker.clone(),
+            resolution_context.clone(),
+        );
         let final_result = match result {
             Ok(uri_package_or_wrapper) => self.package_to_wrapper(uri_package_or_wrapper),
             Err(_) => result,
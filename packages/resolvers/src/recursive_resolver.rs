@@ -6,6 +6,7 @@ use polywrap_core::{
     invoker::Invoker,
     resolution::uri_resolution_context::{UriPackageOrWrapper, UriResolutionContext},
     resolution::uri_resolver::UriResolver,
+    telemetry::try_resolve_uri_in_span,
     uri::Uri,
 };
 
@@ -74,9 +75,12 @@ impl UriResolver for RecursiveResolver {
             Err(Error::ResolverError("Infinite loop error".to_string()))
         } else {
             resolution_context.lock().unwrap().start_resolving(uri);
-            let resolver_result =
-                self.resolver
-                    .try_resolve_uri(uri, invoker.clone(), resolution_context.clone());
+            let resolver_result = try_resolve_uri_in_span(
+                self.resolver.as_ref(),
+                uri,
+                invoker.clone(),
+                resolution_context.clone(),
+            );
 
             let result = self.try_resolve_again_if_redirect(
                 resolver_result,
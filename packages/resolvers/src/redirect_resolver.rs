@@ -38,7 +38,10 @@ impl UriResolver for RedirectResolver {
                 description: Some(format!("Redirect ({} - {})", self.from, self.to)),
                 result: match &result {
                     Ok(r) => Ok(r.clone()),
-                    Err(e) => Err(Error::ResolutionError(e.to_string())),
+                    Err(e) => Err(Error::ResolutionError {
+                        message: e.to_string(),
+                        source: Some(Box::new(e.clone())),
+                    }),
                 },
                 sub_history: None,
             });
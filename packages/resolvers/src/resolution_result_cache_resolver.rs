@@ -8,6 +8,7 @@ use polywrap_core::{
         uri_resolution_context::{UriPackageOrWrapper, UriResolutionContext, UriResolutionStep},
         uri_resolver::UriResolver,
     },
+    telemetry::try_resolve_uri_in_span,
     uri::Uri,
 };
 use std::fmt;
@@ -89,9 +90,12 @@ impl UriResolver for ResolutionResultCacheResolver {
             .unwrap()
             .create_sub_history_context();
         let sub_context = Arc::new(Mutex::new(sub_context));
-        let result = self
-            .resolver
-            .try_resolve_uri(uri, invoker.clone(), sub_context.clone());
+        let result = try_resolve_uri_in_span(
+            self.resolver.as_ref(),
+            uri,
+            invoker.clone(),
+            sub_context.clone(),
+        );
 
         if result.is_ok() {
             let skip_cache = (self.skip_cache)(uri);
@@ -8,6 +8,7 @@ use polywrap_core::{
         uri_resolution_context::{UriPackageOrWrapper, UriResolutionContext},
         uri_resolver::UriResolver,
     },
+    telemetry::try_resolve_uri_in_span,
     uri::Uri,
 };
 
@@ -28,9 +29,12 @@ impl UriResolver for ResolverWithLoopGuard {
         } else {
             resolution_context.lock().unwrap().start_resolving(uri);
 
-            let result = self
-                .resolver
-                .try_resolve_uri(uri, invoker, resolution_context.clone());
+            let result = try_resolve_uri_in_span(
+                self.resolver.as_ref(),
+                uri,
+                invoker,
+                resolution_context.clone(),
+            );
 
             resolution_context.lock().unwrap().stop_resolving(uri);
 
@@ -52,7 +52,7 @@ impl UriResolver for FilesystemResolver {
                 UriPackageOrWrapper::Package(uri.clone(), Arc::new(wasm_wrapper));
             Ok(uri_package_or_wrapper)
         } else {
-            Err(Error::ResolutionError(format!(
+            Err(Error::resolution(format!(
                 "Failed to find manifest file: {manifest_search_pattern}"
             )))
         }
@@ -39,13 +39,14 @@ impl UriResolver for UriResolverAggregator {
         let resolver_result =
             self.get_uri_resolvers(uri, invoker.as_ref(), resolution_context.clone());
 
-        if let Ok(resolvers) = resolver_result {
-            self.try_resolve_uri_with_resolvers(uri, invoker, resolvers, resolution_context)
-        } else {
-            //TODO: verify this case.
-            Err(Error::ResolutionError(
-                "Failed to get URI resolvers".to_string(),
-            ))
+        match resolver_result {
+            Ok(resolvers) => {
+                self.try_resolve_uri_with_resolvers(uri, invoker, resolvers, resolution_context)
+            }
+            Err(e) => Err(Error::ResolutionError {
+                message: "Failed to get URI resolvers".to_string(),
+                source: Some(Box::new(e)),
+            }),
         }
     }
 }
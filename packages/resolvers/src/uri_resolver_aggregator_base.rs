@@ -1,6 +1,7 @@
 use std::sync::{Arc, Mutex};
 
 use polywrap_core::error::Error;
+use polywrap_core::telemetry::try_resolve_uri_in_span;
 
 use polywrap_core::{invoker::Invoker, uri::Uri};
 
@@ -35,7 +36,13 @@ pub trait UriResolverAggregatorBase: UriResolver + core::fmt::Debug {
             .create_sub_history_context();
         let sub_context = Arc::new(Mutex::new(sub_context));
         for resolver in resolvers.into_iter() {
-            let result = resolver.try_resolve_uri(uri, invoker.clone(), sub_context.clone());
+            let result = try_resolve_uri_in_span(
+                resolver.as_ref(),
+                uri,
+                invoker.clone(),
+                sub_context.clone(),
+            );
+
             let track_and_return = if let Ok(UriPackageOrWrapper::Uri(result_uri)) = &result {
                 uri.to_string() != result_uri.to_string()
             } else {
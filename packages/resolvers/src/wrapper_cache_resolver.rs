@@ -8,6 +8,7 @@ use polywrap_core::{
         uri_resolution_context::{UriPackageOrWrapper, UriResolutionContext, UriResolutionStep},
         uri_resolver::UriResolver,
     },
+    telemetry::try_resolve_uri_in_span,
     uri::Uri,
     wrapper::Wrapper,
 };
@@ -87,9 +88,12 @@ impl UriResolver for WrapperCacheResolver {
             .unwrap()
             .create_sub_history_context();
         let sub_context = Arc::new(Mutex::new(sub_context));
-        let result = self
-            .resolver
-            .try_resolve_uri(uri, invoker.clone(), sub_context.clone());
+        let result = try_resolve_uri_in_span(
+            self.resolver.as_ref(),
+            uri,
+            invoker.clone(),
+            sub_context.clone(),
+        );
 
         if result.is_ok() {
             if let UriPackageOrWrapper::Wrapper(_, wrapper) = result.clone().unwrap() {
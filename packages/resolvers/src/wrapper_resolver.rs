@@ -42,7 +42,10 @@ impl UriResolver for WrapperResolver {
                 description: Some(format!("Wrapper ({uri})")),
                 result: match &result {
                     Ok(r) => Ok(r.clone()),
-                    Err(e) => Err(Error::ResolutionError(e.to_string())),
+                    Err(e) => Err(Error::ResolutionError {
+                        message: e.to_string(),
+                        source: Some(Box::new(e.clone())),
+                    }),
                 },
                 sub_history: None,
             });
@@ -0,0 +1,57 @@
+use polywrap_core::invoker::Invoker;
+use polywrap_plugin::error::PluginError;
+use polywrap_plugin::{async_trait, implementor::plugin_impl, module::PluginModule};
+
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    thread::{self, ThreadId},
+    time::Duration,
+};
+use wrap_manifest_schemas::versions::WrapManifest;
+
+use super::concurrent_sleep::ArgsSleep;
+
+#[async_trait]
+pub trait Module: PluginModule {
+    async fn sleep(&self, args: &ArgsSleep, invoker: Arc<dyn Invoker>)
+        -> Result<bool, PluginError>;
+}
+
+/// Sleeps asynchronously in every invocation, recording the threads it runs on.
+#[derive(Debug, Default)]
+pub struct AsyncSleepPlugin {
+    /// Threads polling the invocations
+    pub invoked_on: Mutex<Vec<ThreadId>>,
+    /// Names of the threads running a task spawned by the invocations
+    pub spawned_on: Mutex<Vec<Option<String>>>,
+}
+
+#[plugin_impl(abi)]
+#[async_trait]
+impl Module for AsyncSleepPlugin {
+    async fn sleep(
+        &self,
+        args: &ArgsSleep,
+        _invoker: Arc<dyn Invoker>,
+    ) -> Result<bool, PluginError> {
+        self.invoked_on.lock().unwrap().push(thread::current().id());
+
+        let spawned_on = tokio::spawn(async { thread::current().name().map(String::from) })
+            .await
+            .unwrap();
+        self.spawned_on.lock().unwrap().push(spawned_on);
+
+        tokio::time::sleep(Duration::from_millis(args.ms.into())).await;
+        Ok(true)
+    }
+}
+
+pub fn get_manifest() -> WrapManifest {
+    WrapManifest {
+        name: "AsyncSleep".to_string(),
+        type_: "plugin".to_string(),
+        version: "0.1".to_string(),
+        abi: AsyncSleepPlugin::get_abi(),
+    }
+}
@@ -0,0 +1,54 @@
+use polywrap_core::invoker::Invoker;
+use polywrap_plugin::error::PluginError;
+use polywrap_plugin::{
+    implementor::{plugin_impl, WrapObject},
+    module::ConcurrentPluginModule,
+};
+use serde::{Deserialize, Serialize};
+
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread::sleep,
+    time::Duration,
+};
+use wrap_manifest_schemas::versions::WrapManifest;
+
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
+pub struct ArgsSleep {
+    pub ms: u32,
+}
+
+pub trait Module: ConcurrentPluginModule {
+    fn sleep(&self, args: &ArgsSleep, invoker: Arc<dyn Invoker>) -> Result<bool, PluginError>;
+}
+
+/// Sleeps in every invocation, tracking how many of them run at the same time.
+#[derive(Debug, Default)]
+pub struct ConcurrentSleepPlugin {
+    in_flight: AtomicUsize,
+    pub max_in_flight: AtomicUsize,
+}
+
+#[plugin_impl(concurrent, abi)]
+impl Module for ConcurrentSleepPlugin {
+    fn sleep(&self, args: &ArgsSleep, _invoker: Arc<dyn Invoker>) -> Result<bool, PluginError> {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        sleep(Duration::from_millis(args.ms.into()));
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(true)
+    }
+}
+
+pub fn get_manifest() -> WrapManifest {
+    WrapManifest {
+        name: "ConcurrentSleep".to_string(),
+        type_: "plugin".to_string(),
+        version: "0.1".to_string(),
+        abi: ConcurrentSleepPlugin::get_abi(),
+    }
+}
@@ -1,15 +1,18 @@
 use polywrap_core::invoker::Invoker;
 use polywrap_plugin::error::PluginError;
-use polywrap_plugin::{implementor::plugin_impl, module::PluginModule, JSON};
+use polywrap_plugin::{
+    implementor::{plugin_impl, WrapObject},
+    module::PluginModule,
+};
 use serde::{Deserialize, Serialize};
 
 use std::{fmt::Debug, sync::Arc, thread::sleep, time::Duration};
-use wrap_manifest_schemas::versions::{WrapManifest, WrapManifestAbi};
+use wrap_manifest_schemas::versions::WrapManifest;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
 pub struct ArgsGetData {}
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
 pub struct ArgsSetData {
     pub value: i32,
 }
@@ -33,7 +36,7 @@ pub struct MemoryStoragePlugin {
     pub value: i32,
 }
 
-#[plugin_impl]
+#[plugin_impl(abi)]
 impl Module for MemoryStoragePlugin {
     fn get_data(
         &mut self,
@@ -60,10 +63,6 @@ pub fn get_manifest() -> WrapManifest {
         name: "MemoryStorage".to_string(),
         type_: "plugin".to_string(),
         version: "0.1".to_string(),
-        abi: JSON::from_value::<WrapManifestAbi>(JSON::json!({
-          "moduleType": {},
-          "version": "0.1"
-        }))
-        .unwrap(),
+        abi: MemoryStoragePlugin::get_abi(),
     }
 }
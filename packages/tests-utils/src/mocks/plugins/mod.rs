@@ -1,5 +1,11 @@
+pub mod async_sleep;
+pub use async_sleep::AsyncSleepPlugin;
+
+pub mod concurrent_sleep;
+pub use concurrent_sleep::{ArgsSleep, ConcurrentSleepPlugin};
+
 pub mod memory_storage;
-pub use memory_storage::*;
+pub use memory_storage::{ArgsGetData, ArgsSetData, MemoryStoragePlugin};
 
 pub mod with_env;
-pub use with_env::*;
\ No newline at end of file
+pub use with_env::{Env, GetEnvArgs, PluginEnv};
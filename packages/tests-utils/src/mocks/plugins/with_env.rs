@@ -1,17 +1,20 @@
 use polywrap_core::invoker::Invoker;
 use polywrap_plugin::error::PluginError;
-use polywrap_plugin::{implementor::plugin_impl, module::PluginModule, JSON};
+use polywrap_plugin::{
+    implementor::{plugin_impl, WrapObject},
+    module::PluginModule,
+};
 use serde::{Deserialize, Serialize};
 
 use std::{fmt::Debug, sync::Arc};
-use wrap_manifest_schemas::versions::{WrapManifest, WrapManifestAbi};
+use wrap_manifest_schemas::versions::WrapManifest;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
 pub struct GetEnvArgs {
     key: String,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, WrapObject)]
 pub struct Env {
     foo: String,
 }
@@ -28,7 +31,7 @@ pub trait Module: PluginModule {
     ) -> Result<bool, PluginError>;
 }
 
-#[plugin_impl]
+#[plugin_impl(abi)]
 impl Module for PluginEnv {
     fn check_env_is_bar(
         &mut self,
@@ -53,6 +56,6 @@ pub fn get_manifest() -> WrapManifest {
         name: "env".to_string(),
         type_: "plugin".to_string(),
         version: "0.1".to_string(),
-        abi: JSON::from_value::<WrapManifestAbi>(JSON::json!({})).unwrap(),
+        abi: PluginEnv::get_abi(),
     }
 }
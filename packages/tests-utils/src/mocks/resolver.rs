@@ -28,7 +28,7 @@ impl UriResolver for MockResolver {
         if uri.to_string() == *"wrap://wrap/mock" {
             Ok(get_mock_uri_package_or_wrapper())
         } else {
-            Err(Error::ResolutionError("Not Found".to_string()))
+            Err(Error::resolution("Not Found"))
         }
     }
 }
@@ -57,7 +57,7 @@ impl UriResolver for DifferentMockResolver {
         if uri.to_string() == *"wrap://wrap/mock" {
             Ok(get_mock_uri_package_or_wrapper())
         } else {
-            Err(Error::ResolutionError("Not Found".to_string()))
+            Err(Error::resolution("Not Found"))
         }
     }
 }
@@ -0,0 +1,58 @@
+//! Measures the overhead of the metering middleware on branch-heavy code.
+//!
+//! Run with `cargo run --release -p polywrap_wasm --example metering_overhead`.
+
+use std::{sync::Arc, time::Instant};
+
+use polywrap_wasm::runtime::metering::Metering;
+use wasmer::{imports, sys::EngineBuilder, CompilerConfig, Cranelift, Instance, Module, Store};
+
+const ITERATIONS: i32 = 100_000_000;
+const RUNS: u32 = 5;
+
+// Sums the numbers below `n` that are divisible by 3, branching on every number
+const MODULE: &str = r#"
+    (module
+      (func (export "run") (param $n i32) (result i32)
+        (local $i i32)
+        (local $sum i32)
+        (block $done
+          (loop $continue
+            (br_if $done (i32.ge_u (local.get $i) (local.get $n)))
+            (if (i32.eqz (i32.rem_u (local.get $i) (i32.const 3)))
+              (then (local.set $sum (i32.add (local.get $sum) (local.get $i)))))
+            (local.set $i (i32.add (local.get $i) (i32.const 1)))
+            (br $continue)))
+        (local.get $sum)))
+    "#;
+
+fn run(metered: bool) -> f64 {
+    let mut compiler = Cranelift::default();
+    if metered {
+        compiler.push_middleware(Arc::new(Metering::new()));
+    }
+    let mut store = Store::new(EngineBuilder::new(compiler));
+    let module = Module::new(&store, MODULE).unwrap();
+    let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+    let run = instance
+        .exports
+        .get_typed_function::<i32, i32>(&store, "run")
+        .unwrap();
+
+    let mut fastest = f64::MAX;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        run.call(&mut store, ITERATIONS).unwrap();
+        fastest = fastest.min(start.elapsed().as_secs_f64());
+    }
+    fastest
+}
+
+fn main() {
+    let unmetered = run(false);
+    let metered = run(true);
+
+    println!("unmetered: {:.3}s", unmetered);
+    println!("metered:   {:.3}s", metered);
+    println!("overhead:  {:.1}%", (metered / unmetered - 1.0) * 100.0);
+}
@@ -24,10 +24,17 @@ pub enum WrapperError {
     ModuleSerializeError(#[from] wasmer::SerializeError),
     #[error("`{0}`")]
     ModuleDeserializeError(#[from] wasmer::DeserializeError),
+    #[error("`{0}`")]
+    ResourceLimitError(String),
 }
 
 impl From<WrapperError> for polywrap_core::error::Error {
     fn from(error: WrapperError) -> Self {
-        polywrap_core::error::Error::WrapperError(error.to_string())
+        match error {
+            WrapperError::ResourceLimitError(message) => {
+                polywrap_core::error::Error::ResourceLimitError(message)
+            }
+            _ => polywrap_core::error::Error::wrapper(error.to_string()),
+        }
     }
 }
@@ -1,5 +1,6 @@
 pub mod error;
 pub mod file_reader;
+pub mod module_cache;
 pub mod runtime;
 pub mod wasm_module;
 pub mod wasm_package;
@@ -0,0 +1,224 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::WrapperError,
+    runtime::engine::WasmEngine,
+    wasm_module::{CompiledWasmModule, SerializedWasmModule},
+};
+
+/// Storage for compiled modules, keyed by the bytecode they were compiled from
+/// and the engine that compiled them.
+pub trait ModuleCache: Send + Sync {
+    /// Returns the module compiled by `engine` from `wasm_bytes`, if it's in the cache.
+    fn get(&self, engine: &dyn WasmEngine, wasm_bytes: &[u8]) -> Option<CompiledWasmModule>;
+    /// Stores the module compiled by `engine` from `wasm_bytes`.
+    fn set(
+        &self,
+        engine: &dyn WasmEngine,
+        wasm_bytes: &[u8],
+        module: &CompiledWasmModule,
+    ) -> Result<(), WrapperError>;
+}
+
+static DEFAULT_MODULE_CACHE: Mutex<Option<Arc<dyn ModuleCache>>> = Mutex::new(None);
+
+/// Sets the cache used by the `WasmPackage`s that weren't given one explicitly.
+pub fn set_default_module_cache(cache: Option<Arc<dyn ModuleCache>>) {
+    *DEFAULT_MODULE_CACHE.lock().unwrap() = cache;
+}
+
+/// Returns the cache used by the `WasmPackage`s that weren't given one explicitly.
+pub fn default_module_cache() -> Option<Arc<dyn ModuleCache>> {
+    DEFAULT_MODULE_CACHE.lock().unwrap().clone()
+}
+
+/// Content-addressed cache of compiled modules, stored on disk.
+///
+/// Entries live in `<directory>/<engine key>/<sha256 of the bytecode>`. The engine key
+/// is derived from `WasmEngine::key`, so a module is never loaded by an engine
+/// other than the one that compiled it.
+/// Entries of other engines are kept, in case other processes still use them,
+/// but they are the first to be evicted once the cache outgrows its maximum size.
+pub struct FileSystemModuleCache {
+    directory: PathBuf,
+    max_size: u64,
+}
+
+impl FileSystemModuleCache {
+    /// Creates a cache in `directory`, holding at most `max_size` bytes of compiled modules.
+    pub fn new(directory: impl Into<PathBuf>, max_size: u64) -> Self {
+        Self {
+            directory: directory.into(),
+            max_size,
+        }
+    }
+
+    /// Returns the directory holding the entries of `engine`.
+    pub fn engine_directory(&self, engine: &dyn WasmEngine) -> PathBuf {
+        self.directory.join(engine_key(engine))
+    }
+
+    /// Returns the total size in bytes of the entries in the cache, of every engine.
+    pub fn size(&self) -> u64 {
+        self.entries("").iter().map(|entry| entry.size).sum()
+    }
+
+    fn entry_path(&self, engine: &dyn WasmEngine, wasm_bytes: &[u8]) -> PathBuf {
+        self.engine_directory(engine)
+            .join(to_hex(&Sha256::digest(wasm_bytes)))
+    }
+
+    fn entries(&self, engine_key: &str) -> Vec<CacheEntry> {
+        let Ok(engine_directories) = fs::read_dir(&self.directory) else {
+            return vec![];
+        };
+
+        engine_directories
+            .flatten()
+            .filter(|directory| directory.path().is_dir())
+            .flat_map(|directory| {
+                let current_engine = directory.file_name() == engine_key;
+                fs::read_dir(directory.path())
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .filter_map(move |file| {
+                        let metadata = file.metadata().ok()?;
+                        Some(CacheEntry {
+                            path: file.path(),
+                            size: metadata.len(),
+                            modified: metadata.modified().unwrap_or(UNIX_EPOCH),
+                            current_engine,
+                        })
+                    })
+            })
+            .collect()
+    }
+
+    // Removes entries until the cache fits within its maximum size,
+    // starting with the ones of engines other than `engine`, then the oldest ones.
+    fn evict(&self, engine: &dyn WasmEngine) {
+        let engine_key = engine_key(engine);
+        let mut entries = self.entries(&engine_key);
+        let mut size: u64 = entries.iter().map(|entry| entry.size).sum();
+
+        entries.sort_by_key(|entry| (entry.current_engine, entry.modified));
+
+        for entry in entries {
+            if size <= self.max_size {
+                break;
+            }
+
+            if fs::remove_file(&entry.path).is_ok() {
+                size = size.saturating_sub(entry.size);
+            }
+        }
+
+        // Engine directories left empty are removed, the call fails on the others.
+        if let Ok(engine_directories) = fs::read_dir(&self.directory) {
+            for directory in engine_directories.flatten() {
+                if directory.file_name() != engine_key.as_str() {
+                    let _ = fs::remove_dir(directory.path());
+                }
+            }
+        }
+    }
+}
+
+impl ModuleCache for FileSystemModuleCache {
+    fn get(&self, engine: &dyn WasmEngine, wasm_bytes: &[u8]) -> Option<CompiledWasmModule> {
+        let path = self.entry_path(engine, wasm_bytes);
+        let bytes = fs::read(&path).ok()?;
+
+        let module = decode_entry(&bytes)
+            .map(SerializedWasmModule::deserialize_from_storage)
+            .and_then(|serialized_module| serialized_module.deserialize_with_engine(engine).ok());
+
+        // Entries that can't be loaded are corrupted, so they are removed
+        // to be replaced by the caller.
+        if module.is_none() {
+            let _ = fs::remove_file(&path);
+        }
+
+        module
+    }
+
+    fn set(
+        &self,
+        engine: &dyn WasmEngine,
+        wasm_bytes: &[u8],
+        module: &CompiledWasmModule,
+    ) -> Result<(), WrapperError> {
+        let entry = encode_entry(&module.serialize()?.serialize_for_storage());
+        if entry.len() as u64 > self.max_size {
+            return Ok(());
+        }
+
+        let path = self.entry_path(engine, wasm_bytes);
+        fs::create_dir_all(self.engine_directory(engine))?;
+
+        // The entry is written to a temporary file first, so that other processes
+        // never read a partially written entry.
+        let temp_path = temp_path(&path);
+        fs::write(&temp_path, entry)?;
+        if let Err(error) = fs::rename(&temp_path, &path) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(error.into());
+        }
+
+        self.evict(engine);
+
+        Ok(())
+    }
+}
+
+struct CacheEntry {
+    path: PathBuf,
+    size: u64,
+    modified: SystemTime,
+    current_engine: bool,
+}
+
+// Name of the directory holding the entries of an engine
+fn engine_key(engine: &dyn WasmEngine) -> String {
+    to_hex(&Sha256::digest(engine.key().as_bytes())[..16])
+}
+
+// Entries are prefixed with the sha256 of their content, so that
+// truncated or corrupted entries are never deserialized.
+fn encode_entry(content: &[u8]) -> Vec<u8> {
+    let mut entry = Sha256::digest(content).to_vec();
+    entry.extend_from_slice(content);
+    entry
+}
+
+fn decode_entry(entry: &[u8]) -> Option<&[u8]> {
+    const CHECKSUM_SIZE: usize = 32;
+
+    if entry.len() <= CHECKSUM_SIZE {
+        return None;
+    }
+
+    let (checksum, content) = entry.split_at(CHECKSUM_SIZE);
+    (Sha256::digest(content).as_slice() == checksum).then_some(content)
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.subsec_nanos())
+        .unwrap_or_default();
+
+    path.with_extension(format!("tmp-{}-{nanos}", std::process::id()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
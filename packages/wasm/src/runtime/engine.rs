@@ -0,0 +1,80 @@
+use std::sync::{Arc, Mutex};
+
+use polywrap_core::resource_limits::ResourceLimits;
+
+use crate::error::WrapperError;
+
+use super::{instance::State, interrupt::InterruptHandle};
+
+/// Wasm engine compiling and running wraps.
+pub trait WasmEngine: Send + Sync {
+    /// Identifies the engine, its version and configuration, and the compilation target.
+    /// Modules serialized by an engine can only be deserialized by engines with the same key.
+    fn key(&self) -> String;
+
+    /// Compiles a module from bytecode.
+    fn compile(&self, bytes: &[u8]) -> Result<Arc<dyn EngineModule>, WrapperError>;
+
+    /// Deserializes a module serialized by `EngineModule::serialize`.
+    ///
+    /// # Safety
+    ///
+    /// The bytes are loaded as executable code, so they must be trusted
+    /// to come from an engine with the same key.
+    unsafe fn deserialize(&self, bytes: &[u8]) -> Result<Arc<dyn EngineModule>, WrapperError>;
+}
+
+/// Module compiled by a `WasmEngine`.
+pub trait EngineModule: Send + Sync {
+    /// Instantiates the module with the host functions of the WRAP standard,
+    /// bounded by the given resource limits.
+    fn instantiate(
+        &self,
+        memory_initial_limits: u8,
+        state: Arc<Mutex<State>>,
+        resource_limits: &ResourceLimits,
+    ) -> Result<Box<dyn EngineInstance>, WrapperError>;
+
+    fn serialize(&self) -> Result<Vec<u8>, WrapperError>;
+}
+
+/// Instance of an `EngineModule`.
+pub trait EngineInstance: Send {
+    /// Calls an exported function, returning true if it returned 1.
+    fn call_export(&mut self, name: &str, params: &[i32]) -> Result<bool, WrapperError>;
+
+    fn read_memory(&mut self, offset: u32, length: u32) -> Result<Vec<u8>, WrapperError>;
+
+    fn write_memory(&mut self, offset: u32, data: &[u8]) -> Result<(), WrapperError>;
+
+    fn state(&self) -> Arc<Mutex<State>>;
+
+    fn max_memory_pages(&self) -> Option<u32>;
+
+    /// Returns the amount of fuel consumed so far, or `None` if it isn't metered.
+    fn fuel_consumed(&mut self) -> Option<u64>;
+
+    /// Returns true if execution trapped because its fuel ran out.
+    fn fuel_exhausted(&mut self) -> bool;
+
+    /// Returns true if the module tried to grow its memory past the maximum number of pages.
+    fn memory_growth_denied(&self) -> bool;
+
+    /// Returns a handle interrupting this instance, or `None` if it can't be interrupted.
+    fn interrupt_handle(&mut self) -> Option<Arc<InterruptHandle>>;
+
+    /// Captures the current state of the instance, to be restored after later invocations.
+    /// Returns false if the state can't be captured, in which case the instance can't be pooled.
+    fn snapshot(&mut self) -> bool;
+
+    /// Restores the instance to its snapshot and releases the state of its last invocation.
+    /// Returns false if the instance can't be restored and must be discarded.
+    fn restore(&mut self) -> bool;
+
+    /// Prepares a restored instance to run a new invocation with the given state and limits.
+    fn prepare(
+        &mut self,
+        state: State,
+        resource_limits: &ResourceLimits,
+    ) -> Result<(), WrapperError>;
+}
@@ -0,0 +1,401 @@
+//! Implementation of the `__wrap_*` functions imported by wraps, shared by every engine.
+//!
+//! Engines register adapters calling these functions with the state of the instance
+//! and access to its memory. An `Err` makes the calling instance trap with its message.
+
+use polywrap_core::{
+    error::WrapAbortInfo, telemetry::record_result, uri::Uri, wrap_log::WrapLogEvent,
+};
+use polywrap_msgpack_serde::to_vec;
+
+use super::instance::{State, SubinvokeImplementationState};
+
+/// Linear memory of the instance calling a host function.
+pub trait HostMemory {
+    fn read(&mut self, offset: u32, length: u32) -> Result<Vec<u8>, String>;
+    fn write(&mut self, offset: u32, data: &[u8]) -> Result<(), String>;
+}
+
+fn read_string(
+    memory: &mut dyn HostMemory,
+    offset: u32,
+    length: u32,
+    function: &str,
+) -> Result<String, String> {
+    String::from_utf8(memory.read(offset, length)?).map_err(|e| format!("{function}: {e}"))
+}
+
+fn parse_uri(uri: String, function: &str) -> Result<Uri, String> {
+    uri.clone()
+        .try_into()
+        .map_err(|_| format!("{function}: invalid uri: {uri}"))
+}
+
+pub fn invoke_args(
+    state: &mut State,
+    memory: &mut dyn HostMemory,
+    method_ptr: u32,
+    args_ptr: u32,
+) -> Result<(), String> {
+    if state.method.is_empty() {
+        return Err("__wrap_invoke_args: method is not set".to_string());
+    }
+
+    if state.args.is_empty() {
+        return Err("__wrap_invoke_args: args is not set".to_string());
+    }
+
+    memory.write(method_ptr, &state.method)?;
+    memory.write(args_ptr, &state.args)
+}
+
+pub fn invoke_result(
+    state: &mut State,
+    memory: &mut dyn HostMemory,
+    offset: u32,
+    length: u32,
+) -> Result<(), String> {
+    state.invoke.result = Some(memory.read(offset, length)?);
+    Ok(())
+}
+
+pub fn invoke_error(
+    state: &mut State,
+    memory: &mut dyn HostMemory,
+    offset: u32,
+    length: u32,
+) -> Result<(), String> {
+    state.invoke.error = Some(read_string(memory, offset, length, "__wrap_invoke_error")?);
+    Ok(())
+}
+
+// Always fails, so the wrap stops running
+#[allow(clippy::too_many_arguments)]
+pub fn abort(
+    state: &mut State,
+    memory: &mut dyn HostMemory,
+    msg_offset: u32,
+    msg_length: u32,
+    file_offset: u32,
+    file_length: u32,
+    line: u32,
+    column: u32,
+) -> Result<(), String> {
+    let msg = read_string(memory, msg_offset, msg_length, "__wrap_abort")?;
+    let file = read_string(memory, file_offset, file_length, "__wrap_abort")?;
+
+    let error = format!("__wrap_abort: {msg}\nFile: {file}\nLocation: [{line},{column}]");
+
+    let info = WrapAbortInfo {
+        message: msg,
+        file,
+        line,
+        column,
+    };
+    state.log(WrapLogEvent::Abort(info.clone()));
+    state.abort = Some(info);
+
+    Err(error)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn subinvoke(
+    state: &mut State,
+    memory: &mut dyn HostMemory,
+    uri_ptr: u32,
+    uri_len: u32,
+    method_ptr: u32,
+    method_len: u32,
+    args_ptr: u32,
+    args_len: u32,
+) -> Result<i32, String> {
+    let uri = read_string(memory, uri_ptr, uri_len, "__wrap_subinvoke")?;
+    let method = read_string(memory, method_ptr, method_len, "__wrap_subinvoke")?;
+    let args = memory.read(args_ptr, args_len)?;
+
+    let uri = parse_uri(uri, "__wrap_subinvoke")?;
+
+    let span = tracing::info_span!(
+        "subinvoke",
+        uri = %uri,
+        method,
+        otel.status_code = tracing::field::Empty,
+        error = tracing::field::Empty,
+    );
+
+    let result = span.in_scope(|| {
+        state
+            .invoker
+            .clone()
+            .invoke_raw(&uri, &method, Some(&args), None, None)
+    });
+
+    record_result(&span, &result);
+
+    match result {
+        Ok(result) => {
+            state.subinvoke.result = Some(result);
+            state.subinvoke_failure = None;
+            Ok(1)
+        }
+        Err(error) => {
+            state.subinvoke.error = Some(error.to_string());
+            state.subinvoke_failure = Some(error);
+            Ok(0)
+        }
+    }
+}
+
+pub fn subinvoke_result_len(state: &mut State) -> Result<u32, String> {
+    let result = state
+        .subinvoke
+        .result
+        .as_ref()
+        .ok_or("__wrap_subinvoke_result_len: subinvoke.result is not set")?;
+
+    Ok(result.len() as u32)
+}
+
+pub fn subinvoke_result(
+    state: &mut State,
+    memory: &mut dyn HostMemory,
+    pointer: u32,
+) -> Result<(), String> {
+    let result = state
+        .subinvoke
+        .result
+        .as_ref()
+        .ok_or("__wrap_subinvoke_result: subinvoke.result is not set")?;
+
+    memory.write(pointer, result)
+}
+
+pub fn subinvoke_error_len(state: &mut State) -> Result<u32, String> {
+    let error = state
+        .subinvoke
+        .error
+        .as_ref()
+        .ok_or("__wrap_subinvoke_error_len: subinvoke.error is not set")?;
+
+    Ok(error.len() as u32)
+}
+
+pub fn subinvoke_error(
+    state: &mut State,
+    memory: &mut dyn HostMemory,
+    pointer: u32,
+) -> Result<(), String> {
+    let error = state
+        .subinvoke
+        .error
+        .as_ref()
+        .ok_or("__wrap_subinvoke_error: subinvoke.error is not set")?;
+
+    memory.write(pointer, error.as_bytes())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn subinvoke_implementation(
+    state: &mut State,
+    memory: &mut dyn HostMemory,
+    interface_ptr: u32,
+    interface_len: u32,
+    impl_uri_ptr: u32,
+    impl_uri_len: u32,
+    method_ptr: u32,
+    method_len: u32,
+    args_ptr: u32,
+    args_len: u32,
+) -> Result<i32, String> {
+    const FUNCTION: &str = "__wrap_subinvokeImplementation";
+
+    let interface = read_string(memory, interface_ptr, interface_len, FUNCTION)?;
+    let uri = read_string(memory, impl_uri_ptr, impl_uri_len, FUNCTION)?;
+    let method = read_string(memory, method_ptr, method_len, FUNCTION)?;
+    let args = memory.read(args_ptr, args_len)?;
+
+    let uri = parse_uri(uri, FUNCTION)?;
+
+    let span = tracing::info_span!(
+        "subinvoke_implementation",
+        interface,
+        uri = %uri,
+        method,
+        otel.status_code = tracing::field::Empty,
+        error = tracing::field::Empty,
+    );
+
+    let result = span.in_scope(|| {
+        state
+            .invoker
+            .clone()
+            .invoke_raw(&uri, &method, Some(&args), None, None)
+    });
+
+    record_result(&span, &result);
+
+    match result {
+        Ok(result) => {
+            state.subinvoke_implementation = Some(SubinvokeImplementationState {
+                result: Some(result),
+                args,
+                error: None,
+            });
+            state.subinvoke_failure = None;
+            Ok(1)
+        }
+        Err(e) => {
+            let error = format!(
+                "interface implementation subinvoke failed for uri: {interface} with error: {e}"
+            );
+            state.subinvoke_implementation = Some(SubinvokeImplementationState {
+                result: None,
+                args,
+                error: Some(error),
+            });
+            state.subinvoke_failure = Some(e);
+            Ok(0)
+        }
+    }
+}
+
+fn subinvoke_implementation_state<'a>(
+    state: &'a State,
+    function: &str,
+) -> Result<&'a SubinvokeImplementationState, String> {
+    state
+        .subinvoke_implementation
+        .as_ref()
+        .ok_or(format!("{function}: subinvoke_implementation is not set"))
+}
+
+pub fn subinvoke_implementation_result_len(state: &mut State) -> Result<u32, String> {
+    const FUNCTION: &str = "__wrap_subinvoke_implementation_result_len";
+
+    let result = subinvoke_implementation_state(state, FUNCTION)?
+        .result
+        .as_ref()
+        .ok_or(format!(
+            "{FUNCTION}: subinvoke_implementation.result is not set"
+        ))?;
+
+    Ok(result.len() as u32)
+}
+
+pub fn subinvoke_implementation_result(
+    state: &mut State,
+    memory: &mut dyn HostMemory,
+    pointer: u32,
+) -> Result<(), String> {
+    const FUNCTION: &str = "__wrap_subinvoke_implementation_result";
+
+    let result = subinvoke_implementation_state(state, FUNCTION)?
+        .result
+        .as_ref()
+        .ok_or(format!(
+            "{FUNCTION}: subinvoke_implementation.result is not set"
+        ))?;
+
+    memory.write(pointer, result)
+}
+
+pub fn subinvoke_implementation_error_len(state: &mut State) -> Result<u32, String> {
+    const FUNCTION: &str = "__wrap_subinvoke_implementation_error_len";
+
+    let error = subinvoke_implementation_state(state, FUNCTION)?
+        .error
+        .as_ref()
+        .ok_or(format!(
+            "{FUNCTION}: subinvoke_implementation.error is not set"
+        ))?;
+
+    Ok(error.len() as u32)
+}
+
+pub fn subinvoke_implementation_error(
+    state: &mut State,
+    memory: &mut dyn HostMemory,
+    pointer: u32,
+) -> Result<(), String> {
+    const FUNCTION: &str = "__wrap_subinvoke_implementation_error";
+
+    let error = subinvoke_implementation_state(state, FUNCTION)?
+        .error
+        .as_ref()
+        .ok_or(format!(
+            "{FUNCTION}: subinvoke_implementation.error is not set"
+        ))?;
+
+    memory.write(pointer, error.as_bytes())
+}
+
+pub fn get_implementations(
+    state: &mut State,
+    memory: &mut dyn HostMemory,
+    pointer: u32,
+    length: u32,
+) -> Result<i32, String> {
+    const FUNCTION: &str = "__wrap_getImplementations";
+
+    let uri = read_string(memory, pointer, length, FUNCTION)?;
+    let uri = parse_uri(uri, FUNCTION)?;
+
+    let implementations = state
+        .invoker
+        .get_implementations(&uri)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|uri| uri.to_string())
+        .collect::<Vec<String>>();
+
+    let encoded_implementations = to_vec(&implementations).map_err(|e| e.to_string())?;
+    let found = !encoded_implementations.is_empty();
+    state.get_implementations_result = Some(encoded_implementations);
+
+    Ok(found as i32)
+}
+
+pub fn get_implementations_result_len(state: &mut State) -> Result<u32, String> {
+    let result = state
+        .get_implementations_result
+        .as_ref()
+        .ok_or("__wrap_get_implementation_result_len: get_implementation_result is not set")?;
+
+    Ok(result.len() as u32)
+}
+
+pub fn get_implementations_result(
+    state: &mut State,
+    memory: &mut dyn HostMemory,
+    pointer: u32,
+) -> Result<(), String> {
+    let result = state
+        .get_implementations_result
+        .as_ref()
+        .ok_or("__wrap_get_implementation_result: get_implementation_result is not set")?;
+
+    memory
+        .write(pointer, result)
+        .map_err(|e| format!("__wrap_get_implementation_result: failed to write to memory: {e}"))
+}
+
+pub fn load_env(
+    state: &mut State,
+    memory: &mut dyn HostMemory,
+    pointer: u32,
+) -> Result<(), String> {
+    memory
+        .write(pointer, &state.env)
+        .map_err(|e| format!("__wrap_load_env: failed to write to memory: {e}"))
+}
+
+pub fn debug_log(
+    state: &mut State,
+    memory: &mut dyn HostMemory,
+    offset: u32,
+    length: u32,
+) -> Result<(), String> {
+    let msg = read_string(memory, offset, length, "__wrap_debug_log")?;
+    state.log(WrapLogEvent::Debug(msg));
+    Ok(())
+}
@@ -1,12 +1,34 @@
-use std::sync::{Arc, Mutex};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
 
-use polywrap_core::invoker::Invoker;
-use wasmer::{Instance, Memory, MemoryType, Module, Store, Value};
+use polywrap_core::{
+    error::{Error, WrapAbortInfo},
+    interface_implementation::InterfaceImplementations,
+    invoker::Invoker,
+    resolution::uri_resolution_context::UriResolutionContext,
+    resource_limits::ResourceLimits,
+    uri::Uri,
+    wrap_log::{TracingLogSink, WrapLogEvent, WrapLogRecord, WrapLogSink},
+};
+use wasmer::{
+    sys::NativeEngineExt, AsStoreMut, Extern, Instance, Memory, MemoryType, Module, Store, Value,
+};
+use wasmer_vm::{VMExtern, VMMemory};
 // use wasmer_compiler_llvm::LLVM;
 
 use crate::error::WrapperError;
 
-use super::imports::create_imports;
+use super::{
+    engine::EngineInstance,
+    imports::create_imports,
+    interrupt::InterruptHandle,
+    memory::CappedMemory,
+    metering::{INTERRUPTED_EXPORT, POINTS_EXHAUSTED_EXPORT, REMAINING_POINTS_EXPORT},
+    snapshot::InstanceSnapshot,
+    wasi::WasiContext,
+};
 
 #[derive(Default)]
 pub struct InvokeState {
@@ -29,7 +51,11 @@ pub struct State {
     pub invoker: Arc<dyn Invoker>,
     pub get_implementations_result: Option<Vec<u8>>,
     pub subinvoke_implementation: Option<SubinvokeImplementationState>,
-    pub memory: Option<Memory>,
+    pub abort: Option<WrapAbortInfo>,
+    /// Error of the last subinvocation if it failed, kept as the cause of
+    /// the abort or error the wrap raises after it
+    pub subinvoke_failure: Option<Error>,
+    pub wasi: WasiContext,
 }
 
 impl State {
@@ -39,6 +65,8 @@ impl State {
         args: Vec<u8>,
         env: Vec<u8>,
     ) -> Self {
+        let wasi = WasiContext::new(invoker.get_wasi_policy().unwrap_or_default());
+
         Self {
             method: method.as_bytes().to_vec(),
             args,
@@ -48,14 +76,75 @@ impl State {
             invoker,
             get_implementations_result: None,
             subinvoke_implementation: None,
-            memory: None,
+            abort: None,
+            subinvoke_failure: None,
+            wasi,
+        }
+    }
+
+    /// Sends an event logged by the wrap to the log sink of the invoker,
+    /// or emits it as a `tracing` event if it has none.
+    pub fn log(&self, event: WrapLogEvent) {
+        let record = WrapLogRecord {
+            uri: self.invoker.get_wrap_uri(),
+            method: String::from_utf8_lossy(&self.method).into_owned(),
+            event,
+        };
+
+        match self.invoker.get_log_sink() {
+            Some(sink) => sink.log(&record),
+            None => TracingLogSink.log(&record),
         }
     }
+
+    /// State of an instance waiting in a pool, which must not keep the last invoker alive.
+    pub fn released() -> Self {
+        Self::new(Arc::new(ReleasedInvoker), "", vec![], vec![])
+    }
+}
+
+// Invoker of an instance that isn't running an invocation
+struct ReleasedInvoker;
+
+impl Invoker for ReleasedInvoker {
+    fn invoke_raw(
+        &self,
+        _: &Uri,
+        _: &str,
+        _: Option<&[u8]>,
+        _: Option<&[u8]>,
+        _: Option<Arc<Mutex<UriResolutionContext>>>,
+    ) -> Result<Vec<u8>, Error> {
+        Err(Error::RuntimeError(
+            "Instance is not running an invocation".to_string(),
+        ))
+    }
+
+    fn get_implementations(&self, _: &Uri) -> Result<Vec<Uri>, Error> {
+        Err(Error::RuntimeError(
+            "Instance is not running an invocation".to_string(),
+        ))
+    }
+
+    fn get_interfaces(&self) -> Option<InterfaceImplementations> {
+        None
+    }
+
+    fn get_env_by_uri(&self, _: &Uri) -> Option<Vec<u8>> {
+        None
+    }
 }
 
 pub struct WasmInstance {
     instance: Instance,
     pub store: Store,
+    module: Module,
+    state: Arc<Mutex<State>>,
+    memory: Memory,
+    snapshot: Option<InstanceSnapshot>,
+    fuel: u64,
+    max_memory_pages: Option<u32>,
+    memory_growth_denied: Arc<AtomicBool>,
 }
 
 impl WasmInstance {
@@ -63,6 +152,7 @@ impl WasmInstance {
         module: &Module,
         memory_initial_limits: u8,
         state: Arc<Mutex<State>>,
+        resource_limits: &ResourceLimits,
     ) -> Result<Self, WrapperError> {
         // This has been commented because it introduces the OS dependency of LLVM
         // Research needs to be done on how to easily install this.
@@ -71,16 +161,36 @@ impl WasmInstance {
         // let mut store = Store::new(compiler);
 
         let mut store = Store::default();
-        let memory = WasmInstance::create_memory(&mut store, memory_initial_limits)?;
-
-        state.lock().unwrap().memory = Some(memory.clone());
+        let memory_growth_denied = Arc::new(AtomicBool::new(false));
+        let memory = WasmInstance::create_memory(
+            &mut store,
+            memory_initial_limits,
+            resource_limits.max_memory_pages,
+            memory_growth_denied.clone(),
+        )?;
 
-        let imports = create_imports(memory, &mut store, state);
+        let imports = create_imports(module, memory.clone(), &mut store, state.clone());
 
         let instance = Instance::new(&mut store, &module, &imports)
             .map_err(|e| WrapperError::WasmRuntimeError(e.to_string()))?;
 
-        Ok(Self { instance, store })
+        let mut wasm_instance = Self {
+            instance,
+            store,
+            module: module.clone(),
+            state,
+            memory,
+            snapshot: None,
+            fuel: u64::MAX,
+            max_memory_pages: resource_limits.max_memory_pages,
+            memory_growth_denied,
+        };
+
+        if let Some(fuel) = resource_limits.fuel {
+            wasm_instance.set_fuel(fuel)?;
+        }
+
+        Ok(wasm_instance)
     }
 
     pub fn get_memory_initial_limits(module: &[u8]) -> Result<u8, WrapperError> {
@@ -108,32 +218,169 @@ impl WasmInstance {
         }
     }
 
+    // Creates the memory imported by the module. When it has a maximum number of pages,
+    // attempts to grow it further are recorded in `growth_denied`.
     pub fn create_memory(
         store: &mut Store,
         memory_initial_limits: u8,
+        max_memory_pages: Option<u32>,
+        growth_denied: Arc<AtomicBool>,
     ) -> Result<Memory, WrapperError> {
-        let memory = Memory::new(
+        let max_memory_pages = match max_memory_pages {
+            Some(max_memory_pages) => max_memory_pages,
+            None => {
+                let memory = Memory::new(
+                    store,
+                    MemoryType::new(memory_initial_limits as u32, None, false),
+                )?;
+
+                return Ok(memory);
+            }
+        };
+
+        if (memory_initial_limits as u32) > max_memory_pages {
+            return Err(WrapperError::ResourceLimitError(format!(
+                "Module requires {memory_initial_limits} memory pages, but the limit is {max_memory_pages}"
+            )));
+        }
+
+        let ty = MemoryType::new(memory_initial_limits as u32, Some(max_memory_pages), false);
+        let style = store.engine().tunables().memory_style(&ty);
+        let memory = CappedMemory::new(&ty, &style, growth_denied)?;
+
+        Ok(Memory::new_from_existing(
             store,
-            MemoryType::new(memory_initial_limits as u32, None, false),
-        )?;
+            VMMemory::from(Box::new(memory) as Box<_>),
+        ))
+    }
+
+    // Sets the amount of fuel the instance can consume. Modules compiled
+    // without the metering middleware keep running without a budget.
+    fn set_fuel(&mut self, fuel: u64) -> Result<(), WrapperError> {
+        if let Ok(global) = self.instance.exports.get_global(REMAINING_POINTS_EXPORT) {
+            global
+                .set(&mut self.store, Value::I64(fuel as i64))
+                .map_err(|e| WrapperError::WasmRuntimeError(e.to_string()))?;
+            self.fuel = fuel;
+        }
 
-        Ok(memory)
+        Ok(())
     }
+}
 
-    pub fn call_export(&mut self, name: &str, params: &[Value]) -> Result<bool, WrapperError> {
-        let export = self.instance.exports.get_function(name)
+impl EngineInstance for WasmInstance {
+    fn call_export(&mut self, name: &str, params: &[i32]) -> Result<bool, WrapperError> {
+        let export = self
+            .instance
+            .exports
+            .get_function(name)
             .map_err(|_| WrapperError::WasmRuntimeError(format!("Export {name} not found")))?;
 
-        let result = export.call(&mut self.store, params)
+        let params = params.iter().map(|param| Value::I32(*param)).collect::<Vec<_>>();
+        let result = export
+            .call(&mut self.store, &params)
             .map_err(|e| WrapperError::WasmRuntimeError(e.to_string()))?;
 
-        let result = result.to_vec();
-        
         // If the result is true (1), then the call was successful
-        let result = result.get(0)
-            .and_then(|x| x.i32())
-            .unwrap_or(0);
+        let result = result.first().and_then(|x| x.i32()).unwrap_or(0);
 
         Ok(result == 1)
     }
+
+    fn read_memory(&mut self, offset: u32, length: u32) -> Result<Vec<u8>, WrapperError> {
+        let mut buffer = vec![0; length as usize];
+        self.memory
+            .view(&self.store)
+            .read(offset as u64, &mut buffer)
+            .map_err(|e| WrapperError::WasmRuntimeError(e.to_string()))?;
+        Ok(buffer)
+    }
+
+    fn write_memory(&mut self, offset: u32, data: &[u8]) -> Result<(), WrapperError> {
+        self.memory
+            .view(&self.store)
+            .write(offset as u64, data)
+            .map_err(|e| WrapperError::WasmRuntimeError(e.to_string()))
+    }
+
+    fn state(&self) -> Arc<Mutex<State>> {
+        self.state.clone()
+    }
+
+    fn max_memory_pages(&self) -> Option<u32> {
+        self.max_memory_pages
+    }
+
+    // Modules compiled without the metering middleware aren't metered
+    fn fuel_consumed(&mut self) -> Option<u64> {
+        let global = self
+            .instance
+            .exports
+            .get_global(REMAINING_POINTS_EXPORT)
+            .ok()?;
+
+        match global.get(&mut self.store) {
+            Value::I64(remaining) => Some(self.fuel - remaining as u64),
+            _ => None,
+        }
+    }
+
+    fn fuel_exhausted(&mut self) -> bool {
+        let Ok(global) = self.instance.exports.get_global(POINTS_EXHAUSTED_EXPORT) else {
+            return false;
+        };
+
+        global.get(&mut self.store) == Value::I32(1)
+    }
+
+    fn memory_growth_denied(&self) -> bool {
+        self.memory_growth_denied.load(Ordering::SeqCst)
+    }
+
+    // Modules compiled without the metering middleware can't be interrupted
+    fn interrupt_handle(&mut self) -> Option<Arc<InterruptHandle>> {
+        let global = self
+            .instance
+            .exports
+            .get_global(INTERRUPTED_EXPORT)
+            .ok()?;
+
+        let definition = match Extern::Global(global.clone()).to_vm_extern() {
+            VMExtern::Global(handle) => handle.get(self.store.objects_mut()).vmglobal(),
+            _ => return None,
+        };
+
+        // The global definition lives as long as the store of this instance
+        let handle = unsafe { InterruptHandle::from_flag(definition.cast()) };
+        Some(Arc::new(handle))
+    }
+
+    // See `InstanceSnapshot::capture`
+    fn snapshot(&mut self) -> bool {
+        self.snapshot =
+            InstanceSnapshot::capture(&self.module, &self.instance, &self.memory, &mut self.store);
+        self.snapshot.is_some()
+    }
+
+    fn restore(&mut self) -> bool {
+        *self.state.lock().unwrap() = State::released();
+
+        let Some(snapshot) = &self.snapshot else {
+            return false;
+        };
+
+        if !snapshot.restore(&self.instance, &self.memory, &mut self.store) {
+            return false;
+        }
+
+        self.fuel = u64::MAX;
+        self.memory_growth_denied.store(false, Ordering::SeqCst);
+        true
+    }
+
+    fn prepare(&mut self, state: State, resource_limits: &ResourceLimits) -> Result<(), WrapperError> {
+        *self.state.lock().unwrap() = state;
+
+        self.set_fuel(resource_limits.fuel.unwrap_or(u64::MAX))
+    }
 }
@@ -0,0 +1,179 @@
+use std::{
+    ptr::NonNull,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Condvar, Mutex, OnceLock, Weak,
+    },
+    thread,
+    time::Instant,
+};
+
+use polywrap_core::invoke_options::{CancellationGuard, InvokeOptions};
+
+/// Why the execution of a wasm instance was interrupted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterruptReason {
+    Timeout,
+    Cancelled,
+}
+
+struct InterruptState {
+    interrupt: Option<Box<dyn Fn() + Send>>,
+    reason: Option<InterruptReason>,
+}
+
+// Pointer to the interrupt flag global of a wasmer instance. It's only dereferenced
+// while holding the lock of the handle, and is cleared before the instance owning it is dropped
+struct InterruptFlag(NonNull<AtomicU32>);
+
+unsafe impl Send for InterruptFlag {}
+
+impl InterruptFlag {
+    fn set(&self) {
+        // The instance may be reading the global concurrently, hence the atomic store
+        unsafe { self.0.as_ref() }.store(1, Ordering::SeqCst);
+    }
+}
+
+/// Interrupts a running wasm instance from any thread.
+pub struct InterruptHandle {
+    state: Mutex<InterruptState>,
+}
+
+impl InterruptHandle {
+    /// Creates a handle interrupting an instance by calling `interrupt`,
+    /// until `detach` is called.
+    pub fn new(interrupt: impl Fn() + Send + 'static) -> Self {
+        Self {
+            state: Mutex::new(InterruptState {
+                interrupt: Some(Box::new(interrupt)),
+                reason: None,
+            }),
+        }
+    }
+
+    /// Creates a handle setting the interrupt flag of the metering middleware,
+    /// so the instance traps at the end of its current basic block.
+    ///
+    /// # Safety
+    ///
+    /// `interrupted` must point to the interrupt flag global of a running instance,
+    /// and stay valid until `detach` is called.
+    pub(crate) unsafe fn from_flag(interrupted: NonNull<u32>) -> Self {
+        let flag = InterruptFlag(interrupted.cast());
+
+        Self::new(move || flag.set())
+    }
+
+    pub fn interrupt(&self, reason: InterruptReason) {
+        let mut state = self.state.lock().unwrap();
+
+        if state.reason.is_none() {
+            state.reason = Some(reason);
+        }
+
+        if let Some(interrupt) = &state.interrupt {
+            interrupt();
+        }
+    }
+
+    pub fn reason(&self) -> Option<InterruptReason> {
+        self.state.lock().unwrap().reason
+    }
+
+    pub(crate) fn detach(&self) {
+        self.state.lock().unwrap().interrupt = None;
+    }
+}
+
+/// Keeps an `InterruptHandle` armed with the deadline and cancellation token
+/// of an invocation. Disarms it when dropped.
+pub struct InterruptGuard {
+    handle: Arc<InterruptHandle>,
+    _cancellation: Option<CancellationGuard>,
+}
+
+impl InterruptGuard {
+    pub fn new(handle: Arc<InterruptHandle>, options: &InvokeOptions) -> Self {
+        if let Some(deadline) = options.deadline {
+            watchdog().watch(deadline, Arc::downgrade(&handle));
+        }
+
+        let cancellation = options.cancellation_token.as_ref().map(|token| {
+            let handle = Arc::downgrade(&handle);
+            token.on_cancel(move || {
+                if let Some(handle) = handle.upgrade() {
+                    handle.interrupt(InterruptReason::Cancelled);
+                }
+            })
+        });
+
+        Self {
+            handle,
+            _cancellation: cancellation,
+        }
+    }
+
+    pub fn reason(&self) -> Option<InterruptReason> {
+        self.handle.reason()
+    }
+}
+
+impl Drop for InterruptGuard {
+    fn drop(&mut self) {
+        self.handle.detach();
+    }
+}
+
+/// Background thread interrupting instances once their deadline passes.
+struct Watchdog {
+    deadlines: Mutex<Vec<(Instant, Weak<InterruptHandle>)>>,
+    condvar: Condvar,
+}
+
+fn watchdog() -> &'static Watchdog {
+    static WATCHDOG: OnceLock<Watchdog> = OnceLock::new();
+
+    WATCHDOG.get_or_init(|| {
+        thread::Builder::new()
+            .name("polywrap-wasm-watchdog".to_string())
+            .spawn(|| watchdog().run())
+            .expect("Failed to spawn the wasm watchdog thread");
+
+        Watchdog {
+            deadlines: Mutex::new(vec![]),
+            condvar: Condvar::new(),
+        }
+    })
+}
+
+impl Watchdog {
+    fn watch(&self, deadline: Instant, handle: Weak<InterruptHandle>) {
+        self.deadlines.lock().unwrap().push((deadline, handle));
+        self.condvar.notify_one();
+    }
+
+    fn run(&self) {
+        let mut deadlines = self.deadlines.lock().unwrap();
+
+        loop {
+            let now = Instant::now();
+
+            deadlines.retain(|(deadline, handle)| {
+                if *deadline > now {
+                    return handle.strong_count() > 0;
+                }
+
+                if let Some(handle) = handle.upgrade() {
+                    handle.interrupt(InterruptReason::Timeout);
+                }
+                false
+            });
+
+            deadlines = match deadlines.iter().map(|(deadline, _)| *deadline).min() {
+                Some(next) => self.condvar.wait_timeout(deadlines, next - now).unwrap().0,
+                None => self.condvar.wait(deadlines).unwrap(),
+            };
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::{
+    ptr::NonNull,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use wasmer::{MemoryType, Pages};
+use wasmer_types::MemoryStyle;
+use wasmer_vm::{LinearMemory, MemoryError, Trap, VMMemory, VMMemoryDefinition};
+
+/// Linear memory that records whether the wrap tried to grow it past its maximum.
+///
+/// A failed `memory.grow` doesn't trap, so the wrap usually fails later on
+/// with an unrelated error. The record lets the runtime report the actual cause.
+#[derive(Debug)]
+pub struct CappedMemory {
+    memory: VMMemory,
+    growth_denied: Arc<AtomicBool>,
+}
+
+impl CappedMemory {
+    pub fn new(
+        ty: &MemoryType,
+        style: &MemoryStyle,
+        growth_denied: Arc<AtomicBool>,
+    ) -> Result<Self, MemoryError> {
+        Ok(Self {
+            memory: VMMemory::new(ty, style)?,
+            growth_denied,
+        })
+    }
+}
+
+impl LinearMemory for CappedMemory {
+    fn ty(&self) -> MemoryType {
+        self.memory.ty()
+    }
+
+    fn size(&self) -> Pages {
+        self.memory.size()
+    }
+
+    fn style(&self) -> MemoryStyle {
+        self.memory.style()
+    }
+
+    fn grow(&mut self, delta: Pages) -> Result<Pages, MemoryError> {
+        let result = self.memory.grow(delta);
+
+        if result.is_err() {
+            self.growth_denied.store(true, Ordering::SeqCst);
+        }
+
+        result
+    }
+
+    fn grow_at_least(&mut self, min_size: u64) -> Result<(), MemoryError> {
+        self.memory.grow_at_least(min_size)
+    }
+
+    fn reset(&mut self) -> Result<(), MemoryError> {
+        self.memory.reset()
+    }
+
+    fn vmmemory(&self) -> NonNull<VMMemoryDefinition> {
+        self.memory.vmmemory()
+    }
+
+    fn try_clone(&self) -> Result<Box<dyn LinearMemory + 'static>, MemoryError> {
+        self.memory.try_clone()
+    }
+
+    unsafe fn initialize_with_data(&self, start: usize, data: &[u8]) -> Result<(), Trap> {
+        self.memory.initialize_with_data(start, data)
+    }
+
+    fn copy(&mut self) -> Result<Box<dyn LinearMemory + 'static>, MemoryError> {
+        self.memory.copy()
+    }
+}
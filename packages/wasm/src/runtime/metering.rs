@@ -0,0 +1,182 @@
+use std::sync::Mutex;
+
+use wasmer::{
+    wasmparser::{BlockType, Operator},
+    ExportIndex, FunctionMiddleware, GlobalInit, GlobalType, LocalFunctionIndex, MiddlewareError,
+    MiddlewareReaderState, ModuleMiddleware, Mutability, Type,
+};
+use wasmer_types::{GlobalIndex, ModuleInfo};
+
+/// Name of the exported global holding the points left before execution traps.
+pub const REMAINING_POINTS_EXPORT: &str = "__wrap_metering_remaining_points";
+/// Name of the exported global set to 1 once execution ran out of points.
+pub const POINTS_EXHAUSTED_EXPORT: &str = "__wrap_metering_points_exhausted";
+/// Name of the exported global the runtime sets to a non-zero value to interrupt execution.
+pub const INTERRUPTED_EXPORT: &str = "__wrap_metering_interrupted";
+
+/// Indexes of the globals added to the module by `Metering`.
+#[derive(Debug, Clone, Copy)]
+struct MeteringGlobals {
+    remaining_points: GlobalIndex,
+    points_exhausted: GlobalIndex,
+    interrupted: GlobalIndex,
+}
+
+/// Compiler middleware charging one point per executed operator.
+///
+/// Points and the interrupt flag are checked at the end of every basic block, and
+/// execution traps once points run out or the flag is set. Modules start with an
+/// unlimited amount of points. The interrupt flag is never written by the module
+/// itself, so the runtime can safely set it from another thread.
+///
+/// A `Metering` instance must only be used to compile a single module.
+#[derive(Debug, Default)]
+pub struct Metering {
+    global_indexes: Mutex<Option<MeteringGlobals>>,
+}
+
+impl Metering {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ModuleMiddleware for Metering {
+    fn generate_function_middleware(&self, _: LocalFunctionIndex) -> Box<dyn FunctionMiddleware> {
+        let globals = self
+            .global_indexes
+            .lock()
+            .unwrap()
+            .expect("Metering globals are added before functions are compiled");
+
+        Box::new(FunctionMetering {
+            remaining_points: globals.remaining_points.as_u32(),
+            points_exhausted: globals.points_exhausted.as_u32(),
+            interrupted: globals.interrupted.as_u32(),
+            accumulated_cost: 0,
+        })
+    }
+
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) -> Result<(), MiddlewareError> {
+        let mut global_indexes = self.global_indexes.lock().unwrap();
+
+        if global_indexes.is_some() {
+            return Err(MiddlewareError::new(
+                "Metering",
+                "a Metering middleware can only be used to compile a single module",
+            ));
+        }
+
+        let remaining_points = module_info
+            .globals
+            .push(GlobalType::new(Type::I64, Mutability::Var));
+        module_info
+            .global_initializers
+            .push(GlobalInit::I64Const(u64::MAX as i64));
+        module_info.exports.insert(
+            REMAINING_POINTS_EXPORT.to_string(),
+            ExportIndex::Global(remaining_points),
+        );
+
+        let points_exhausted = module_info
+            .globals
+            .push(GlobalType::new(Type::I32, Mutability::Var));
+        module_info
+            .global_initializers
+            .push(GlobalInit::I32Const(0));
+        module_info.exports.insert(
+            POINTS_EXHAUSTED_EXPORT.to_string(),
+            ExportIndex::Global(points_exhausted),
+        );
+
+        let interrupted = module_info
+            .globals
+            .push(GlobalType::new(Type::I32, Mutability::Var));
+        module_info
+            .global_initializers
+            .push(GlobalInit::I32Const(0));
+        module_info.exports.insert(
+            INTERRUPTED_EXPORT.to_string(),
+            ExportIndex::Global(interrupted),
+        );
+
+        *global_indexes = Some(MeteringGlobals {
+            remaining_points,
+            points_exhausted,
+            interrupted,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct FunctionMetering {
+    remaining_points: u32,
+    points_exhausted: u32,
+    interrupted: u32,
+    accumulated_cost: u64,
+}
+
+impl FunctionMiddleware for FunctionMetering {
+    fn feed<'a>(
+        &mut self,
+        operator: Operator<'a>,
+        state: &mut MiddlewareReaderState<'a>,
+    ) -> Result<(), MiddlewareError> {
+        self.accumulated_cost += 1;
+
+        // Charge the accumulated cost before any operator that ends a basic block,
+        // including `If`, as what runs before it is paid for whichever branch is taken
+        match operator {
+            Operator::Loop { .. }
+            | Operator::If { .. }
+            | Operator::End
+            | Operator::Else
+            | Operator::Br { .. }
+            | Operator::BrTable { .. }
+            | Operator::BrIf { .. }
+            | Operator::Call { .. }
+            | Operator::CallIndirect { .. }
+            | Operator::Return => {
+                let cost = self.accumulated_cost as i64;
+                state.extend([
+                    Operator::GlobalGet {
+                        global_index: self.interrupted,
+                    },
+                    Operator::If {
+                        blockty: BlockType::Empty,
+                    },
+                    Operator::Unreachable,
+                    Operator::End,
+                    Operator::GlobalGet {
+                        global_index: self.remaining_points,
+                    },
+                    Operator::I64Const { value: cost },
+                    Operator::I64LtU,
+                    Operator::If {
+                        blockty: BlockType::Empty,
+                    },
+                    Operator::I32Const { value: 1 },
+                    Operator::GlobalSet {
+                        global_index: self.points_exhausted,
+                    },
+                    Operator::Unreachable,
+                    Operator::End,
+                    Operator::GlobalGet {
+                        global_index: self.remaining_points,
+                    },
+                    Operator::I64Const { value: cost },
+                    Operator::I64Sub,
+                    Operator::GlobalSet {
+                        global_index: self.remaining_points,
+                    },
+                ]);
+                self.accumulated_cost = 0;
+            }
+            _ => {}
+        }
+
+        state.push_operator(operator);
+        Ok(())
+    }
+}
@@ -1,2 +1,13 @@
+pub mod engine;
+pub mod host;
 pub mod imports;
 pub mod instance;
+pub mod interrupt;
+pub mod memory;
+pub mod metering;
+pub mod pool;
+pub mod snapshot;
+pub mod wasi;
+pub mod wasmer_engine;
+#[cfg(feature = "wasmtime")]
+pub mod wasmtime_engine;
@@ -0,0 +1,96 @@
+use std::sync::{Arc, Mutex};
+
+use polywrap_core::resource_limits::ResourceLimits;
+
+use crate::{error::WrapperError, wasm_module::CompiledWasmModule};
+
+use super::{engine::EngineInstance, instance::State};
+
+/// A bounded pool of idle instances of a module.
+///
+/// Instances are restored to their post-instantiation snapshot when released,
+/// so later invocations can reuse them instead of instantiating the module again.
+/// Instances that can't be restored, e.g. because they trapped or grew their memory,
+/// are discarded.
+pub struct InstancePool {
+    max_size: usize,
+    instances: Mutex<Vec<PooledInstance>>,
+}
+
+/// An instance taken from an `InstancePool`.
+pub struct PooledInstance {
+    pub instance: Box<dyn EngineInstance>,
+    // Whether the instance holds its post-instantiation snapshot
+    snapshot: bool,
+}
+
+impl From<Box<dyn EngineInstance>> for PooledInstance {
+    // Wraps an instance that isn't meant to be pooled
+    fn from(instance: Box<dyn EngineInstance>) -> Self {
+        Self {
+            instance,
+            snapshot: false,
+        }
+    }
+}
+
+impl InstancePool {
+    /// Creates a pool keeping at most `max_size` idle instances.
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            instances: Mutex::new(vec![]),
+        }
+    }
+
+    /// Takes an idle instance able to run within the given limits, or instantiates a new one.
+    pub fn acquire(
+        &self,
+        module: &CompiledWasmModule,
+        state: State,
+        resource_limits: &ResourceLimits,
+    ) -> Result<PooledInstance, WrapperError> {
+        let idle = {
+            let mut instances = self.instances.lock().unwrap();
+            instances
+                .iter()
+                .rposition(|pooled| {
+                    pooled.instance.max_memory_pages() == resource_limits.max_memory_pages
+                })
+                .map(|index| instances.swap_remove(index))
+        };
+
+        if let Some(mut pooled) = idle {
+            pooled.instance.prepare(state, resource_limits)?;
+            return Ok(pooled);
+        }
+
+        let mut instance = module.create_instance(Arc::new(Mutex::new(state)), resource_limits)?;
+        let snapshot = instance.snapshot();
+
+        Ok(PooledInstance { instance, snapshot })
+    }
+
+    /// Restores an instance and keeps it for later invocations, unless the pool is full.
+    pub fn release(&self, mut pooled: PooledInstance) {
+        if !pooled.snapshot {
+            return;
+        }
+
+        if self.instances.lock().unwrap().len() >= self.max_size {
+            return;
+        }
+
+        if pooled.instance.restore() {
+            let mut instances = self.instances.lock().unwrap();
+            if instances.len() < self.max_size {
+                instances.push(pooled);
+            }
+        }
+    }
+
+    /// Returns the number of idle instances in the pool.
+    pub fn idle_count(&self) -> usize {
+        self.instances.lock().unwrap().len()
+    }
+}
@@ -0,0 +1,110 @@
+use wasmer::{
+    AsStoreMut, ExportIndex, FunctionMiddleware, LocalFunctionIndex, Memory, MiddlewareError,
+    Module, ModuleMiddleware, Mutability, Value,
+};
+use wasmer_types::ModuleInfo;
+
+/// Prefix of the names under which `ExportGlobals` exports globals.
+pub const GLOBAL_EXPORT_PREFIX: &str = "__wrap_global_";
+
+/// Compiler middleware exporting every global of a module that isn't exported yet,
+/// so the runtime can snapshot and restore the whole state of an instance.
+#[derive(Debug, Default)]
+pub struct ExportGlobals;
+
+impl ExportGlobals {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ModuleMiddleware for ExportGlobals {
+    fn generate_function_middleware(&self, _: LocalFunctionIndex) -> Box<dyn FunctionMiddleware> {
+        Box::new(PassThrough)
+    }
+
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) -> Result<(), MiddlewareError> {
+        let global_indexes = module_info.globals.keys().collect::<Vec<_>>();
+
+        for index in global_indexes {
+            let exported = module_info
+                .exports
+                .values()
+                .any(|export| *export == ExportIndex::Global(index));
+
+            if !exported {
+                module_info.exports.insert(
+                    format!("{GLOBAL_EXPORT_PREFIX}{}", index.as_u32()),
+                    ExportIndex::Global(index),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct PassThrough;
+
+impl FunctionMiddleware for PassThrough {}
+
+/// State of an instance right after instantiation: its memory and mutable globals.
+pub struct InstanceSnapshot {
+    memory: Vec<u8>,
+    globals: Vec<(String, Value)>,
+}
+
+impl InstanceSnapshot {
+    /// Captures the state of an instance of `module`, or returns `None` if one of
+    /// its mutable globals isn't exported, e.g. when it was compiled without `ExportGlobals`.
+    pub fn capture(
+        module: &Module,
+        instance: &wasmer::Instance,
+        memory: &Memory,
+        store: &mut impl AsStoreMut,
+    ) -> Option<Self> {
+        let info = module.info();
+        let mut globals = vec![];
+
+        for (index, global_type) in info.globals.iter() {
+            if global_type.mutability != Mutability::Var {
+                continue;
+            }
+
+            let name = info
+                .exports
+                .iter()
+                .find(|(_, export)| **export == ExportIndex::Global(index))
+                .map(|(name, _)| name.clone())?;
+
+            let value = instance.exports.get_global(&name).ok()?.get(store);
+            globals.push((name, value));
+        }
+
+        let memory = memory.view(store).copy_to_vec().ok()?;
+
+        Some(Self { memory, globals })
+    }
+
+    /// Restores the captured state. Returns false if the instance can't be restored,
+    /// because its memory grew since the snapshot was taken.
+    pub fn restore(
+        &self,
+        instance: &wasmer::Instance,
+        memory: &Memory,
+        store: &mut impl AsStoreMut,
+    ) -> bool {
+        let view = memory.view(store);
+        if view.data_size() != self.memory.len() as u64 || view.write(0, &self.memory).is_err() {
+            return false;
+        }
+
+        self.globals.iter().all(|(name, value)| {
+            instance
+                .exports
+                .get_global(name)
+                .is_ok_and(|global| global.set(store, value.clone()).is_ok())
+        })
+    }
+}
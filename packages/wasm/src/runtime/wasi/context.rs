@@ -0,0 +1,171 @@
+use std::{
+    fs::File,
+    io,
+    path::{Component, Path, PathBuf},
+};
+
+use polywrap_core::wasi_policy::WasiPolicy;
+
+use super::errno::{self, Errno};
+
+pub enum Descriptor {
+    Stdout,
+    Stderr,
+    Directory(Directory),
+    File {
+        file: File,
+        readable: bool,
+        writable: bool,
+    },
+}
+
+pub struct Directory {
+    /// Preopened directory this directory is in, which paths can't escape.
+    pub root: PathBuf,
+    /// Path of the directory on the host.
+    pub path: PathBuf,
+    /// Name the wrap knows the directory by, if it is preopened.
+    pub guest_path: Option<String>,
+    pub read_only: bool,
+}
+
+impl Directory {
+    /// Resolves a path relative to this directory to a path of the host,
+    /// failing if it leads outside of its preopened directory.
+    pub fn resolve(&self, path: &str) -> Result<PathBuf, Errno> {
+        let mut components = self
+            .path
+            .strip_prefix(&self.root)
+            .map_err(|_| errno::NOTCAPABLE)?
+            .components()
+            .collect::<Vec<_>>();
+
+        for component in Path::new(path).components() {
+            match component {
+                Component::Normal(_) => components.push(component),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    components.pop().ok_or(errno::NOTCAPABLE)?;
+                }
+                Component::RootDir | Component::Prefix(_) => return Err(errno::NOTCAPABLE),
+            }
+        }
+
+        let resolved = self.root.join(components.iter().collect::<PathBuf>());
+
+        // Symbolic links can still lead outside of the preopened directory
+        let root = self.root.canonicalize()?;
+        let ancestor = resolved
+            .ancestors()
+            .find(|ancestor| ancestor.exists())
+            .unwrap_or(&self.root)
+            .canonicalize()?;
+        if !ancestor.starts_with(root) {
+            return Err(errno::NOTCAPABLE);
+        }
+
+        Ok(resolved)
+    }
+
+    /// Fails if the wrap isn't allowed to modify this directory.
+    pub fn check_writable(&self) -> Result<(), Errno> {
+        if self.read_only {
+            Err(errno::NOTCAPABLE)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// WASI state of an invocation: the capabilities of the wrap
+/// and the file descriptors it has opened.
+pub struct WasiContext {
+    pub clock: bool,
+    pub random: bool,
+    descriptors: Vec<Option<Descriptor>>,
+}
+
+impl WasiContext {
+    pub fn new(policy: WasiPolicy) -> Self {
+        // Stdin is never readable
+        let mut descriptors = vec![
+            None,
+            policy.stdout.then_some(Descriptor::Stdout),
+            policy.stderr.then_some(Descriptor::Stderr),
+        ];
+
+        for dir in policy.preopened_dirs {
+            descriptors.push(Some(Descriptor::Directory(Directory {
+                root: dir.host_path.clone(),
+                path: dir.host_path,
+                guest_path: Some(dir.guest_path),
+                read_only: dir.read_only,
+            })));
+        }
+
+        Self {
+            clock: policy.clock,
+            random: policy.random,
+            descriptors,
+        }
+    }
+
+    pub fn get(&mut self, fd: u32) -> Result<&mut Descriptor, Errno> {
+        self.descriptors
+            .get_mut(fd as usize)
+            .and_then(|descriptor| descriptor.as_mut())
+            .ok_or(errno::BADF)
+    }
+
+    pub fn get_directory(&mut self, fd: u32) -> Result<&mut Directory, Errno> {
+        match self.get(fd)? {
+            Descriptor::Directory(directory) => Ok(directory),
+            _ => Err(errno::NOTDIR),
+        }
+    }
+
+    pub fn insert(&mut self, descriptor: Descriptor) -> u32 {
+        let free = self
+            .descriptors
+            .iter()
+            .skip(3)
+            .position(|descriptor| descriptor.is_none());
+
+        match free {
+            Some(index) => {
+                self.descriptors[index + 3] = Some(descriptor);
+                (index + 3) as u32
+            }
+            None => {
+                self.descriptors.push(Some(descriptor));
+                (self.descriptors.len() - 1) as u32
+            }
+        }
+    }
+
+    pub fn remove(&mut self, fd: u32) -> Result<Descriptor, Errno> {
+        self.descriptors
+            .get_mut(fd as usize)
+            .and_then(|descriptor| descriptor.take())
+            .ok_or(errno::BADF)
+    }
+}
+
+impl Default for WasiContext {
+    fn default() -> Self {
+        Self::new(WasiPolicy::default())
+    }
+}
+
+impl From<io::Error> for Errno {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::NotFound => errno::NOENT,
+            io::ErrorKind::PermissionDenied => errno::ACCES,
+            io::ErrorKind::AlreadyExists => errno::EXIST,
+            io::ErrorKind::InvalidInput => errno::INVAL,
+            io::ErrorKind::Unsupported => errno::NOTSUP,
+            _ => errno::IO,
+        }
+    }
+}
@@ -0,0 +1,628 @@
+//! WASI preview1 functions imported by wraps, shared by every engine.
+//!
+//! Wraps are denied every capability their `WasiPolicy` doesn't grant,
+//! calls relying on a denied capability fail with `ENOTCAPABLE` or `EBADF`.
+//! Functions that aren't implemented fail with `ENOSYS`.
+
+mod context;
+
+use std::{
+    fs::{self, Metadata, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::Path,
+    sync::OnceLock,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+pub use self::context::WasiContext;
+use self::{
+    context::{Descriptor, Directory},
+    errno::Errno,
+};
+
+use super::{host::HostMemory, instance::State};
+
+/// Namespace of the WASI preview1 imports.
+pub const WASI_MODULE: &str = "wasi_snapshot_preview1";
+
+mod errno {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Errno(pub u16);
+
+    pub const ACCES: Errno = Errno(2);
+    pub const BADF: Errno = Errno(8);
+    pub const EXIST: Errno = Errno(20);
+    pub const INVAL: Errno = Errno(28);
+    pub const IO: Errno = Errno(29);
+    pub const NOENT: Errno = Errno(44);
+    pub const NOSYS: Errno = Errno(52);
+    pub const NOTDIR: Errno = Errno(54);
+    pub const NOTSUP: Errno = Errno(58);
+    pub const SPIPE: Errno = Errno(70);
+    pub const NOTCAPABLE: Errno = Errno(76);
+}
+
+const CLOCK_REALTIME: u64 = 0;
+const CLOCK_MONOTONIC: u64 = 1;
+
+const FILETYPE_UNKNOWN: u8 = 0;
+const FILETYPE_CHARACTER_DEVICE: u8 = 2;
+const FILETYPE_DIRECTORY: u8 = 3;
+const FILETYPE_REGULAR_FILE: u8 = 4;
+const FILETYPE_SYMBOLIC_LINK: u8 = 7;
+
+const OFLAGS_CREAT: u64 = 1;
+const OFLAGS_DIRECTORY: u64 = 2;
+const OFLAGS_EXCL: u64 = 4;
+const OFLAGS_TRUNC: u64 = 8;
+
+const FDFLAGS_APPEND: u64 = 1;
+
+const LOOKUPFLAGS_SYMLINK_FOLLOW: u64 = 1;
+
+const RIGHTS_FD_READ: u64 = 1 << 1;
+const RIGHTS_FD_WRITE: u64 = 1 << 6;
+const RIGHTS_ALL: u64 = (1 << 30) - 1;
+
+// Outcome of a failed call: an error code returned to the wrap,
+// or a message the calling instance traps with
+enum Failure {
+    Errno(Errno),
+    Trap(String),
+}
+
+impl From<Errno> for Failure {
+    fn from(errno: Errno) -> Self {
+        Failure::Errno(errno)
+    }
+}
+
+impl From<io::Error> for Failure {
+    fn from(error: io::Error) -> Self {
+        Failure::Errno(error.into())
+    }
+}
+
+impl From<String> for Failure {
+    fn from(message: String) -> Self {
+        Failure::Trap(message)
+    }
+}
+
+/// Calls the WASI function `name` with the parameters of the import,
+/// returning the error code the wrap receives.
+/// Parameters are passed as `u64`, whether they are `i32` or `i64`.
+pub fn call(
+    state: &mut State,
+    memory: &mut dyn HostMemory,
+    name: &str,
+    params: &[u64],
+) -> Result<i32, String> {
+    let context = &mut state.wasi;
+
+    let result = match (name, params) {
+        ("args_get" | "environ_get", [_, _]) => Ok(()),
+        ("args_sizes_get" | "environ_sizes_get", &[count, size]) => {
+            write_u32(memory, count, 0).and_then(|_| write_u32(memory, size, 0))
+        }
+        ("clock_res_get", &[id, resolution]) => clock_res_get(context, memory, id, resolution),
+        ("clock_time_get", &[id, _, time]) => clock_time_get(context, memory, id, time),
+        ("random_get", &[buffer, length]) => random_get(context, memory, buffer, length),
+        ("fd_write", &[fd, iovs, iovs_len, written]) => {
+            fd_write(context, memory, fd, iovs, iovs_len, written)
+        }
+        ("fd_read", &[fd, iovs, iovs_len, read]) => {
+            fd_read(context, memory, fd, iovs, iovs_len, read)
+        }
+        ("fd_seek", &[fd, offset, whence, position]) => {
+            fd_seek(context, memory, fd, offset, whence, position)
+        }
+        ("fd_tell", &[fd, position]) => fd_seek(context, memory, fd, 0, 1, position),
+        ("fd_close", &[fd]) => context.remove(fd as u32).map(|_| ()).map_err(Failure::from),
+        ("fd_sync" | "fd_datasync", &[fd]) => fd_sync(context, fd),
+        ("fd_fdstat_get", &[fd, stat]) => fd_fdstat_get(context, memory, fd, stat),
+        ("fd_filestat_get", &[fd, stat]) => fd_filestat_get(context, memory, fd, stat),
+        ("fd_prestat_get", &[fd, prestat]) => fd_prestat_get(context, memory, fd, prestat),
+        ("fd_prestat_dir_name", &[fd, path, length]) => {
+            fd_prestat_dir_name(context, memory, fd, path, length)
+        }
+        ("fd_readdir", &[fd, buffer, length, cookie, used]) => {
+            fd_readdir(context, memory, fd, buffer, length, cookie, used)
+        }
+        ("path_open", &[fd, _, path, path_len, oflags, rights, _, fdflags, opened]) => path_open(
+            context, memory, fd, path, path_len, oflags, rights, fdflags, opened,
+        ),
+        ("path_filestat_get", &[fd, flags, path, path_len, stat]) => {
+            path_filestat_get(context, memory, fd, flags, path, path_len, stat)
+        }
+        ("path_create_directory", &[fd, path, path_len]) => {
+            modify_path(context, memory, fd, path, path_len, |path| {
+                fs::create_dir(path)
+            })
+        }
+        ("path_remove_directory", &[fd, path, path_len]) => {
+            modify_path(context, memory, fd, path, path_len, |path| {
+                fs::remove_dir(path)
+            })
+        }
+        ("path_unlink_file", &[fd, path, path_len]) => {
+            modify_path(context, memory, fd, path, path_len, |path| {
+                fs::remove_file(path)
+            })
+        }
+        ("sched_yield", []) => Ok(()),
+        ("proc_exit", &[code]) => Err(Failure::Trap(format!(
+            "proc_exit: wrap exited with code {code}"
+        ))),
+        _ => Err(errno::NOSYS.into()),
+    };
+
+    match result {
+        Ok(()) => Ok(0),
+        Err(Failure::Errno(errno)) => Ok(errno.0 as i32),
+        Err(Failure::Trap(message)) => Err(message),
+    }
+}
+
+fn write_u32(memory: &mut dyn HostMemory, pointer: u64, value: u32) -> Result<(), Failure> {
+    Ok(memory.write(pointer as u32, &value.to_le_bytes())?)
+}
+
+fn write_u64(memory: &mut dyn HostMemory, pointer: u64, value: u64) -> Result<(), Failure> {
+    Ok(memory.write(pointer as u32, &value.to_le_bytes())?)
+}
+
+fn read_path(memory: &mut dyn HostMemory, pointer: u64, length: u64) -> Result<String, Failure> {
+    let bytes = memory.read(pointer as u32, length as u32)?;
+    String::from_utf8(bytes).map_err(|_| errno::INVAL.into())
+}
+
+// Reads the buffers of an array of `iovec`, as pairs of pointer and length
+fn read_iovecs(
+    memory: &mut dyn HostMemory,
+    iovs: u64,
+    iovs_len: u64,
+) -> Result<Vec<(u32, u32)>, Failure> {
+    let length = (iovs_len as u32).checked_mul(8).ok_or(errno::INVAL)?;
+    let bytes = memory.read(iovs as u32, length)?;
+
+    Ok(bytes
+        .chunks_exact(8)
+        .map(|iovec| {
+            let pointer = u32::from_le_bytes(iovec[0..4].try_into().unwrap());
+            let length = u32::from_le_bytes(iovec[4..8].try_into().unwrap());
+            (pointer, length)
+        })
+        .collect())
+}
+
+fn clock_res_get(
+    context: &mut WasiContext,
+    memory: &mut dyn HostMemory,
+    id: u64,
+    resolution: u64,
+) -> Result<(), Failure> {
+    if !context.clock {
+        return Err(errno::NOTCAPABLE.into());
+    }
+
+    match id {
+        CLOCK_REALTIME | CLOCK_MONOTONIC => write_u64(memory, resolution, 1_000),
+        _ => Err(errno::INVAL.into()),
+    }
+}
+
+fn clock_time_get(
+    context: &mut WasiContext,
+    memory: &mut dyn HostMemory,
+    id: u64,
+    time: u64,
+) -> Result<(), Failure> {
+    // Monotonic time is measured from the first time it is read
+    static START: OnceLock<Instant> = OnceLock::new();
+
+    if !context.clock {
+        return Err(errno::NOTCAPABLE.into());
+    }
+
+    let nanoseconds = match id {
+        CLOCK_REALTIME => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| errno::IO)?
+            .as_nanos(),
+        CLOCK_MONOTONIC => START.get_or_init(Instant::now).elapsed().as_nanos(),
+        _ => return Err(errno::INVAL.into()),
+    };
+
+    write_u64(memory, time, nanoseconds as u64)
+}
+
+fn random_get(
+    context: &mut WasiContext,
+    memory: &mut dyn HostMemory,
+    buffer: u64,
+    length: u64,
+) -> Result<(), Failure> {
+    if !context.random {
+        return Err(errno::NOTCAPABLE.into());
+    }
+
+    let mut bytes = vec![0; length as u32 as usize];
+    getrandom::getrandom(&mut bytes).map_err(|_| errno::IO)?;
+
+    Ok(memory.write(buffer as u32, &bytes)?)
+}
+
+fn fd_write(
+    context: &mut WasiContext,
+    memory: &mut dyn HostMemory,
+    fd: u64,
+    iovs: u64,
+    iovs_len: u64,
+    written: u64,
+) -> Result<(), Failure> {
+    let mut data = vec![];
+    for (pointer, length) in read_iovecs(memory, iovs, iovs_len)? {
+        data.extend(memory.read(pointer, length)?);
+    }
+
+    match context.get(fd as u32)? {
+        Descriptor::Stdout => io::stdout().write_all(&data)?,
+        Descriptor::Stderr => io::stderr().write_all(&data)?,
+        Descriptor::File {
+            file,
+            writable: true,
+            ..
+        } => file.write_all(&data)?,
+        _ => return Err(errno::BADF.into()),
+    }
+
+    write_u32(memory, written, data.len() as u32)
+}
+
+fn fd_read(
+    context: &mut WasiContext,
+    memory: &mut dyn HostMemory,
+    fd: u64,
+    iovs: u64,
+    iovs_len: u64,
+    read: u64,
+) -> Result<(), Failure> {
+    let iovecs = read_iovecs(memory, iovs, iovs_len)?;
+
+    let file = match context.get(fd as u32)? {
+        Descriptor::File {
+            file,
+            readable: true,
+            ..
+        } => file,
+        _ => return Err(errno::BADF.into()),
+    };
+
+    let mut total = 0;
+    for (pointer, length) in iovecs {
+        let mut buffer = vec![0; length as usize];
+        let count = file.read(&mut buffer)?;
+        memory.write(pointer, &buffer[..count])?;
+        total += count;
+
+        if count < buffer.len() {
+            break;
+        }
+    }
+
+    write_u32(memory, read, total as u32)
+}
+
+fn fd_seek(
+    context: &mut WasiContext,
+    memory: &mut dyn HostMemory,
+    fd: u64,
+    offset: u64,
+    whence: u64,
+    position: u64,
+) -> Result<(), Failure> {
+    let file = match context.get(fd as u32)? {
+        Descriptor::File { file, .. } => file,
+        Descriptor::Stdout | Descriptor::Stderr => return Err(errno::SPIPE.into()),
+        Descriptor::Directory(_) => return Err(errno::BADF.into()),
+    };
+
+    let offset = offset as i64;
+    let seek = match whence {
+        0 => SeekFrom::Start(offset as u64),
+        1 => SeekFrom::Current(offset),
+        2 => SeekFrom::End(offset),
+        _ => return Err(errno::INVAL.into()),
+    };
+
+    let new_position = file.seek(seek)?;
+    write_u64(memory, position, new_position)
+}
+
+fn fd_sync(context: &mut WasiContext, fd: u64) -> Result<(), Failure> {
+    match context.get(fd as u32)? {
+        Descriptor::File { file, .. } => Ok(file.sync_all()?),
+        _ => Err(errno::BADF.into()),
+    }
+}
+
+fn fd_fdstat_get(
+    context: &mut WasiContext,
+    memory: &mut dyn HostMemory,
+    fd: u64,
+    stat: u64,
+) -> Result<(), Failure> {
+    let (filetype, rights_base, rights_inheriting) = match context.get(fd as u32)? {
+        Descriptor::Stdout | Descriptor::Stderr => (FILETYPE_CHARACTER_DEVICE, RIGHTS_FD_WRITE, 0),
+        Descriptor::Directory(_) => (FILETYPE_DIRECTORY, RIGHTS_ALL, RIGHTS_ALL),
+        Descriptor::File {
+            readable, writable, ..
+        } => {
+            let mut rights = 0;
+            if *readable {
+                rights |= RIGHTS_FD_READ;
+            }
+            if *writable {
+                rights |= RIGHTS_FD_WRITE;
+            }
+            (
+                FILETYPE_REGULAR_FILE,
+                RIGHTS_ALL & !(RIGHTS_FD_READ | RIGHTS_FD_WRITE) | rights,
+                0,
+            )
+        }
+    };
+
+    let mut bytes = [0; 24];
+    bytes[0] = filetype;
+    bytes[8..16].copy_from_slice(&rights_base.to_le_bytes());
+    bytes[16..24].copy_from_slice(&rights_inheriting.to_le_bytes());
+
+    Ok(memory.write(stat as u32, &bytes)?)
+}
+
+fn write_filestat(
+    memory: &mut dyn HostMemory,
+    stat: u64,
+    metadata: Option<&Metadata>,
+) -> Result<(), Failure> {
+    let mut bytes = [0; 64];
+
+    match metadata {
+        Some(metadata) => {
+            let file_type = metadata.file_type();
+            bytes[16] = if file_type.is_dir() {
+                FILETYPE_DIRECTORY
+            } else if file_type.is_file() {
+                FILETYPE_REGULAR_FILE
+            } else if file_type.is_symlink() {
+                FILETYPE_SYMBOLIC_LINK
+            } else {
+                FILETYPE_UNKNOWN
+            };
+
+            let timestamp = |time: io::Result<SystemTime>| {
+                time.ok()
+                    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |duration| duration.as_nanos() as u64)
+            };
+
+            bytes[24..32].copy_from_slice(&1u64.to_le_bytes());
+            bytes[32..40].copy_from_slice(&metadata.len().to_le_bytes());
+            bytes[40..48].copy_from_slice(&timestamp(metadata.accessed()).to_le_bytes());
+            bytes[48..56].copy_from_slice(&timestamp(metadata.modified()).to_le_bytes());
+            bytes[56..64].copy_from_slice(&timestamp(metadata.created()).to_le_bytes());
+        }
+        None => bytes[16] = FILETYPE_CHARACTER_DEVICE,
+    }
+
+    Ok(memory.write(stat as u32, &bytes)?)
+}
+
+fn fd_filestat_get(
+    context: &mut WasiContext,
+    memory: &mut dyn HostMemory,
+    fd: u64,
+    stat: u64,
+) -> Result<(), Failure> {
+    let metadata = match context.get(fd as u32)? {
+        Descriptor::Stdout | Descriptor::Stderr => None,
+        Descriptor::Directory(directory) => Some(fs::metadata(&directory.path)?),
+        Descriptor::File { file, .. } => Some(file.metadata()?),
+    };
+
+    write_filestat(memory, stat, metadata.as_ref())
+}
+
+fn preopened_name(context: &mut WasiContext, fd: u64) -> Result<String, Failure> {
+    match context.get(fd as u32)? {
+        Descriptor::Directory(Directory {
+            guest_path: Some(guest_path),
+            ..
+        }) => Ok(guest_path.clone()),
+        _ => Err(errno::BADF.into()),
+    }
+}
+
+fn fd_prestat_get(
+    context: &mut WasiContext,
+    memory: &mut dyn HostMemory,
+    fd: u64,
+    prestat: u64,
+) -> Result<(), Failure> {
+    let name = preopened_name(context, fd)?;
+
+    // Tag of directories, followed by the length of their name
+    let mut bytes = [0; 8];
+    bytes[4..8].copy_from_slice(&(name.len() as u32).to_le_bytes());
+
+    Ok(memory.write(prestat as u32, &bytes)?)
+}
+
+fn fd_prestat_dir_name(
+    context: &mut WasiContext,
+    memory: &mut dyn HostMemory,
+    fd: u64,
+    path: u64,
+    length: u64,
+) -> Result<(), Failure> {
+    let name = preopened_name(context, fd)?;
+    let length = name.len().min(length as u32 as usize);
+
+    Ok(memory.write(path as u32, &name.as_bytes()[..length])?)
+}
+
+fn fd_readdir(
+    context: &mut WasiContext,
+    memory: &mut dyn HostMemory,
+    fd: u64,
+    buffer: u64,
+    length: u64,
+    cookie: u64,
+    used: u64,
+) -> Result<(), Failure> {
+    let directory = context.get_directory(fd as u32)?;
+
+    let mut entries = fs::read_dir(&directory.path)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    // Entries are written up to the length of the buffer,
+    // a full buffer tells the wrap to read the rest with a later call
+    let mut bytes = vec![];
+    for (index, entry) in entries.iter().enumerate().skip(cookie as usize) {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let file_type = entry.file_type()?;
+        let filetype = if file_type.is_dir() {
+            FILETYPE_DIRECTORY
+        } else if file_type.is_file() {
+            FILETYPE_REGULAR_FILE
+        } else if file_type.is_symlink() {
+            FILETYPE_SYMBOLIC_LINK
+        } else {
+            FILETYPE_UNKNOWN
+        };
+
+        let mut dirent = [0; 24];
+        dirent[0..8].copy_from_slice(&(index as u64 + 1).to_le_bytes());
+        dirent[16..20].copy_from_slice(&(name.len() as u32).to_le_bytes());
+        dirent[20] = filetype;
+
+        bytes.extend_from_slice(&dirent);
+        bytes.extend_from_slice(name.as_bytes());
+
+        if bytes.len() >= length as u32 as usize {
+            break;
+        }
+    }
+
+    bytes.truncate(length as u32 as usize);
+    memory.write(buffer as u32, &bytes)?;
+    write_u32(memory, used, bytes.len() as u32)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn path_open(
+    context: &mut WasiContext,
+    memory: &mut dyn HostMemory,
+    fd: u64,
+    path: u64,
+    path_len: u64,
+    oflags: u64,
+    rights: u64,
+    fdflags: u64,
+    opened: u64,
+) -> Result<(), Failure> {
+    let path = read_path(memory, path, path_len)?;
+    let directory = context.get_directory(fd as u32)?;
+    let resolved = directory.resolve(&path)?;
+
+    let create = oflags & OFLAGS_CREAT != 0;
+    let truncate = oflags & OFLAGS_TRUNC != 0;
+    let append = fdflags & FDFLAGS_APPEND != 0;
+    let writable = rights & RIGHTS_FD_WRITE != 0 || append;
+    let readable = rights & RIGHTS_FD_READ != 0 || !writable;
+
+    let descriptor = if oflags & OFLAGS_DIRECTORY != 0 || (resolved.is_dir() && !writable) {
+        if create {
+            return Err(errno::INVAL.into());
+        }
+
+        if !resolved.is_dir() {
+            return Err(if resolved.exists() {
+                errno::NOTDIR
+            } else {
+                errno::NOENT
+            }
+            .into());
+        }
+
+        Descriptor::Directory(Directory {
+            root: directory.root.clone(),
+            path: resolved,
+            guest_path: None,
+            read_only: directory.read_only,
+        })
+    } else {
+        if writable || create || truncate {
+            directory.check_writable()?;
+        }
+
+        let file = OpenOptions::new()
+            .read(readable)
+            .write(writable && !append)
+            .append(append)
+            .create(create)
+            .create_new(create && oflags & OFLAGS_EXCL != 0)
+            .truncate(truncate)
+            .open(resolved)?;
+
+        Descriptor::File {
+            file,
+            readable,
+            writable,
+        }
+    };
+
+    let fd = context.insert(descriptor);
+    write_u32(memory, opened, fd)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn path_filestat_get(
+    context: &mut WasiContext,
+    memory: &mut dyn HostMemory,
+    fd: u64,
+    flags: u64,
+    path: u64,
+    path_len: u64,
+    stat: u64,
+) -> Result<(), Failure> {
+    let path = read_path(memory, path, path_len)?;
+    let resolved = context.get_directory(fd as u32)?.resolve(&path)?;
+
+    let metadata = if flags & LOOKUPFLAGS_SYMLINK_FOLLOW != 0 {
+        fs::metadata(resolved)?
+    } else {
+        fs::symlink_metadata(resolved)?
+    };
+
+    write_filestat(memory, stat, Some(&metadata))
+}
+
+// Applies a modification to a path, if its directory isn't read only
+fn modify_path(
+    context: &mut WasiContext,
+    memory: &mut dyn HostMemory,
+    fd: u64,
+    path: u64,
+    path_len: u64,
+    modify: impl FnOnce(&Path) -> io::Result<()>,
+) -> Result<(), Failure> {
+    let path = read_path(memory, path, path_len)?;
+    let directory = context.get_directory(fd as u32)?;
+    directory.check_writable()?;
+
+    Ok(modify(&directory.resolve(&path)?)?)
+}
@@ -0,0 +1,116 @@
+use std::sync::{Arc, Mutex};
+
+use polywrap_core::resource_limits::ResourceLimits;
+use wasmer::{sys::EngineBuilder, CompilerConfig, Cranelift, Module, Store, Target};
+
+use crate::error::WrapperError;
+
+use super::{
+    engine::{EngineInstance, EngineModule, WasmEngine},
+    instance::{State, WasmInstance},
+    metering::Metering,
+    snapshot::ExportGlobals,
+};
+
+/// Version of the middlewares modules are compiled with.
+/// Must be bumped whenever they change, so modules serialized by
+/// previous versions stop being deserialized.
+const MIDDLEWARES_VERSION: u32 = 2;
+
+/// Engine running wraps on wasmer, with the Cranelift compiler.
+///
+/// Modules are compiled with all their globals exported so their instances can be pooled,
+/// and, unless the engine is `unmetered`, with the metering middleware so their execution
+/// can be interrupted and their fuel consumption bounded.
+///
+/// Metering checks the remaining points at the end of every basic block, which makes
+/// tight branching loops run up to ~75% slower (see the `metering_overhead` example).
+/// Engines only running trusted wraps without fuel limits, timeouts or cancellation
+/// can skip it.
+#[derive(Debug, Clone, Copy)]
+pub struct WasmerEngine {
+    metered: bool,
+}
+
+impl WasmerEngine {
+    pub fn new() -> Self {
+        Self { metered: true }
+    }
+
+    /// Creates an engine compiling modules without the metering middleware.
+    ///
+    /// Invocations of these modules run without a fuel budget and can't be
+    /// interrupted once they time out or are cancelled.
+    pub fn unmetered() -> Self {
+        Self { metered: false }
+    }
+}
+
+impl Default for WasmerEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WasmEngine for WasmerEngine {
+    fn key(&self) -> String {
+        let target = Target::default();
+
+        format!(
+            "wasmer-{}:{}:{:?}:{}{}",
+            wasmer::VERSION,
+            target.triple(),
+            target.cpu_features(),
+            MIDDLEWARES_VERSION,
+            if self.metered { "" } else { ":unmetered" }
+        )
+    }
+
+    fn compile(&self, bytes: &[u8]) -> Result<Arc<dyn EngineModule>, WrapperError> {
+        let mut compiler = Cranelift::default();
+        if self.metered {
+            compiler.push_middleware(Arc::new(Metering::new()));
+        }
+        compiler.push_middleware(Arc::new(ExportGlobals::new()));
+        let store = Store::new(EngineBuilder::new(compiler));
+        let module = Module::new(&store, bytes)?;
+
+        Ok(Arc::new(WasmerModule {
+            module,
+            store: Arc::new(store),
+        }))
+    }
+
+    unsafe fn deserialize(&self, bytes: &[u8]) -> Result<Arc<dyn EngineModule>, WrapperError> {
+        let store = Store::default();
+        let module = Module::deserialize(&store, bytes)?;
+
+        Ok(Arc::new(WasmerModule {
+            module,
+            store: Arc::new(store),
+        }))
+    }
+}
+
+/// Module compiled by a `WasmerEngine`.
+pub struct WasmerModule {
+    pub module: Module,
+    pub store: Arc<Store>,
+}
+
+impl EngineModule for WasmerModule {
+    fn instantiate(
+        &self,
+        memory_initial_limits: u8,
+        state: Arc<Mutex<State>>,
+        resource_limits: &ResourceLimits,
+    ) -> Result<Box<dyn EngineInstance>, WrapperError> {
+        let instance =
+            WasmInstance::new(&self.module, memory_initial_limits, state, resource_limits)?;
+        Ok(Box::new(instance))
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, WrapperError> {
+        Ok(self.module.serialize()?.into())
+    }
+}
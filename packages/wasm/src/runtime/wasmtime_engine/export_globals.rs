@@ -0,0 +1,172 @@
+use wasmparser::{BinaryReader, ImportSectionReader, TypeRef};
+
+use crate::{error::WrapperError, runtime::snapshot::GLOBAL_EXPORT_PREFIX};
+
+const IMPORT_SECTION: u8 = 2;
+const GLOBAL_SECTION: u8 = 6;
+const EXPORT_SECTION: u8 = 7;
+// Sections following the export section, in the order of the binary format
+const SECTIONS_AFTER_EXPORTS: [u8; 5] = [8, 9, 12, 10, 11];
+
+const GLOBAL_EXTERNAL_KIND: u8 = 3;
+
+struct Section<'a> {
+    id: u8,
+    content: &'a [u8],
+}
+
+/// Rewrites a module so that every global it defines is exported,
+/// like the `ExportGlobals` middleware does for wasmer.
+/// Globals that aren't exported yet are exported as `__wrap_global_{index}`.
+pub fn export_globals(bytes: &[u8]) -> Result<Vec<u8>, WrapperError> {
+    let invalid =
+        |message: &str| WrapperError::ModuleReadError(format!("Invalid module: {message}"));
+
+    if bytes.len() < 8 {
+        return Err(invalid("missing header"));
+    }
+
+    let mut sections = vec![];
+    let mut position = 8;
+    while position < bytes.len() {
+        let id = bytes[position];
+        position += 1;
+        let size = read_u32(bytes, &mut position).ok_or_else(|| invalid("truncated section"))?;
+        let end = position + size as usize;
+        let content = bytes
+            .get(position..end)
+            .ok_or_else(|| invalid("truncated section"))?;
+        sections.push(Section { id, content });
+        position = end;
+    }
+
+    let mut imported_globals = 0;
+    let mut defined_globals = 0;
+    let mut exports: Option<(u32, &[u8])> = None;
+    let mut exported_globals = vec![];
+
+    for section in &sections {
+        match section.id {
+            IMPORT_SECTION => {
+                let reader = ImportSectionReader::new(BinaryReader::new(section.content, 0))
+                    .map_err(|e| invalid(&e.to_string()))?;
+                for import in reader {
+                    let import = import.map_err(|e| invalid(&e.to_string()))?;
+                    if matches!(import.ty, TypeRef::Global(_)) {
+                        imported_globals += 1;
+                    }
+                }
+            }
+            GLOBAL_SECTION => {
+                defined_globals = read_u32(section.content, &mut 0)
+                    .ok_or_else(|| invalid("truncated global section"))?;
+            }
+            EXPORT_SECTION => {
+                let mut position = 0;
+                let count = read_u32(section.content, &mut position)
+                    .ok_or_else(|| invalid("truncated export section"))?;
+                let entries = &section.content[position..];
+
+                for _ in 0..count {
+                    let name_length = read_u32(section.content, &mut position)
+                        .ok_or_else(|| invalid("truncated export section"))?;
+                    position += name_length as usize;
+                    let kind = *section
+                        .content
+                        .get(position)
+                        .ok_or_else(|| invalid("truncated export section"))?;
+                    position += 1;
+                    let index = read_u32(section.content, &mut position)
+                        .ok_or_else(|| invalid("truncated export section"))?;
+
+                    if kind == GLOBAL_EXTERNAL_KIND {
+                        exported_globals.push(index);
+                    }
+                }
+
+                exports = Some((count, entries));
+            }
+            _ => {}
+        }
+    }
+
+    let unexported_globals = (imported_globals..imported_globals + defined_globals)
+        .filter(|index| !exported_globals.contains(index))
+        .collect::<Vec<_>>();
+
+    if unexported_globals.is_empty() {
+        return Ok(bytes.to_vec());
+    }
+
+    let (count, entries) = exports.unwrap_or((0, &[]));
+    let mut export_section = vec![];
+    write_u32(&mut export_section, count + unexported_globals.len() as u32);
+    export_section.extend_from_slice(entries);
+    for index in unexported_globals {
+        let name = format!("{GLOBAL_EXPORT_PREFIX}{index}");
+        write_u32(&mut export_section, name.len() as u32);
+        export_section.extend_from_slice(name.as_bytes());
+        export_section.push(GLOBAL_EXTERNAL_KIND);
+        write_u32(&mut export_section, index);
+    }
+
+    let mut output = bytes[..8].to_vec();
+    let mut export_section = Some(export_section);
+    for section in sections {
+        if section.id == EXPORT_SECTION {
+            continue;
+        }
+
+        if SECTIONS_AFTER_EXPORTS.contains(&section.id) {
+            if let Some(export_section) = export_section.take() {
+                write_section(&mut output, EXPORT_SECTION, &export_section);
+            }
+        }
+
+        write_section(&mut output, section.id, section.content);
+    }
+
+    if let Some(export_section) = export_section {
+        write_section(&mut output, EXPORT_SECTION, &export_section);
+    }
+
+    Ok(output)
+}
+
+fn write_section(output: &mut Vec<u8>, id: u8, content: &[u8]) {
+    output.push(id);
+    write_u32(output, content.len() as u32);
+    output.extend_from_slice(content);
+}
+
+// Reads an unsigned LEB128 integer
+fn read_u32(bytes: &[u8], position: &mut usize) -> Option<u32> {
+    let mut result: u32 = 0;
+
+    for shift in (0..35).step_by(7) {
+        let byte = *bytes.get(*position)?;
+        *position += 1;
+        result |= ((byte & 0x7f) as u32) << shift;
+
+        if byte & 0x80 == 0 {
+            return Some(result);
+        }
+    }
+
+    None
+}
+
+// Writes an unsigned LEB128 integer
+fn write_u32(output: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            output.push(byte);
+            return;
+        }
+
+        output.push(byte | 0x80);
+    }
+}
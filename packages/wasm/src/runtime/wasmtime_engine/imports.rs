@@ -0,0 +1,359 @@
+use std::sync::{Arc, Mutex};
+
+use wasmtime::{Caller, Engine, ExternType, Linker, Memory, Module, ResourceLimiter, Val};
+
+use crate::{
+    error::WrapperError,
+    runtime::{
+        host::{self, HostMemory},
+        instance::State,
+        wasi::{self, WASI_MODULE},
+    },
+};
+
+/// Data of the store of a wasmtime instance.
+pub struct StoreData {
+    pub state: Arc<Mutex<State>>,
+    pub memory: Option<Memory>,
+    pub limiter: MemoryLimiter,
+}
+
+/// Bounds the memory of an instance to a number of pages, recording denied growths.
+pub struct MemoryLimiter {
+    pub max_memory_pages: Option<u32>,
+    pub growth_denied: bool,
+}
+
+impl ResourceLimiter for MemoryLimiter {
+    fn memory_growing(
+        &mut self,
+        _: usize,
+        desired: usize,
+        _: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        const PAGE_SIZE: usize = 65536;
+
+        let allowed = self.max_memory_pages.map_or(true, |max_memory_pages| {
+            desired <= max_memory_pages as usize * PAGE_SIZE
+        });
+
+        if !allowed {
+            self.growth_denied = true;
+        }
+
+        Ok(allowed)
+    }
+
+    fn table_growing(&mut self, _: usize, _: usize, _: Option<usize>) -> wasmtime::Result<bool> {
+        Ok(true)
+    }
+}
+
+struct WasmtimeMemory<'a, 'b> {
+    memory: Memory,
+    caller: &'a mut Caller<'b, StoreData>,
+}
+
+impl HostMemory for WasmtimeMemory<'_, '_> {
+    fn read(&mut self, offset: u32, length: u32) -> Result<Vec<u8>, String> {
+        let mut buffer = vec![0; length as usize];
+        self.memory
+            .read(&*self.caller, offset as usize, &mut buffer)
+            .map_err(|e| e.to_string())?;
+        Ok(buffer)
+    }
+
+    fn write(&mut self, offset: u32, data: &[u8]) -> Result<(), String> {
+        self.memory
+            .write(&mut *self.caller, offset as usize, data)
+            .map_err(|e| e.to_string())
+    }
+}
+
+// Runs a host function with the state and memory of the calling instance
+fn call_host<T>(
+    mut caller: Caller<'_, StoreData>,
+    function: impl FnOnce(&mut State, &mut dyn HostMemory) -> Result<T, String>,
+) -> wasmtime::Result<T> {
+    let state = caller.data().state.clone();
+    let mut state = state.lock().unwrap();
+    let memory = caller
+        .data()
+        .memory
+        .ok_or_else(|| wasmtime::Error::msg("Memory is not set"))?;
+    let mut memory = WasmtimeMemory {
+        memory,
+        caller: &mut caller,
+    };
+
+    function(&mut state, &mut memory).map_err(wasmtime::Error::msg)
+}
+
+/// Creates a linker defining the host functions of the WRAP standard.
+/// The memory imported by modules must be defined for each store.
+pub fn create_linker(engine: &Engine) -> Result<Linker<StoreData>, WrapperError> {
+    let mut linker = Linker::new(engine);
+
+    define_imports(&mut linker).map_err(|e| WrapperError::WasmRuntimeError(e.to_string()))?;
+
+    Ok(linker)
+}
+
+fn define_imports(linker: &mut Linker<StoreData>) -> wasmtime::Result<()> {
+    linker
+        .func_wrap(
+            "wrap",
+            "__wrap_invoke_args",
+            |caller: Caller<'_, StoreData>, method_ptr: u32, args_ptr: u32| {
+                call_host(caller, |state, memory| {
+                    host::invoke_args(state, memory, method_ptr, args_ptr)
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_invoke_result",
+            |caller: Caller<'_, StoreData>, offset: u32, length: u32| {
+                call_host(caller, |state, memory| {
+                    host::invoke_result(state, memory, offset, length)
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_invoke_error",
+            |caller: Caller<'_, StoreData>, offset: u32, length: u32| {
+                call_host(caller, |state, memory| {
+                    host::invoke_error(state, memory, offset, length)
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_abort",
+            |caller: Caller<'_, StoreData>,
+             msg_offset: u32,
+             msg_length: u32,
+             file_offset: u32,
+             file_length: u32,
+             line: u32,
+             column: u32| {
+                call_host(caller, |state, memory| {
+                    host::abort(
+                        state,
+                        memory,
+                        msg_offset,
+                        msg_length,
+                        file_offset,
+                        file_length,
+                        line,
+                        column,
+                    )
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_subinvoke",
+            |caller: Caller<'_, StoreData>,
+             uri_ptr: u32,
+             uri_len: u32,
+             method_ptr: u32,
+             method_len: u32,
+             args_ptr: u32,
+             args_len: u32| {
+                call_host(caller, |state, memory| {
+                    host::subinvoke(
+                        state, memory, uri_ptr, uri_len, method_ptr, method_len, args_ptr, args_len,
+                    )
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_subinvoke_result_len",
+            |caller: Caller<'_, StoreData>| {
+                call_host(caller, |state, _| host::subinvoke_result_len(state))
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_subinvoke_result",
+            |caller: Caller<'_, StoreData>, pointer: u32| {
+                call_host(caller, |state, memory| {
+                    host::subinvoke_result(state, memory, pointer)
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_subinvoke_error_len",
+            |caller: Caller<'_, StoreData>| {
+                call_host(caller, |state, _| host::subinvoke_error_len(state))
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_subinvoke_error",
+            |caller: Caller<'_, StoreData>, pointer: u32| {
+                call_host(caller, |state, memory| {
+                    host::subinvoke_error(state, memory, pointer)
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_subinvokeImplementation",
+            |caller: Caller<'_, StoreData>,
+             interface_ptr: u32,
+             interface_len: u32,
+             impl_uri_ptr: u32,
+             impl_uri_len: u32,
+             method_ptr: u32,
+             method_len: u32,
+             args_ptr: u32,
+             args_len: u32| {
+                call_host(caller, |state, memory| {
+                    host::subinvoke_implementation(
+                        state,
+                        memory,
+                        interface_ptr,
+                        interface_len,
+                        impl_uri_ptr,
+                        impl_uri_len,
+                        method_ptr,
+                        method_len,
+                        args_ptr,
+                        args_len,
+                    )
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_subinvokeImplementation_result_len",
+            |caller: Caller<'_, StoreData>| {
+                call_host(caller, |state, _| {
+                    host::subinvoke_implementation_result_len(state)
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_subinvokeImplementation_result",
+            |caller: Caller<'_, StoreData>, pointer: u32| {
+                call_host(caller, |state, memory| {
+                    host::subinvoke_implementation_result(state, memory, pointer)
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_subinvokeImplementation_error_len",
+            |caller: Caller<'_, StoreData>| {
+                call_host(caller, |state, _| {
+                    host::subinvoke_implementation_error_len(state)
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_subinvokeImplementation_error",
+            |caller: Caller<'_, StoreData>, pointer: u32| {
+                call_host(caller, |state, memory| {
+                    host::subinvoke_implementation_error(state, memory, pointer)
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_getImplementations",
+            |caller: Caller<'_, StoreData>, pointer: u32, length: u32| {
+                call_host(caller, |state, memory| {
+                    host::get_implementations(state, memory, pointer, length)
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_getImplementations_result",
+            |caller: Caller<'_, StoreData>, pointer: u32| {
+                call_host(caller, |state, memory| {
+                    host::get_implementations_result(state, memory, pointer)
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_getImplementations_result_len",
+            |caller: Caller<'_, StoreData>| {
+                call_host(caller, |state, _| {
+                    host::get_implementations_result_len(state)
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_load_env",
+            |caller: Caller<'_, StoreData>, pointer: u32| {
+                call_host(caller, |state, memory| {
+                    host::load_env(state, memory, pointer)
+                })
+            },
+        )?
+        .func_wrap(
+            "wrap",
+            "__wrap_debug_log",
+            |caller: Caller<'_, StoreData>, offset: u32, length: u32| {
+                call_host(caller, |state, memory| {
+                    host::debug_log(state, memory, offset, length)
+                })
+            },
+        )?;
+
+    Ok(())
+}
+
+/// Defines the WASI functions the module imports, with the signatures it imports them with.
+pub fn define_wasi_imports(
+    linker: &mut Linker<StoreData>,
+    module: &Module,
+) -> wasmtime::Result<()> {
+    for import in module
+        .imports()
+        .filter(|import| import.module() == WASI_MODULE)
+    {
+        let ExternType::Func(ty) = import.ty() else {
+            continue;
+        };
+
+        let name = import.name().to_string();
+        linker.func_new(
+            WASI_MODULE,
+            import.name(),
+            ty,
+            move |caller, params, results| {
+                let params = params
+                    .iter()
+                    .map(|param| match param {
+                        Val::I32(value) => *value as u32 as u64,
+                        Val::I64(value) => *value as u64,
+                        _ => 0,
+                    })
+                    .collect::<Vec<_>>();
+
+                let errno = call_host(caller, |state, memory| {
+                    wasi::call(state, memory, &name, &params)
+                })?;
+
+                if let Some(result) = results.first_mut() {
+                    *result = Val::I32(errno);
+                }
+
+                Ok(())
+            },
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,271 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+use polywrap_core::resource_limits::ResourceLimits;
+use wasmtime::{
+    Engine, ExternType, Instance, Linker, Memory, MemoryType, Module, Mutability, Store, Trap,
+    UpdateDeadline, Val,
+};
+
+use crate::{
+    error::WrapperError,
+    runtime::{engine::EngineInstance, instance::State, interrupt::InterruptHandle},
+};
+
+use super::imports::{define_wasi_imports, MemoryLimiter, StoreData};
+
+/// Instance of a module compiled by a `WasmtimeEngine`.
+///
+/// Fuel is metered by wasmtime itself, and execution is interrupted through
+/// epoch interruption: interrupting an instance sets its flag and increments the epoch
+/// of the engine, and the instance traps once it notices the new epoch.
+pub struct WasmtimeInstance {
+    instance: Instance,
+    store: Store<StoreData>,
+    engine: Engine,
+    module: Module,
+    state: Arc<Mutex<State>>,
+    memory: Memory,
+    snapshot: Option<WasmtimeSnapshot>,
+    fuel: u64,
+    interrupted: Arc<AtomicBool>,
+    trap: Option<Trap>,
+}
+
+// Memory and mutable globals of an instance, globals being exported by `export_globals`
+struct WasmtimeSnapshot {
+    memory: Vec<u8>,
+    globals: Vec<(String, Val)>,
+}
+
+impl WasmtimeInstance {
+    pub fn new(
+        engine: &Engine,
+        linker: &Linker<StoreData>,
+        module: &Module,
+        memory_initial_limits: u8,
+        state: Arc<Mutex<State>>,
+        resource_limits: &ResourceLimits,
+    ) -> Result<Self, WrapperError> {
+        let runtime_error =
+            |e: wasmtime::Error| WrapperError::WasmRuntimeError(e.root_cause().to_string());
+
+        if let Some(max_memory_pages) = resource_limits.max_memory_pages {
+            if (memory_initial_limits as u32) > max_memory_pages {
+                return Err(WrapperError::ResourceLimitError(format!(
+                    "Module requires {memory_initial_limits} memory pages, but the limit is {max_memory_pages}"
+                )));
+            }
+        }
+
+        let mut store = Store::new(
+            engine,
+            StoreData {
+                state: state.clone(),
+                memory: None,
+                limiter: MemoryLimiter {
+                    max_memory_pages: resource_limits.max_memory_pages,
+                    growth_denied: false,
+                },
+            },
+        );
+        store.limiter(|data| &mut data.limiter);
+
+        // Start functions run during instantiation, so they are bounded by the fuel too
+        let fuel = resource_limits.fuel.unwrap_or(u64::MAX);
+        store.set_fuel(fuel).map_err(runtime_error)?;
+
+        let interrupted = Arc::new(AtomicBool::new(false));
+        let flag = interrupted.clone();
+        store.epoch_deadline_callback(move |_| {
+            if flag.load(Ordering::SeqCst) {
+                return Err(wasmtime::Error::msg("Execution was interrupted"));
+            }
+            // The epoch was incremented to interrupt another instance
+            Ok(UpdateDeadline::Continue(1))
+        });
+        store.set_epoch_deadline(1);
+
+        let memory = Memory::new(
+            &mut store,
+            MemoryType::new(memory_initial_limits as u32, None),
+        )
+        .map_err(runtime_error)?;
+        store.data_mut().memory = Some(memory);
+
+        let mut linker = linker.clone();
+        linker
+            .define(&store, "env", "memory", memory)
+            .map_err(runtime_error)?;
+        define_wasi_imports(&mut linker, module).map_err(runtime_error)?;
+        let instance = linker
+            .instantiate(&mut store, module)
+            .map_err(runtime_error)?;
+
+        Ok(Self {
+            instance,
+            store,
+            engine: engine.clone(),
+            module: module.clone(),
+            state,
+            memory,
+            snapshot: None,
+            fuel,
+            interrupted,
+            trap: None,
+        })
+    }
+
+    fn set_fuel(&mut self, fuel: u64) -> Result<(), WrapperError> {
+        self.store
+            .set_fuel(fuel)
+            .map_err(|e| WrapperError::WasmRuntimeError(e.to_string()))?;
+        self.fuel = fuel;
+        Ok(())
+    }
+}
+
+impl EngineInstance for WasmtimeInstance {
+    fn call_export(&mut self, name: &str, params: &[i32]) -> Result<bool, WrapperError> {
+        let export = self
+            .instance
+            .get_func(&mut self.store, name)
+            .ok_or_else(|| WrapperError::WasmRuntimeError(format!("Export {name} not found")))?;
+
+        let params = params
+            .iter()
+            .map(|param| Val::I32(*param))
+            .collect::<Vec<_>>();
+        let mut results = vec![Val::I32(0); export.ty(&self.store).results().len()];
+
+        self.trap = None;
+        self.store.set_epoch_deadline(1);
+
+        export
+            .call(&mut self.store, &params, &mut results)
+            .map_err(|e| {
+                self.trap = e.downcast_ref::<Trap>().copied();
+                WrapperError::WasmRuntimeError(e.root_cause().to_string())
+            })?;
+
+        // If the result is true (1), then the call was successful
+        let result = results.first().and_then(|x| x.i32()).unwrap_or(0);
+
+        Ok(result == 1)
+    }
+
+    fn read_memory(&mut self, offset: u32, length: u32) -> Result<Vec<u8>, WrapperError> {
+        let mut buffer = vec![0; length as usize];
+        self.memory
+            .read(&self.store, offset as usize, &mut buffer)
+            .map_err(|e| WrapperError::WasmRuntimeError(e.to_string()))?;
+        Ok(buffer)
+    }
+
+    fn write_memory(&mut self, offset: u32, data: &[u8]) -> Result<(), WrapperError> {
+        self.memory
+            .write(&mut self.store, offset as usize, data)
+            .map_err(|e| WrapperError::WasmRuntimeError(e.to_string()))
+    }
+
+    fn state(&self) -> Arc<Mutex<State>> {
+        self.state.clone()
+    }
+
+    fn max_memory_pages(&self) -> Option<u32> {
+        self.store.data().limiter.max_memory_pages
+    }
+
+    fn fuel_consumed(&mut self) -> Option<u64> {
+        let remaining = self.store.get_fuel().ok()?;
+        Some(self.fuel - remaining)
+    }
+
+    fn fuel_exhausted(&mut self) -> bool {
+        self.trap == Some(Trap::OutOfFuel)
+    }
+
+    fn memory_growth_denied(&self) -> bool {
+        self.store.data().limiter.growth_denied
+    }
+
+    fn interrupt_handle(&mut self) -> Option<Arc<InterruptHandle>> {
+        let interrupted = self.interrupted.clone();
+        let engine = self.engine.clone();
+
+        Some(Arc::new(InterruptHandle::new(move || {
+            interrupted.store(true, Ordering::SeqCst);
+            engine.increment_epoch();
+        })))
+    }
+
+    // Captures the memory and the mutable globals, which `WasmtimeEngine`
+    // exports when compiling modules
+    fn snapshot(&mut self) -> bool {
+        let mut globals = vec![];
+
+        for export in self.module.exports() {
+            let ExternType::Global(global_type) = export.ty() else {
+                continue;
+            };
+
+            if global_type.mutability() != Mutability::Var {
+                continue;
+            }
+
+            let Some(global) = self.instance.get_global(&mut self.store, export.name()) else {
+                return false;
+            };
+            globals.push((export.name().to_string(), global.get(&mut self.store)));
+        }
+
+        self.snapshot = Some(WasmtimeSnapshot {
+            memory: self.memory.data(&self.store).to_vec(),
+            globals,
+        });
+        true
+    }
+
+    fn restore(&mut self) -> bool {
+        *self.state.lock().unwrap() = State::released();
+
+        let Some(snapshot) = &self.snapshot else {
+            return false;
+        };
+
+        if self.memory.data_size(&self.store) != snapshot.memory.len() {
+            return false;
+        }
+        self.memory
+            .data_mut(&mut self.store)
+            .copy_from_slice(&snapshot.memory);
+
+        for (name, value) in &snapshot.globals {
+            let restored = self
+                .instance
+                .get_global(&mut self.store, name)
+                .is_some_and(|global| global.set(&mut self.store, *value).is_ok());
+
+            if !restored {
+                return false;
+            }
+        }
+
+        self.interrupted.store(false, Ordering::SeqCst);
+        self.store.data_mut().limiter.growth_denied = false;
+        self.trap = None;
+        true
+    }
+
+    fn prepare(
+        &mut self,
+        state: State,
+        resource_limits: &ResourceLimits,
+    ) -> Result<(), WrapperError> {
+        *self.state.lock().unwrap() = state;
+
+        self.set_fuel(resource_limits.fuel.unwrap_or(u64::MAX))
+    }
+}
@@ -0,0 +1,119 @@
+//! Engine running wraps on wasmtime, enabled by the `wasmtime` feature.
+
+mod export_globals;
+mod imports;
+mod instance;
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex},
+};
+
+use polywrap_core::resource_limits::ResourceLimits;
+use wasmtime::{Config, Engine, Linker, Module};
+
+use crate::error::WrapperError;
+
+pub use self::instance::WasmtimeInstance;
+use self::{
+    export_globals::export_globals,
+    imports::{create_linker, StoreData},
+};
+
+use super::{
+    engine::{EngineInstance, EngineModule, WasmEngine},
+    instance::State,
+};
+
+/// Version of the transformations applied to modules before compiling them.
+/// Must be bumped whenever they change, so modules serialized by
+/// previous versions stop being deserialized.
+const TRANSFORMS_VERSION: u32 = 1;
+
+/// Engine running wraps on wasmtime, with fuel metering and epoch interruption enabled.
+///
+/// Modules get all their globals exported before being compiled, so their
+/// instances can be pooled.
+#[derive(Clone)]
+pub struct WasmtimeEngine {
+    engine: Engine,
+    linker: Arc<Linker<StoreData>>,
+}
+
+impl WasmtimeEngine {
+    pub fn new() -> Result<Self, WrapperError> {
+        let mut config = Config::new();
+        config.consume_fuel(true).epoch_interruption(true);
+
+        let engine =
+            Engine::new(&config).map_err(|e| WrapperError::WasmRuntimeError(e.to_string()))?;
+        let linker = Arc::new(create_linker(&engine)?);
+
+        Ok(Self { engine, linker })
+    }
+}
+
+impl WasmEngine for WasmtimeEngine {
+    fn key(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        self.engine
+            .precompile_compatibility_hash()
+            .hash(&mut hasher);
+
+        format!("wasmtime-{:x}:{}", hasher.finish(), TRANSFORMS_VERSION)
+    }
+
+    fn compile(&self, bytes: &[u8]) -> Result<Arc<dyn EngineModule>, WrapperError> {
+        let bytes = export_globals(bytes)?;
+        let module = Module::new(&self.engine, bytes)
+            .map_err(|e| WrapperError::ModuleReadError(e.to_string()))?;
+
+        Ok(Arc::new(WasmtimeModule {
+            module,
+            engine: self.clone(),
+        }))
+    }
+
+    unsafe fn deserialize(&self, bytes: &[u8]) -> Result<Arc<dyn EngineModule>, WrapperError> {
+        let module = Module::deserialize(&self.engine, bytes)
+            .map_err(|e| WrapperError::ModuleReadError(e.to_string()))?;
+
+        Ok(Arc::new(WasmtimeModule {
+            module,
+            engine: self.clone(),
+        }))
+    }
+}
+
+/// Module compiled by a `WasmtimeEngine`.
+pub struct WasmtimeModule {
+    module: Module,
+    engine: WasmtimeEngine,
+}
+
+impl EngineModule for WasmtimeModule {
+    fn instantiate(
+        &self,
+        memory_initial_limits: u8,
+        state: Arc<Mutex<State>>,
+        resource_limits: &ResourceLimits,
+    ) -> Result<Box<dyn EngineInstance>, WrapperError> {
+        let instance = WasmtimeInstance::new(
+            &self.engine.engine,
+            &self.engine.linker,
+            &self.module,
+            memory_initial_limits,
+            state,
+            resource_limits,
+        )?;
+
+        Ok(Box::new(instance))
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, WrapperError> {
+        self.module
+            .serialize()
+            .map_err(|e| WrapperError::WasmRuntimeError(e.to_string()))
+    }
+}
@@ -1,10 +1,14 @@
 use std::sync::{Arc, Mutex};
 
-use wasmer::{Module, Store};
+use polywrap_core::resource_limits::ResourceLimits;
 
 use crate::{
     error::WrapperError,
-    runtime::instance::{State, WasmInstance},
+    runtime::{
+        engine::{EngineInstance, EngineModule, WasmEngine},
+        instance::{State, WasmInstance},
+        wasmer_engine::WasmerEngine,
+    },
 };
 
 #[derive(Clone)]
@@ -17,9 +21,22 @@ pub enum WasmModule {
 impl WasmModule {
     // Compile the Wasm module regardless of its current state.
     pub fn compile(self) -> Result<CompiledWasmModule, WrapperError> {
+        self.compile_with_engine(&WasmerEngine::new())
+    }
+
+    // Compile the Wasm module with the given engine regardless of its current state.
+    // Serialized modules must have been serialized by the same engine.
+    pub fn compile_with_engine(
+        self,
+        engine: &dyn WasmEngine,
+    ) -> Result<CompiledWasmModule, WrapperError> {
         Ok(match self {
-            WasmModule::WasmBytecode(bytes) => CompiledWasmModule::try_from_bytecode(&bytes)?,
-            WasmModule::Serialized(serialized_module) => serialized_module.deserialize()?,
+            WasmModule::WasmBytecode(bytes) => {
+                CompiledWasmModule::try_from_bytecode_with_engine(&bytes, engine)?
+            }
+            WasmModule::Serialized(serialized_module) => {
+                serialized_module.deserialize_with_engine(engine)?
+            }
             WasmModule::Compiled(compiled_module) => compiled_module,
         })
     }
@@ -34,13 +51,19 @@ pub struct SerializedWasmModule {
 impl SerializedWasmModule {
     // Deserialize the module back into a CompiledWasmModule.
     pub fn deserialize(self) -> Result<CompiledWasmModule, WrapperError> {
-        let store = Store::default();
-        let wasmer_module = unsafe { Module::deserialize(&store, &*self.compiled_bytes)? };
+        self.deserialize_with_engine(&WasmerEngine::new())
+    }
+
+    // Deserialize the module back into a CompiledWasmModule with the engine that serialized it.
+    pub fn deserialize_with_engine(
+        self,
+        engine: &dyn WasmEngine,
+    ) -> Result<CompiledWasmModule, WrapperError> {
+        let module = unsafe { engine.deserialize(&self.compiled_bytes)? };
 
         Ok(CompiledWasmModule {
-            module: wasmer_module,
+            module,
             memory_initial_limits: self.memory_initial_limits,
-            store: Arc::new(store),
         })
     }
 
@@ -63,38 +86,45 @@ impl SerializedWasmModule {
 
 #[derive(Clone)]
 pub struct CompiledWasmModule {
-    pub module: Module,
+    pub module: Arc<dyn EngineModule>,
     pub memory_initial_limits: u8,
-    pub store: Arc<Store>,
 }
 
 impl CompiledWasmModule {
-    // Creates a WasmInstance from the module.
-    pub fn create_instance(&self, state: Arc<Mutex<State>>) -> Result<WasmInstance, WrapperError> {
-        let instance = WasmInstance::new(&self.module, self.memory_initial_limits, state)?;
-        Ok(instance)
+    // Creates a WasmInstance from the module, bounded by the given resource limits.
+    pub fn create_instance(
+        &self,
+        state: Arc<Mutex<State>>,
+        resource_limits: &ResourceLimits,
+    ) -> Result<Box<dyn EngineInstance>, WrapperError> {
+        self.module
+            .instantiate(self.memory_initial_limits, state, resource_limits)
     }
 
     // Serialize the module into a SerializedWasmModule.
     pub fn serialize(&self) -> Result<SerializedWasmModule, WrapperError> {
         let compiled_bytes = self.module.serialize()?;
         Ok(SerializedWasmModule {
-            compiled_bytes: (Vec::<u8>::from(compiled_bytes)).into(),
+            compiled_bytes: compiled_bytes.into(),
             memory_initial_limits: self.memory_initial_limits,
         })
     }
 
-    // Compiles a new CompiledWasmModule from given bytecode.
+    // Compiles a new CompiledWasmModule from given bytecode with the default engine, wasmer.
     pub fn try_from_bytecode(bytes: &[u8]) -> Result<Self, WrapperError> {
-        let store = Store::default();
-        let wasmer_module = Module::new(&store, bytes)?;
+        Self::try_from_bytecode_with_engine(bytes, &WasmerEngine::new())
+    }
 
+    // Compiles a new CompiledWasmModule from given bytecode with the given engine.
+    pub fn try_from_bytecode_with_engine(
+        bytes: &[u8],
+        engine: &dyn WasmEngine,
+    ) -> Result<Self, WrapperError> {
         let memory_initial_limits = WasmInstance::get_memory_initial_limits(bytes)?;
 
         Ok(CompiledWasmModule {
-            module: wasmer_module,
+            module: engine.compile(bytes)?,
             memory_initial_limits,
-            store: Arc::new(store),
         })
     }
 }
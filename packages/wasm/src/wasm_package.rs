@@ -15,6 +15,8 @@ use wrap_manifest_schemas::{
 
 use crate::{
     error::WrapperError,
+    module_cache::{default_module_cache, ModuleCache},
+    runtime::{engine::WasmEngine, wasmer_engine::WasmerEngine},
     wasm_module::{CompiledWasmModule, WasmModule},
     wasm_wrapper::WasmWrapper,
 };
@@ -25,6 +27,9 @@ pub struct WasmPackage {
     file_reader: Arc<dyn FileReader>,
     manifest: Option<Vec<u8>>,
     wasm_module: Arc<Mutex<Option<WasmModule>>>,
+    instance_pool_size: Option<usize>,
+    module_cache: Option<Arc<dyn ModuleCache>>,
+    engine: Arc<dyn WasmEngine>,
 }
 
 impl WasmPackage {
@@ -43,6 +48,9 @@ impl WasmPackage {
             wasm_module: Arc::new(Mutex::new(Some(WasmModule::WasmBytecode(
                 wasm_bytes.into(),
             )))),
+            instance_pool_size: None,
+            module_cache: default_module_cache(),
+            engine: Arc::new(WasmerEngine::new()),
         }
     }
 
@@ -51,6 +59,9 @@ impl WasmPackage {
             file_reader,
             manifest,
             wasm_module: Arc::new(Mutex::new(None)),
+            instance_pool_size: None,
+            module_cache: default_module_cache(),
+            engine: Arc::new(WasmerEngine::new()),
         }
     }
 
@@ -64,6 +75,9 @@ impl WasmPackage {
             file_reader: Arc::new(InMemoryFileReader::new(file_reader, None, Some(wasm_bytes))),
             manifest,
             wasm_module: Arc::new(Mutex::new(Some(WasmModule::Compiled(wasm_module)))),
+            instance_pool_size: None,
+            module_cache: default_module_cache(),
+            engine: Arc::new(WasmerEngine::new()),
         }
     }
 
@@ -77,14 +91,58 @@ impl WasmPackage {
             file_reader: Arc::new(InMemoryFileReader::new(file_reader, None, Some(wasm_bytes))),
             manifest,
             wasm_module: Arc::new(Mutex::new(Some(wasm_module))),
+            instance_pool_size: None,
+            module_cache: default_module_cache(),
+            engine: Arc::new(WasmerEngine::new()),
         }
     }
 
+    /// Makes the wrappers created by this package reuse instances across invocations,
+    /// keeping up to `max_size` idle ones. See `WasmWrapper::with_instance_pool`.
+    pub fn with_instance_pool(mut self, max_size: usize) -> Self {
+        self.instance_pool_size = Some(max_size);
+        self
+    }
+
+    /// Makes this package look up the module compiled from its bytecode in `cache`,
+    /// and store it there once compiled, instead of using the default module cache.
+    pub fn with_module_cache(mut self, cache: Option<Arc<dyn ModuleCache>>) -> Self {
+        self.module_cache = cache;
+        self
+    }
+
+    /// Makes this package compile its bytecode with `engine` instead of wasmer.
+    /// Compiled and serialized modules the package was created from must come from the same engine.
+    pub fn with_engine(mut self, engine: Arc<dyn WasmEngine>) -> Self {
+        self.engine = engine;
+        self
+    }
+
     pub fn get_wasm_module(&self) -> Result<Vec<u8>, polywrap_core::error::Error> {
         let file_content = self.file_reader.read_file("wrap.wasm")?;
 
         Ok(file_content)
     }
+
+    // Compiles the bytecode, unless the module cache already holds the compiled module.
+    fn compile_with_cache(&self, wasm_bytes: &[u8]) -> Result<CompiledWasmModule, WrapperError> {
+        let engine = self.engine.as_ref();
+
+        let Some(cache) = &self.module_cache else {
+            return CompiledWasmModule::try_from_bytecode_with_engine(wasm_bytes, engine);
+        };
+
+        if let Some(compiled_module) = cache.get(engine, wasm_bytes) {
+            return Ok(compiled_module);
+        }
+
+        let compiled_module = CompiledWasmModule::try_from_bytecode_with_engine(wasm_bytes, engine)?;
+        if let Err(error) = cache.set(engine, wasm_bytes, &compiled_module) {
+            tracing::warn!(%error, "failed to cache compiled module");
+        }
+
+        Ok(compiled_module)
+    }
 }
 
 impl PartialEq for WasmPackage {
@@ -132,17 +190,19 @@ impl WrapPackage for WasmPackage {
 
         let mut wasm_module = self.wasm_module.lock().unwrap();
 
-        let compiled_module = if wasm_module.is_some() {
-            wasm_module.clone().unwrap().compile()?
-        } else {
-            CompiledWasmModule::try_from_bytecode(&wasm_bytes)?
+        let compiled_module = match wasm_module.clone() {
+            Some(WasmModule::WasmBytecode(bytes)) => self.compile_with_cache(&bytes)?,
+            Some(module) => module.compile_with_engine(self.engine.as_ref())?,
+            None => self.compile_with_cache(&wasm_bytes)?,
         };
 
         *wasm_module = Some(WasmModule::Compiled(compiled_module.clone()));
 
-        return Ok(Arc::new(WasmWrapper::new(
-            compiled_module,
-            self.file_reader.clone(),
-        )));
+        let wrapper = WasmWrapper::new(compiled_module, self.file_reader.clone());
+
+        return Ok(Arc::new(match self.instance_pool_size {
+            Some(max_size) => wrapper.with_instance_pool(max_size),
+            None => wrapper,
+        }));
     }
 }
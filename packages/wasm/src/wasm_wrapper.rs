@@ -1,10 +1,15 @@
 use crate::error::WrapperError;
+use crate::runtime::engine::{EngineInstance, WasmEngine};
 use crate::runtime::instance::State;
+use crate::runtime::interrupt::{InterruptGuard, InterruptReason};
+use crate::runtime::pool::{InstancePool, PooledInstance};
 use crate::wasm_module::CompiledWasmModule;
 
 use polywrap_core::error::Error;
 use polywrap_core::file_reader::FileReader;
 use polywrap_core::invoker::Invoker;
+use polywrap_core::resource_limits::ResourceLimits;
+use polywrap_core::telemetry::record_result;
 use polywrap_core::wrapper::Encoding;
 use polywrap_core::wrapper::GetFileOptions;
 use polywrap_core::wrapper::Wrapper;
@@ -13,12 +18,12 @@ use serde::de::DeserializeOwned;
 use std::fmt::Formatter;
 use std::sync::Mutex;
 use std::{fmt::Debug, sync::Arc};
-use wasmer::Value;
 
 #[derive(Clone)]
 pub struct WasmWrapper {
     wasm_module: CompiledWasmModule,
     file_reader: Arc<dyn FileReader>,
+    instance_pool: Option<Arc<InstancePool>>,
 }
 
 impl WasmWrapper {
@@ -26,19 +31,38 @@ impl WasmWrapper {
         Self {
             wasm_module,
             file_reader,
+            instance_pool: None,
         }
     }
 
+    /// Reuses instances of the module across invocations, keeping up to `max_size` idle ones.
+    /// Clones of the wrapper share the pool.
+    pub fn with_instance_pool(mut self, max_size: usize) -> Self {
+        self.instance_pool = Some(Arc::new(InstancePool::new(max_size)));
+        self
+    }
+
+    pub fn instance_pool(&self) -> Option<&InstancePool> {
+        self.instance_pool.as_deref()
+    }
+
     pub fn try_from_bytecode(
         bytes: &[u8],
         file_reader: Arc<dyn FileReader>,
     ) -> Result<Self, WrapperError> {
         let wasm_module = CompiledWasmModule::try_from_bytecode(bytes)?;
 
-        Ok(Self {
-            wasm_module,
-            file_reader,
-        })
+        Ok(Self::new(wasm_module, file_reader))
+    }
+
+    pub fn try_from_bytecode_with_engine(
+        bytes: &[u8],
+        engine: &dyn WasmEngine,
+        file_reader: Arc<dyn FileReader>,
+    ) -> Result<Self, WrapperError> {
+        let wasm_module = CompiledWasmModule::try_from_bytecode_with_engine(bytes, engine)?;
+
+        Ok(Self::new(wasm_module, file_reader))
     }
 
     pub fn invoke_and_decode<T: DeserializeOwned>(
@@ -54,16 +78,8 @@ impl WasmWrapper {
 
         Ok(result)
     }
-}
-
-impl Debug for WasmWrapper {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, r#"WasmModule(...)"#)
-    }
-}
 
-impl Wrapper for WasmWrapper {
-    fn invoke(
+    fn invoke_module(
         &self,
         method: &str,
         args: Option<&[u8]>,
@@ -80,26 +96,110 @@ impl Wrapper for WasmWrapper {
             None => vec![],
         };
 
-        let params = &[
-            Value::I32(method.len() as _),
-            Value::I32(args.len() as _),
-            Value::I32(env.len() as _),
-        ];
+        let params = &[method.len() as i32, args.len() as i32, env.len() as i32];
+
+        let options = invoker.get_invoke_options();
+        if let Some(options) = &options {
+            options.check(method)?;
+        }
+
+        let resource_limits = invoker.get_resource_limits().unwrap_or_default();
+
+        let state = State::new(invoker, method, args, env);
+
+        let mut pooled = match &self.instance_pool {
+            Some(pool) => pool.acquire(&self.wasm_module, state, &resource_limits)?,
+            None => PooledInstance::from(
+                self.wasm_module
+                    .create_instance(Arc::new(Mutex::new(state)), &resource_limits)?,
+            ),
+        };
+        let wasm_instance = pooled.instance.as_mut();
+
+        // Interrupts the execution once the invocation times out or is cancelled
+        let interrupt = options.as_ref().and_then(|options| {
+            let handle = wasm_instance.interrupt_handle()?;
+            Some(InterruptGuard::new(handle, options))
+        });
+
+        let result = wasm_instance.call_export("_wrap_invoke", params);
+
+        if let Some(fuel_consumed) = wasm_instance.fuel_consumed() {
+            tracing::Span::current().record("fuel_consumed", fuel_consumed);
 
-        let state = Arc::new(Mutex::new(State::new(
-            invoker,
+            let resource_usage = options.as_ref().and_then(|o| o.resource_usage.as_ref());
+            if let Some(resource_usage) = resource_usage {
+                resource_usage.add_fuel_consumed(fuel_consumed);
+            }
+        }
+
+        let interrupt_reason = interrupt.and_then(|interrupt| interrupt.reason());
+        // Instances that trapped are left in an unknown state
+        let reusable = result.is_ok();
+
+        let output = Self::read_output(
             method,
-            args,
-            env,
-        )));
+            result,
+            wasm_instance,
+            interrupt_reason,
+            &resource_limits,
+        );
+
+        if let (Some(pool), true) = (&self.instance_pool, reusable) {
+            pool.release(pooled);
+        }
 
-        let mut wasm_instance = self.wasm_module.create_instance(state.clone())?;
+        output
+    }
+
+    // Reads the output of an invocation from the state of the instance that ran it.
+    fn read_output(
+        method: &str,
+        result: Result<bool, WrapperError>,
+        wasm_instance: &mut dyn EngineInstance,
+        interrupt_reason: Option<InterruptReason>,
+        resource_limits: &ResourceLimits,
+    ) -> Result<Vec<u8>, Error> {
+        if result.is_err() {
+            match interrupt_reason {
+                Some(InterruptReason::Timeout) => {
+                    return Err(Error::TimeoutError(method.to_string()))
+                }
+                Some(InterruptReason::Cancelled) => {
+                    return Err(Error::CancelledError(method.to_string()))
+                }
+                None => {}
+            }
+
+            if wasm_instance.fuel_exhausted() {
+                return Err(Error::ResourceLimitError(format!(
+                    "{method} ran out of fuel, budget: {}",
+                    resource_limits.fuel.unwrap_or(u64::MAX)
+                )));
+            }
+        }
+
+        let state = wasm_instance.state();
+        let mut state = state.lock().unwrap();
 
-        let result = wasm_instance
-            .call_export("_wrap_invoke", params)?;
+        // A wrap denied more memory usually fails with an unrelated error, if at all
+        let failed = state.abort.is_some() || !matches!(result, Ok(true));
+        if failed && wasm_instance.memory_growth_denied() {
+            return Err(Error::ResourceLimitError(format!(
+                "{method} exceeded its memory limit of {} pages",
+                resource_limits.max_memory_pages.unwrap_or_default()
+            )));
+        }
 
-        let state = state.lock().unwrap();
-        if result {
+        // A wrap failing after a subinvocation failed usually propagates that error,
+        // in which case it is kept as the cause of the failure
+        let source = state.subinvoke_failure.take().map(Box::new);
+
+        if let Some(info) = state.abort.take() {
+            return Err(Error::WrapAbort { info, source });
+        }
+
+        if result? {
             if state.invoke.result.is_none() {
                 return Err(Error::RuntimeError("Invoke result is missing".to_string()));
             }
@@ -108,10 +208,45 @@ impl Wrapper for WasmWrapper {
         } else if state.invoke.error.is_none() {
             Err(Error::RuntimeError("Invoke error is missing".to_string()))
         } else {
-            Err(Error::WrapperError(
-                state.invoke.error.as_ref().unwrap().to_string(),
-            ))
+            Err(Error::WrapperError {
+                message: state.invoke.error.take().unwrap(),
+                source,
+            })
+        }
+    }
+}
+
+impl Debug for WasmWrapper {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, r#"WasmModule(...)"#)
+    }
+}
+
+impl Wrapper for WasmWrapper {
+    fn invoke(
+        &self,
+        method: &str,
+        args: Option<&[u8]>,
+        env: Option<&[u8]>,
+        invoker: Arc<dyn Invoker>,
+    ) -> Result<Vec<u8>, Error> {
+        let span = tracing::info_span!(
+            "wasm_invoke",
+            uri = tracing::field::Empty,
+            method,
+            fuel_consumed = tracing::field::Empty,
+            otel.status_code = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+        if let Some(uri) = invoker.get_wrap_uri() {
+            span.record("uri", tracing::field::display(uri));
         }
+        let _enter = span.enter();
+
+        let result = self.invoke_module(method, args, env, invoker);
+
+        record_result(&span, &result);
+        result
     }
 
     fn get_file(&self, options: &GetFileOptions) -> Result<Vec<u8>, Error> {
@@ -130,7 +265,7 @@ impl Wrapper for WasmWrapper {
 
             Ok(result)
         } else {
-            Err(Error::WrapperError(format!(
+            Err(Error::wrapper(format!(
                 "WasmWrapper: File was not found.\nSubpath: {}",
                 options.path
             )))
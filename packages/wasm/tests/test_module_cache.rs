@@ -0,0 +1,174 @@
+use std::{fs, path::PathBuf, sync::Arc};
+
+use polywrap_core::{file_reader::SimpleFileReader, package::WrapPackage};
+use polywrap_wasm::{
+    module_cache::{FileSystemModuleCache, ModuleCache},
+    runtime::wasmer_engine::WasmerEngine,
+    wasm_module::CompiledWasmModule,
+    wasm_package::WasmPackage,
+};
+
+const MODULE: &str = r#"
+    (module
+      (import "env" "memory" (memory 1))
+      (func (export "_wrap_invoke") (param i32 i32 i32) (result i32)
+        (i32.const 1)))
+    "#;
+
+const OTHER_MODULE: &str = r#"
+    (module
+      (import "env" "memory" (memory 1))
+      (func (export "_wrap_invoke") (param i32 i32 i32) (result i32)
+        (i32.const 0)))
+    "#;
+
+fn cache_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!(
+        "polywrap-module-cache-{name}-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&directory);
+    directory
+}
+
+fn entries(directory: &PathBuf) -> Vec<PathBuf> {
+    fs::read_dir(directory)
+        .unwrap()
+        .flatten()
+        .flat_map(|engine| fs::read_dir(engine.path()).unwrap().flatten())
+        .map(|entry| entry.path())
+        .collect()
+}
+
+#[test]
+fn get_cached_module() {
+    let directory = cache_directory("get");
+    let cache = FileSystemModuleCache::new(&directory, u64::MAX);
+    let wasm_bytes = wat::parse_str(MODULE).unwrap();
+
+    assert!(cache.get(&WasmerEngine::new(), &wasm_bytes).is_none());
+
+    let module = CompiledWasmModule::try_from_bytecode(&wasm_bytes).unwrap();
+    cache
+        .set(&WasmerEngine::new(), &wasm_bytes, &module)
+        .unwrap();
+
+    let cached_module = cache.get(&WasmerEngine::new(), &wasm_bytes).unwrap();
+    assert_eq!(
+        cached_module.memory_initial_limits,
+        module.memory_initial_limits
+    );
+    assert!(cache
+        .get(&WasmerEngine::new(), &wat::parse_str(OTHER_MODULE).unwrap())
+        .is_none());
+
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn keep_unmetered_modules_apart() {
+    let directory = cache_directory("unmetered");
+    let cache = FileSystemModuleCache::new(&directory, u64::MAX);
+    let wasm_bytes = wat::parse_str(MODULE).unwrap();
+
+    let module = CompiledWasmModule::try_from_bytecode(&wasm_bytes).unwrap();
+    cache
+        .set(&WasmerEngine::new(), &wasm_bytes, &module)
+        .unwrap();
+
+    assert!(cache.get(&WasmerEngine::unmetered(), &wasm_bytes).is_none());
+
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn discard_corrupted_entry() {
+    let directory = cache_directory("corrupted");
+    let cache = FileSystemModuleCache::new(&directory, u64::MAX);
+    let wasm_bytes = wat::parse_str(MODULE).unwrap();
+
+    let module = CompiledWasmModule::try_from_bytecode(&wasm_bytes).unwrap();
+    cache
+        .set(&WasmerEngine::new(), &wasm_bytes, &module)
+        .unwrap();
+
+    let entry = entries(&directory).pop().unwrap();
+    let mut bytes = fs::read(&entry).unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xff;
+    fs::write(&entry, bytes).unwrap();
+
+    assert!(cache.get(&WasmerEngine::new(), &wasm_bytes).is_none());
+    assert!(!entry.exists());
+
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn evict_entries_over_max_size() {
+    let directory = cache_directory("evict");
+    let wasm_bytes = wat::parse_str(MODULE).unwrap();
+    let other_wasm_bytes = wat::parse_str(OTHER_MODULE).unwrap();
+
+    let module = CompiledWasmModule::try_from_bytecode(&wasm_bytes).unwrap();
+    FileSystemModuleCache::new(&directory, u64::MAX)
+        .set(&WasmerEngine::new(), &wasm_bytes, &module)
+        .unwrap();
+    let entry_size = FileSystemModuleCache::new(&directory, u64::MAX).size();
+
+    // Leaves room for a single entry
+    let cache = FileSystemModuleCache::new(&directory, entry_size + entry_size / 2);
+    let other_module = CompiledWasmModule::try_from_bytecode(&other_wasm_bytes).unwrap();
+    cache
+        .set(&WasmerEngine::new(), &other_wasm_bytes, &other_module)
+        .unwrap();
+
+    assert_eq!(entries(&directory).len(), 1);
+    assert!(cache.get(&WasmerEngine::new(), &other_wasm_bytes).is_some());
+
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn evict_entries_of_other_engines_first() {
+    let directory = cache_directory("engines");
+    let cache = FileSystemModuleCache::new(&directory, u64::MAX);
+    let wasm_bytes = wat::parse_str(MODULE).unwrap();
+
+    let module = CompiledWasmModule::try_from_bytecode(&wasm_bytes).unwrap();
+    cache
+        .set(&WasmerEngine::new(), &wasm_bytes, &module)
+        .unwrap();
+
+    let stale_directory = directory.join("stale-engine");
+    fs::create_dir_all(&stale_directory).unwrap();
+    fs::write(stale_directory.join("entry"), vec![0; 64]).unwrap();
+
+    let cache = FileSystemModuleCache::new(&directory, cache.size() - 1);
+    cache
+        .set(&WasmerEngine::new(), &wasm_bytes, &module)
+        .unwrap();
+
+    assert!(!stale_directory.exists());
+    assert!(cache.get(&WasmerEngine::new(), &wasm_bytes).is_some());
+
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn create_wrapper_with_module_cache() {
+    let directory = cache_directory("package");
+    let cache: Arc<dyn ModuleCache> = Arc::new(FileSystemModuleCache::new(&directory, u64::MAX));
+    let wasm_bytes = wat::parse_str(MODULE).unwrap();
+
+    let package =
+        WasmPackage::from_bytecode(wasm_bytes.clone(), Arc::new(SimpleFileReader::new()), None)
+            .with_module_cache(Some(cache.clone()));
+
+    package.create_wrapper().unwrap();
+
+    assert_eq!(entries(&directory).len(), 1);
+    assert!(cache.get(&WasmerEngine::new(), &wasm_bytes).is_some());
+
+    fs::remove_dir_all(directory).unwrap();
+}
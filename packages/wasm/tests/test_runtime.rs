@@ -1,13 +1,24 @@
 use polywrap_core::macros::uri;
 use polywrap_core::{
-    error::Error, file_reader::SimpleFileReader,
-    interface_implementation::InterfaceImplementations, invoker::Invoker,
-    resolution::uri_resolution_context::UriResolutionContext, uri::Uri, wrapper::Wrapper,
+    error::Error,
+    file_reader::SimpleFileReader,
+    interface_implementation::InterfaceImplementations,
+    invoke_options::{CancellationToken, InvokeOptions},
+    invoker::Invoker,
+    resolution::uri_resolution_context::UriResolutionContext,
+    resource_limits::ResourceLimits,
+    uri::Uri,
+    wasi_policy::{PreopenedDir, WasiPolicy},
+    wrap_log::{WrapLogEvent, WrapLogRecord, WrapLogSink},
+    wrapper::Wrapper,
 };
+#[cfg(feature = "wasmtime")]
+use polywrap_wasm::runtime::wasmtime_engine::WasmtimeEngine;
+use polywrap_wasm::runtime::{engine::WasmEngine, wasmer_engine::WasmerEngine};
 use polywrap_wasm::wasm_module::CompiledWasmModule;
 use polywrap_wasm::wasm_wrapper::WasmWrapper;
 use serde::Serialize;
-use std::{collections::HashMap, path::Path, sync::Mutex};
+use std::{collections::HashMap, path::Path, sync::Mutex, thread, time::Duration};
 use wrap_manifest_schemas::deserialize::deserialize_wrap_manifest;
 
 use polywrap_msgpack_serde::to_vec;
@@ -15,14 +26,53 @@ use polywrap_tests_utils::helpers::get_tests_path;
 use std::fs;
 use std::sync::Arc;
 
+// Engines the tests run on
+fn engines() -> Vec<Arc<dyn WasmEngine>> {
+    vec![
+        Arc::new(WasmerEngine::new()),
+        #[cfg(feature = "wasmtime")]
+        Arc::new(WasmtimeEngine::new().unwrap()),
+    ]
+}
+
 #[derive(Clone)]
 struct MockInvoker {
     wrapper: WasmWrapper,
+    options: Option<InvokeOptions>,
+    resource_limits: Option<ResourceLimits>,
+    wasi_policy: Option<WasiPolicy>,
+    log_sink: Option<Arc<dyn WrapLogSink>>,
 }
 
 impl MockInvoker {
     fn new(wrapper: WasmWrapper) -> Self {
-        Self { wrapper }
+        Self {
+            wrapper,
+            options: None,
+            resource_limits: None,
+            wasi_policy: None,
+            log_sink: None,
+        }
+    }
+
+    fn with_options(mut self, options: InvokeOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    fn with_resource_limits(mut self, resource_limits: ResourceLimits) -> Self {
+        self.resource_limits = Some(resource_limits);
+        self
+    }
+
+    fn with_wasi_policy(mut self, wasi_policy: WasiPolicy) -> Self {
+        self.wasi_policy = Some(wasi_policy);
+        self
+    }
+
+    fn with_log_sink(mut self, log_sink: Arc<dyn WrapLogSink>) -> Self {
+        self.log_sink = Some(log_sink);
+        self
     }
 
     fn invoke_wrapper_raw(
@@ -69,6 +119,37 @@ impl Invoker for MockInvoker {
     fn get_env_by_uri(&self, _: &Uri) -> Option<Vec<u8>> {
         None
     }
+
+    fn get_invoke_options(&self) -> Option<InvokeOptions> {
+        self.options.clone()
+    }
+
+    fn get_resource_limits(&self) -> Option<ResourceLimits> {
+        self.resource_limits
+    }
+
+    fn get_wasi_policy(&self) -> Option<WasiPolicy> {
+        self.wasi_policy.clone()
+    }
+
+    fn get_wrap_uri(&self) -> Option<Uri> {
+        Some(uri!("mock/wrap"))
+    }
+
+    fn get_log_sink(&self) -> Option<Arc<dyn WrapLogSink>> {
+        self.log_sink.clone()
+    }
+}
+
+#[derive(Debug, Default)]
+struct MockLogSink {
+    records: Mutex<Vec<WrapLogRecord>>,
+}
+
+impl WrapLogSink for MockLogSink {
+    fn log(&self, record: &WrapLogRecord) {
+        self.records.lock().unwrap().push(record.clone());
+    }
 }
 
 #[derive(Serialize)]
@@ -89,21 +170,27 @@ fn invoke_from_bytecode() {
     let manifest_bytes = fs::read(Path::new(&manifest_path)).unwrap();
 
     let _manifest = deserialize_wrap_manifest(&manifest_bytes, None).unwrap();
-    let file_reader = SimpleFileReader::new();
 
-    let wrapper = WasmWrapper::try_from_bytecode(&module_bytes, Arc::new(file_reader)).unwrap();
-
-    let mock_invoker = MockInvoker::new(wrapper);
-    let result = Arc::new(mock_invoker)
-        .invoke_raw(
-            &uri!("mock/wrap"),
-            "add",
-            Some(&to_vec(&AddArgs { a: 1, b: 1 }).unwrap()),
-            None,
-            None,
+    for engine in engines() {
+        let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+            &module_bytes,
+            engine.as_ref(),
+            Arc::new(SimpleFileReader::new()),
         )
         .unwrap();
-    assert_eq!(result, [2])
+
+        let mock_invoker = MockInvoker::new(wrapper);
+        let result = Arc::new(mock_invoker)
+            .invoke_raw(
+                &uri!("mock/wrap"),
+                "add",
+                Some(&to_vec(&AddArgs { a: 1, b: 1 }).unwrap()),
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(result, [2])
+    }
 }
 
 #[test]
@@ -118,22 +205,25 @@ fn invoke_from_compiled_module() {
     let manifest_bytes = fs::read(Path::new(&manifest_path)).unwrap();
 
     let _manifest = deserialize_wrap_manifest(&manifest_bytes, None).unwrap();
-    let file_reader = SimpleFileReader::new();
 
-    let compiled_module = CompiledWasmModule::try_from_bytecode(&module_bytes).unwrap();
-    let wrapper = WasmWrapper::new(compiled_module, Arc::new(file_reader));
+    for engine in engines() {
+        let compiled_module =
+            CompiledWasmModule::try_from_bytecode_with_engine(&module_bytes, engine.as_ref())
+                .unwrap();
+        let wrapper = WasmWrapper::new(compiled_module, Arc::new(SimpleFileReader::new()));
 
-    let mock_invoker = MockInvoker::new(wrapper);
-    let result = Arc::new(mock_invoker)
-        .invoke_raw(
-            &uri!("mock/wrap"),
-            "add",
-            Some(&to_vec(&AddArgs { a: 1, b: 1 }).unwrap()),
-            None,
-            None,
-        )
-        .unwrap();
-    assert_eq!(result, [2])
+        let mock_invoker = MockInvoker::new(wrapper);
+        let result = Arc::new(mock_invoker)
+            .invoke_raw(
+                &uri!("mock/wrap"),
+                "add",
+                Some(&to_vec(&AddArgs { a: 1, b: 1 }).unwrap()),
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(result, [2])
+    }
 }
 
 #[test]
@@ -148,25 +238,603 @@ fn invoke_from_deserialized_module() {
     let manifest_bytes = fs::read(Path::new(&manifest_path)).unwrap();
 
     let _manifest = deserialize_wrap_manifest(&manifest_bytes, None).unwrap();
-    let file_reader = SimpleFileReader::new();
 
-    let compiled_module = CompiledWasmModule::try_from_bytecode(&module_bytes).unwrap();
+    for engine in engines() {
+        let compiled_module =
+            CompiledWasmModule::try_from_bytecode_with_engine(&module_bytes, engine.as_ref())
+                .unwrap();
+
+        let result = compiled_module.serialize().unwrap();
+
+        let module = result.deserialize_with_engine(engine.as_ref()).unwrap();
+
+        let wrapper = WasmWrapper::new(module, Arc::new(SimpleFileReader::new()));
+
+        let mock_invoker = MockInvoker::new(wrapper);
+        let result = Arc::new(mock_invoker)
+            .invoke_raw(
+                &uri!("mock/wrap"),
+                "add",
+                Some(&to_vec(&AddArgs { a: 1, b: 1 }).unwrap()),
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(result, [2])
+    }
+}
+
+#[test]
+fn invoke_with_abort() {
+    let module_bytes = wat::parse_str(
+        r#"
+        (module
+          (import "env" "memory" (memory 1))
+          (import "wrap" "__wrap_abort" (func $abort (param i32 i32 i32 i32 i32 i32)))
+          (data (i32.const 0) "boom")
+          (data (i32.const 16) "src/lib.ts")
+          (func (export "_wrap_invoke") (param i32 i32 i32) (result i32)
+            (call $abort (i32.const 0) (i32.const 4) (i32.const 16) (i32.const 10) (i32.const 3) (i32.const 7))
+            (i32.const 0)))
+        "#,
+    )
+    .unwrap();
+
+    for engine in engines() {
+        let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+            &module_bytes,
+            engine.as_ref(),
+            Arc::new(SimpleFileReader::new()),
+        )
+        .unwrap();
+        let mock_invoker = MockInvoker::new(wrapper.clone());
+
+        let error = wrapper
+            .invoke("method", None, None, Arc::new(mock_invoker))
+            .unwrap_err();
+
+        match error {
+            Error::WrapAbort { info, source } => {
+                assert_eq!(info.message, "boom");
+                assert_eq!(info.file, "src/lib.ts");
+                assert_eq!(info.line, 3);
+                assert_eq!(info.column, 7);
+                assert!(source.is_none());
+            }
+            _ => panic!("Expected WrapAbort, got: {:?}", error),
+        }
+    }
+}
+
+#[test]
+fn invoke_with_abort_after_failed_subinvoke() {
+    // Invoked with args, the wrap subinvokes itself without args and aborts
+    // with its own message once the nested invocation aborted
+    let module_bytes = wat::parse_str(
+        r#"
+        (module
+          (import "env" "memory" (memory 1))
+          (import "wrap" "__wrap_abort" (func $abort (param i32 i32 i32 i32 i32 i32)))
+          (import "wrap" "__wrap_subinvoke" (func $subinvoke (param i32 i32 i32 i32 i32 i32) (result i32)))
+          (data (i32.const 0) "inner")
+          (data (i32.const 8) "outer")
+          (data (i32.const 16) "src/lib.ts")
+          (data (i32.const 32) "mock/self")
+          (data (i32.const 48) "method")
+          (func (export "_wrap_invoke") (param i32 i32 i32) (result i32)
+            (if (i32.eqz (local.get 1))
+              (then
+                (call $abort (i32.const 0) (i32.const 5) (i32.const 16) (i32.const 10) (i32.const 1) (i32.const 1))))
+            (drop (call $subinvoke (i32.const 32) (i32.const 9) (i32.const 48) (i32.const 6) (i32.const 0) (i32.const 0)))
+            (call $abort (i32.const 8) (i32.const 5) (i32.const 16) (i32.const 10) (i32.const 2) (i32.const 1))
+            (i32.const 0)))
+        "#,
+    )
+    .unwrap();
+
+    for engine in engines() {
+        let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+            &module_bytes,
+            engine.as_ref(),
+            Arc::new(SimpleFileReader::new()),
+        )
+        .unwrap();
+        let mock_invoker = MockInvoker::new(wrapper.clone());
+
+        let error = wrapper
+            .invoke("method", Some(&[1]), None, Arc::new(mock_invoker))
+            .unwrap_err();
+
+        assert_eq!(error.abort_info().unwrap().message, "outer");
+        match error.cause() {
+            Some(Error::WrapAbort { info, source }) => {
+                assert_eq!(info.message, "inner");
+                assert!(source.is_none());
+            }
+            cause => panic!("Expected WrapAbort, got: {:?}", cause),
+        }
+    }
+}
+
+#[test]
+fn invoke_error_after_failed_subinvoke() {
+    let module_bytes = wat::parse_str(
+        r#"
+        (module
+          (import "env" "memory" (memory 1))
+          (import "wrap" "__wrap_abort" (func $abort (param i32 i32 i32 i32 i32 i32)))
+          (import "wrap" "__wrap_invoke_error" (func $invoke_error (param i32 i32)))
+          (import "wrap" "__wrap_subinvoke" (func $subinvoke (param i32 i32 i32 i32 i32 i32) (result i32)))
+          (data (i32.const 0) "inner")
+          (data (i32.const 8) "outer")
+          (data (i32.const 16) "src/lib.ts")
+          (data (i32.const 32) "mock/self")
+          (data (i32.const 48) "method")
+          (func (export "_wrap_invoke") (param i32 i32 i32) (result i32)
+            (if (i32.eqz (local.get 1))
+              (then
+                (call $abort (i32.const 0) (i32.const 5) (i32.const 16) (i32.const 10) (i32.const 1) (i32.const 1))))
+            (drop (call $subinvoke (i32.const 32) (i32.const 9) (i32.const 48) (i32.const 6) (i32.const 0) (i32.const 0)))
+            (call $invoke_error (i32.const 8) (i32.const 5))
+            (i32.const 0)))
+        "#,
+    )
+    .unwrap();
+
+    for engine in engines() {
+        let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+            &module_bytes,
+            engine.as_ref(),
+            Arc::new(SimpleFileReader::new()),
+        )
+        .unwrap();
+        let mock_invoker = MockInvoker::new(wrapper.clone());
+
+        let error = wrapper
+            .invoke("method", Some(&[1]), None, Arc::new(mock_invoker))
+            .unwrap_err();
+
+        match &error {
+            Error::WrapperError { message, .. } => assert_eq!(message, "outer"),
+            _ => panic!("Expected WrapperError, got: {:?}", error),
+        }
+        assert_eq!(error.abort_info().unwrap().message, "inner");
+    }
+}
+
+#[test]
+fn invoke_with_log_sink() {
+    let module_bytes = wat::parse_str(
+        r#"
+        (module
+          (import "env" "memory" (memory 1))
+          (import "wrap" "__wrap_debug_log" (func $debug_log (param i32 i32)))
+          (import "wrap" "__wrap_abort" (func $abort (param i32 i32 i32 i32 i32 i32)))
+          (data (i32.const 0) "boom")
+          (data (i32.const 16) "src/lib.ts")
+          (data (i32.const 32) "hello")
+          (func (export "_wrap_invoke") (param i32 i32 i32) (result i32)
+            (call $debug_log (i32.const 32) (i32.const 5))
+            (call $abort (i32.const 0) (i32.const 4) (i32.const 16) (i32.const 10) (i32.const 3) (i32.const 7))
+            (i32.const 0)))
+        "#,
+    )
+    .unwrap();
+
+    for engine in engines() {
+        let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+            &module_bytes,
+            engine.as_ref(),
+            Arc::new(SimpleFileReader::new()),
+        )
+        .unwrap();
+        let log_sink = Arc::new(MockLogSink::default());
+        let mock_invoker = MockInvoker::new(wrapper.clone()).with_log_sink(log_sink.clone());
+
+        let error = wrapper
+            .invoke("method", None, None, Arc::new(mock_invoker))
+            .unwrap_err();
+
+        let records = log_sink.records.lock().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].uri, Some(uri!("mock/wrap")));
+        assert_eq!(records[0].method, "method");
+        assert_eq!(records[0].event, WrapLogEvent::Debug("hello".to_string()));
+        assert_eq!(
+            records[1].event,
+            WrapLogEvent::Abort(error.abort_info().unwrap().clone())
+        );
+    }
+}
+
+const INFINITE_LOOP_MODULE: &str = r#"
+    (module
+      (import "env" "memory" (memory 1))
+      (func (export "_wrap_invoke") (param i32 i32 i32) (result i32)
+        (loop $forever
+          (br $forever))
+        (i32.const 1)))
+    "#;
+
+#[test]
+fn invoke_with_timeout() {
+    let module_bytes = wat::parse_str(INFINITE_LOOP_MODULE).unwrap();
+
+    for engine in engines() {
+        let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+            &module_bytes,
+            engine.as_ref(),
+            Arc::new(SimpleFileReader::new()),
+        )
+        .unwrap();
+        let mock_invoker = MockInvoker::new(wrapper.clone())
+            .with_options(InvokeOptions::with_timeout(Duration::from_millis(100)));
+
+        let error = wrapper
+            .invoke("method", None, None, Arc::new(mock_invoker))
+            .unwrap_err();
+
+        assert!(matches!(error, Error::TimeoutError(_)), "{error:?}");
+    }
+}
+
+#[test]
+fn invoke_with_cancellation() {
+    let module_bytes = wat::parse_str(INFINITE_LOOP_MODULE).unwrap();
+
+    for engine in engines() {
+        let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+            &module_bytes,
+            engine.as_ref(),
+            Arc::new(SimpleFileReader::new()),
+        )
+        .unwrap();
+        let token = CancellationToken::new();
+        let mock_invoker = MockInvoker::new(wrapper.clone())
+            .with_options(InvokeOptions::with_cancellation_token(token.clone()));
+
+        let canceller = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            token.cancel();
+        });
+
+        let error = wrapper
+            .invoke("method", None, None, Arc::new(mock_invoker))
+            .unwrap_err();
+        canceller.join().unwrap();
+
+        assert!(matches!(error, Error::CancelledError(_)), "{error:?}");
+    }
+}
+
+#[test]
+fn invoke_with_fuel_limit() {
+    let module_bytes = wat::parse_str(INFINITE_LOOP_MODULE).unwrap();
+
+    for engine in engines() {
+        let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+            &module_bytes,
+            engine.as_ref(),
+            Arc::new(SimpleFileReader::new()),
+        )
+        .unwrap();
+        let mock_invoker = MockInvoker::new(wrapper.clone()).with_resource_limits(ResourceLimits {
+            fuel: Some(10_000),
+            max_memory_pages: None,
+        });
+
+        let error = wrapper
+            .invoke("method", None, None, Arc::new(mock_invoker))
+            .unwrap_err();
 
-    let result = compiled_module.serialize().unwrap();
+        assert!(matches!(error, Error::ResourceLimitError(_)), "{error:?}");
+    }
+}
 
-    let module = result.deserialize().unwrap();
+// Loops 200 times through the else branch, running 12 operators per iteration,
+// 8 of them before the `if`
+const ELSE_BRANCH_LOOP_MODULE: &str = r#"
+    (module
+      (import "env" "memory" (memory 1))
+      (func (export "_wrap_invoke") (param i32 i32 i32) (result i32)
+        (local $i i32)
+        (loop $continue
+          (local.set $i (i32.add (local.get $i) (i32.const 1)))
+          (if (i32.lt_u (local.get $i) (i32.const 0))
+            (then nop)
+            (else
+              (br_if $continue (i32.lt_u (local.get $i) (i32.const 200))))))
+        (i32.const 1)))
+    "#;
 
-    let wrapper = WasmWrapper::new(module, Arc::new(file_reader));
+#[test]
+fn invoke_with_fuel_limit_in_else_branch() {
+    let module_bytes = wat::parse_str(ELSE_BRANCH_LOOP_MODULE).unwrap();
 
-    let mock_invoker = MockInvoker::new(wrapper);
-    let result = Arc::new(mock_invoker)
-        .invoke_raw(
-            &uri!("mock/wrap"),
-            "add",
-            Some(&to_vec(&AddArgs { a: 1, b: 1 }).unwrap()),
-            None,
-            None,
+    for engine in engines() {
+        let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+            &module_bytes,
+            engine.as_ref(),
+            Arc::new(SimpleFileReader::new()),
         )
         .unwrap();
-    assert_eq!(result, [2])
+        let mock_invoker = MockInvoker::new(wrapper.clone()).with_resource_limits(ResourceLimits {
+            fuel: Some(1_500),
+            max_memory_pages: None,
+        });
+
+        let error = wrapper
+            .invoke("method", None, None, Arc::new(mock_invoker))
+            .unwrap_err();
+
+        assert!(matches!(error, Error::ResourceLimitError(_)), "{error:?}");
+    }
+}
+
+#[test]
+fn invoke_unmetered_with_fuel_limit() {
+    let module_bytes = wat::parse_str(ELSE_BRANCH_LOOP_MODULE).unwrap();
+
+    let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+        &module_bytes,
+        &WasmerEngine::unmetered(),
+        Arc::new(SimpleFileReader::new()),
+    )
+    .unwrap();
+    let mock_invoker = MockInvoker::new(wrapper.clone()).with_resource_limits(ResourceLimits {
+        fuel: Some(1_500),
+        max_memory_pages: None,
+    });
+
+    // Runs to completion, but never sets a result
+    let error = wrapper
+        .invoke("method", None, None, Arc::new(mock_invoker))
+        .unwrap_err();
+
+    assert!(matches!(error, Error::RuntimeError(_)), "{error:?}");
+}
+
+#[test]
+fn invoke_with_memory_limit() {
+    let module_bytes = wat::parse_str(
+        r#"
+        (module
+          (import "env" "memory" (memory 1))
+          (func (export "_wrap_invoke") (param i32 i32 i32) (result i32)
+            (if (i32.eq (memory.grow (i32.const 4)) (i32.const -1))
+              (then unreachable))
+            (i32.const 0)))
+        "#,
+    )
+    .unwrap();
+
+    for engine in engines() {
+        let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+            &module_bytes,
+            engine.as_ref(),
+            Arc::new(SimpleFileReader::new()),
+        )
+        .unwrap();
+        let mock_invoker = MockInvoker::new(wrapper.clone()).with_resource_limits(ResourceLimits {
+            fuel: None,
+            max_memory_pages: Some(2),
+        });
+
+        let error = wrapper
+            .invoke("method", None, None, Arc::new(mock_invoker))
+            .unwrap_err();
+
+        assert!(matches!(error, Error::ResourceLimitError(_)), "{error:?}");
+    }
+}
+
+// Counts calls in a global and in memory, and returns the sum of both counts
+const COUNTER_MODULE: &str = r#"
+    (module
+      (import "env" "memory" (memory 1))
+      (import "wrap" "__wrap_invoke_result" (func $result (param i32 i32)))
+      (global $calls (mut i32) (i32.const 0))
+      (func (export "_wrap_invoke") (param i32 i32 i32) (result i32)
+        (global.set $calls (i32.add (global.get $calls) (i32.const 1)))
+        (i32.store8 (i32.const 0) (i32.add (i32.load8_u (i32.const 0)) (global.get $calls)))
+        (call $result (i32.const 0) (i32.const 1))
+        (i32.const 1)))
+    "#;
+
+#[test]
+fn invoke_with_instance_pool() {
+    let module_bytes = wat::parse_str(COUNTER_MODULE).unwrap();
+
+    for engine in engines() {
+        let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+            &module_bytes,
+            engine.as_ref(),
+            Arc::new(SimpleFileReader::new()),
+        )
+        .unwrap()
+        .with_instance_pool(2);
+        let mock_invoker = Arc::new(MockInvoker::new(wrapper.clone()));
+
+        for _ in 0..3 {
+            let result = wrapper
+                .invoke("method", None, None, mock_invoker.clone())
+                .unwrap();
+            assert_eq!(result, [1]);
+        }
+
+        assert_eq!(wrapper.instance_pool().unwrap().idle_count(), 1);
+    }
+}
+
+#[test]
+fn invoke_with_instance_pool_across_threads() {
+    let module_bytes = wat::parse_str(COUNTER_MODULE).unwrap();
+
+    for engine in engines() {
+        let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+            &module_bytes,
+            engine.as_ref(),
+            Arc::new(SimpleFileReader::new()),
+        )
+        .unwrap()
+        .with_instance_pool(2);
+
+        let handles = (0..8)
+            .map(|_| {
+                let wrapper = wrapper.clone();
+                thread::spawn(move || {
+                    let mock_invoker = Arc::new(MockInvoker::new(wrapper.clone()));
+                    for _ in 0..10 {
+                        let result = wrapper
+                            .invoke("method", None, None, mock_invoker.clone())
+                            .unwrap();
+                        assert_eq!(result, [1]);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert!(wrapper.instance_pool().unwrap().idle_count() <= 2);
+    }
+}
+
+// Returns the error codes of WASI calls to the clock, random, stdout and an unimplemented function
+const WASI_CAPABILITIES_MODULE: &str = r#"
+    (module
+      (import "env" "memory" (memory 1))
+      (import "wrap" "__wrap_invoke_result" (func $result (param i32 i32)))
+      (import "wasi_snapshot_preview1" "clock_time_get" (func $clock_time_get (param i32 i64 i32) (result i32)))
+      (import "wasi_snapshot_preview1" "random_get" (func $random_get (param i32 i32) (result i32)))
+      (import "wasi_snapshot_preview1" "fd_write" (func $fd_write (param i32 i32 i32 i32) (result i32)))
+      (import "wasi_snapshot_preview1" "poll_oneoff" (func $poll_oneoff (param i32 i32 i32 i32) (result i32)))
+      (data (i32.const 64) "hello\n")
+      (func (export "_wrap_invoke") (param i32 i32 i32) (result i32)
+        (i32.store (i32.const 32) (i32.const 64))
+        (i32.store (i32.const 36) (i32.const 6))
+        (i32.store8 (i32.const 0) (call $clock_time_get (i32.const 0) (i64.const 0) (i32.const 8)))
+        (i32.store8 (i32.const 1) (call $random_get (i32.const 16) (i32.const 8)))
+        (i32.store8 (i32.const 2) (call $fd_write (i32.const 1) (i32.const 32) (i32.const 1) (i32.const 40)))
+        (i32.store8 (i32.const 3) (call $poll_oneoff (i32.const 0) (i32.const 0) (i32.const 0) (i32.const 40)))
+        (call $result (i32.const 0) (i32.const 4))
+        (i32.const 1)))
+    "#;
+
+#[test]
+fn invoke_with_wasi_denied_by_default() {
+    let module_bytes = wat::parse_str(WASI_CAPABILITIES_MODULE).unwrap();
+
+    for engine in engines() {
+        let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+            &module_bytes,
+            engine.as_ref(),
+            Arc::new(SimpleFileReader::new()),
+        )
+        .unwrap();
+        let mock_invoker = MockInvoker::new(wrapper.clone());
+
+        let result = wrapper
+            .invoke("method", None, None, Arc::new(mock_invoker))
+            .unwrap();
+
+        // ENOTCAPABLE, ENOTCAPABLE, EBADF, ENOSYS
+        assert_eq!(result, [76, 76, 8, 52]);
+    }
+}
+
+#[test]
+fn invoke_with_wasi_policy() {
+    let module_bytes = wat::parse_str(WASI_CAPABILITIES_MODULE).unwrap();
+
+    for engine in engines() {
+        let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+            &module_bytes,
+            engine.as_ref(),
+            Arc::new(SimpleFileReader::new()),
+        )
+        .unwrap();
+        let mock_invoker = MockInvoker::new(wrapper.clone()).with_wasi_policy(WasiPolicy {
+            clock: true,
+            random: true,
+            ..Default::default()
+        });
+
+        let result = wrapper
+            .invoke("method", None, None, Arc::new(mock_invoker))
+            .unwrap();
+
+        assert_eq!(result, [0, 0, 8, 52]);
+    }
+}
+
+// Writes "hello" to out.txt in the preopened directory, then tries to create a file outside of it
+const WASI_PREOPENED_DIR_MODULE: &str = r#"
+    (module
+      (import "env" "memory" (memory 1))
+      (import "wrap" "__wrap_invoke_result" (func $result (param i32 i32)))
+      (import "wasi_snapshot_preview1" "path_open" (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+      (import "wasi_snapshot_preview1" "fd_write" (func $fd_write (param i32 i32 i32 i32) (result i32)))
+      (import "wasi_snapshot_preview1" "fd_close" (func $fd_close (param i32) (result i32)))
+      (data (i32.const 64) "hello\n")
+      (data (i32.const 128) "out.txt")
+      (data (i32.const 144) "../out.txt")
+      (func (export "_wrap_invoke") (param i32 i32 i32) (result i32)
+        (i32.store (i32.const 32) (i32.const 64))
+        (i32.store (i32.const 36) (i32.const 6))
+        (i32.store8 (i32.const 0)
+          (call $path_open (i32.const 3) (i32.const 0) (i32.const 128) (i32.const 7)
+            (i32.const 9) (i64.const 64) (i64.const 0) (i32.const 0) (i32.const 8)))
+        (i32.store8 (i32.const 1)
+          (call $fd_write (i32.load (i32.const 8)) (i32.const 32) (i32.const 1) (i32.const 40)))
+        (i32.store8 (i32.const 2) (call $fd_close (i32.load (i32.const 8))))
+        (i32.store8 (i32.const 3)
+          (call $path_open (i32.const 3) (i32.const 0) (i32.const 144) (i32.const 10)
+            (i32.const 9) (i64.const 64) (i64.const 0) (i32.const 0) (i32.const 12)))
+        (call $result (i32.const 0) (i32.const 4))
+        (i32.const 1)))
+    "#;
+
+#[test]
+fn invoke_with_wasi_preopened_dir() {
+    let module_bytes = wat::parse_str(WASI_PREOPENED_DIR_MODULE).unwrap();
+
+    for (index, engine) in engines().into_iter().enumerate() {
+        let dir =
+            std::env::temp_dir().join(format!("polywrap_wasi_{}_{index}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let wrapper = WasmWrapper::try_from_bytecode_with_engine(
+            &module_bytes,
+            engine.as_ref(),
+            Arc::new(SimpleFileReader::new()),
+        )
+        .unwrap();
+        let invoke = |read_only| {
+            let mock_invoker = MockInvoker::new(wrapper.clone()).with_wasi_policy(WasiPolicy {
+                preopened_dirs: vec![PreopenedDir {
+                    host_path: dir.clone(),
+                    guest_path: "/data".to_string(),
+                    read_only,
+                }],
+                ..Default::default()
+            });
+
+            wrapper
+                .invoke("method", None, None, Arc::new(mock_invoker))
+                .unwrap()
+        };
+
+        // EBADF as the file descriptor was never opened
+        assert_eq!(invoke(true), [76, 8, 8, 76]);
+        assert!(!dir.join("out.txt").exists());
+
+        assert_eq!(invoke(false), [0, 0, 0, 76]);
+        assert_eq!(fs::read_to_string(dir.join("out.txt")).unwrap(), "hello\n");
+        assert!(!dir.parent().unwrap().join("out.txt").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }